@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate criterion;
+extern crate test_data_generation;
+
+use criterion::Criterion;
+use test_data_generation::data_sample_parser::DataSampleParser;
+use test_data_generation::Profile;
+
+// Benchmarks the generation of data from a profile that analyzed the 15K names of the sample-names.csv file
+fn generate_benchmark(c: &mut Criterion) {
+	let mut dsp = DataSampleParser::new();
+	dsp.analyze_csv_file(&String::from("./tests/samples/sample-names.csv")).unwrap();
+
+	let mut profile = Profile::new();
+	for name in ["Pernell", "Vicky", "Claudie", "Shoshana", "Kipling", "Longstreet", "MacAlroy", "Oscroft"].iter() {
+		profile.analyze(name);
+	}
+	profile.pre_generate();
+
+	c.bench_function("profile generate (8 names)", |b| b.iter(|| profile.generate().unwrap()));
+	c.bench_function("profile generate_from_pattern (8 names)", |b| b.iter(|| profile.generate_from_pattern("Cvccvcc".to_string()).unwrap()));
+	c.bench_function("dsp generate_record (15k names)", |b| b.iter(|| dsp.generate_record().unwrap()));
+}
+
+criterion_group!{
+	name = benches;
+	config = Criterion::default().sample_size(20);
+	targets = generate_benchmark
+}
+criterion_main!(benches);
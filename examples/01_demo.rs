@@ -11,6 +11,6 @@ fn main() {
 		let dsp = DataSampleParser::new();
 
 		// generate some test data using the demo functions
-		println!("generate date:{}", dsp.demo_date());
-		println!("generate person:{}", dsp.demo_person_name());
+		println!("generate date:{}", dsp.demo_date().unwrap());
+		println!("generate person:{}", dsp.demo_person_name().unwrap());
 }
\ No newline at end of file
@@ -13,5 +13,5 @@ fn main() {
         dsp.analyze_csv_file(&String::from("./tests/samples/sample-names.csv")).unwrap();
 
         // generate some test data using the demo functions
-        println!("My new name is {} {}", dsp.generate_record()[0], dsp.generate_record()[1]);
+        println!("My new name is {} {}", dsp.generate_record().unwrap()[0], dsp.generate_record().unwrap()[1]);
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ fn main() {
 		let mut profile = match Path::new(profile_file).exists() {
           true => {
             // use existing file
-            Profile::from_file(profile_file)
+            Profile::from_file(profile_file).unwrap()
           },
           false => {
             // create new file
@@ -26,10 +26,10 @@ fn main() {
         profile.analyze("Jonathon");
         profile.pre_generate();
         profile.save(&profile_file).unwrap();
-        println!("My new name is {}", profile.generate().to_string());
+        println!("My new name is {}", profile.generate().unwrap());
 
         // analyze the second data set and add it to the saved profile.
-        let mut profile2 = Profile::from_file(profile_file);
+        let mut profile2 = Profile::from_file(profile_file).unwrap();
         profile2.analyze("Chris");
         profile2.analyze("Kris");
         profile2.analyze("Christopher");
@@ -37,10 +37,10 @@ fn main() {
         profile2.analyze("Krissy");
         profile2.pre_generate();
         profile2.save(&profile_file).unwrap();
-        println!("My new name is {}", profile2.generate().to_string());
+        println!("My new name is {}", profile2.generate().unwrap());
 
         // analyze the third data set and add it to the saved profile.
-        let mut profile3 = Profile::from_file(profile_file);
+        let mut profile3 = Profile::from_file(profile_file).unwrap();
         profile3.analyze("Dan");
         profile3.analyze("Danny");
         profile3.analyze("Danyl");
@@ -48,5 +48,5 @@ fn main() {
         profile3.analyze("Danathon");
         profile3.pre_generate();
         profile3.save(&profile_file).unwrap();
-        println!("My new name is {}", profile3.generate().to_string());
+        println!("My new name is {}", profile3.generate().unwrap());
 }
\ No newline at end of file
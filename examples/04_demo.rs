@@ -0,0 +1,36 @@
+extern crate test_data_generation;
+
+use std::thread;
+use test_data_generation::{Profile};
+ 
+fn main() {
+        // This example demonstrates the ability to build profiles in parallel and merge them into a single profile. 
+        let data_sets = vec!(
+            vec!("Jonny", "Jon", "Johnathon", "John", "Jonathon"),
+            vec!("Chris", "Kris", "Christopher", "Christian", "Krissy"),
+            vec!("Dan", "Danny", "Danyl", "Dannie", "Danathon"),
+        );
+
+        // analyze each data set in its own thread
+        let jobs = data_sets.into_iter().map(|data_set| {
+            thread::spawn(move || {
+                let mut profile = Profile::new();
+
+                for name in data_set {
+                    profile.analyze(name);
+                }
+
+                profile
+            })
+        }).collect::<Vec<_>>();
+
+        // merge the profiles into one profile
+        let mut profile = Profile::new_with_id("demo-04".to_string());
+
+        for job in jobs {
+            profile.merge(&job.join().unwrap());
+        }
+
+        println!("The merged profile analyzed {} names", profile.pattern_total);
+        println!("My new name is {}", profile.generate().unwrap());
+}
@@ -13,18 +13,56 @@
 //! fn main() {
 //!		// initalize a new Configs
 //!		let mut cfg = Configs::new(&String::from("./tests/config/tdg.yaml"));
-//!		cfg.load_config_file();
+//!		cfg.load_config_file().unwrap();
 //!
 //!		// verify the configuration file has been loaded
 //!		println!("{:?}", cfg);
 //! }
 //! ```
+//!
+//! The configuration file can define the symbol classes that are used to analyze the data, (see the `engine::SymbolClass`).
+//!
+//! ```yaml
+//! symbol-classes:
+//!   - name: HexDigit
+//!     symbol: h
+//!     chars: "0123456789abcdef"
+//!     priority: 1
+//!   - name: Currency
+//!     symbol: "¤"
+//!     regex: "\\p{Sc}"
+//!     priority: 1
+//! ```
+//!
+//! The configuration file can also define the dialect of the csv data, (see the `CsvDialect`).
+//!
+//! ```yaml
+//! csv-dialect:
+//!   delimiter: "|"
+//!   quote: "'"
+//!   escape: "\\"
+//! ```
 
 //use std::path::Path;
 use std::fs::File;
 use std::io::prelude::*;
 use yaml_rust::YamlLoader;
 use serde_json;
+use serde_yaml;
+use crate::csv_dialect::CsvDialect;
+use crate::engine::SymbolClass;
+use crate::Error;
+
+#[derive(Deserialize, Debug, Default)]
+// Represents the settings of the configuration file that are used by the library
+struct ConfigFile {
+	/// the user-defined symbol classes
+	#[serde(default, rename = "symbol-classes")]
+	symbol_classes: Vec<SymbolClass>,
+	/// the dialect of the csv data
+	#[serde(default, rename = "csv-dialect")]
+	csv_dialect: Option<CsvDialect>,
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 // Represents a Configs object that can be set by an implementation of the test data generation library
@@ -50,7 +88,7 @@ impl Configs {
 	/// fn main() {
 	///		// initalize a new Configs
 	///		let mut cfg = Configs::new(&String::from("./tests/config/tdg.yaml"));
-	///		cfg.load_config_file();
+	///		cfg.load_config_file().unwrap();
 	///
 	///		// verify the configuration file has been loaded
 	///		println!("{:?}", cfg);
@@ -74,13 +112,13 @@ impl Configs {
 	///
 	/// fn main() {
 	///		let serialized = "{\"file\":\"./tests/config/tdg.yaml\"}";
-    ///		let mut cfg = Configs::from_serialized(&serialized);
+    ///		let mut cfg = Configs::from_serialized(&serialized).unwrap();
     ///
     ///		assert_eq!(cfg.get_config_file_path(), "./tests/config/tdg.yaml");
 	/// }
     /// ```
-	pub fn from_serialized(serialized: &str) -> Configs {
-		serde_json::from_str(&serialized).unwrap()
+	pub fn from_serialized(serialized: &str) -> Result<Configs, Error> {
+		serde_json::from_str(serialized).map_err(Error::from)
 	}
 
 	/// Loads the configuration file using the path that was provided during calling a new Configs object
@@ -116,18 +154,86 @@ impl Configs {
 	/// fn main() {
 	///		// initalize a new Configs
 	///		let mut cfg = Configs::new(&String::from("./tests/config/tdg.yaml"));
-	///		cfg.load_config_file();
+	///		cfg.load_config_file().unwrap();
 	///
 	///		// verify the configuration file has been loaded
 	///		println!("{:?}", cfg);
 	/// }
 	/// ```
-	pub fn load_config_file(&mut self){
-		let mut f = File::open(&self.file).expect(&format!("Error: Configuration file not found at {}", &self.file.to_string()));
-		let mut contents = String::new();
-		f.read_to_string(&mut contents).expect("Something went wrong reading file");
-		let _cfg_yaml = &YamlLoader::load_from_str(&*contents).expect("failed to load YAML file")[0];
+	pub fn load_config_file(&mut self) -> Result<(), Error> {
+		let contents = self.read_config_file()?;
+		let _cfg_yaml = YamlLoader::load_from_str(&*contents).map_err(|e| {
+			error!("Failed to load YAML file {}", &self.file.to_string());
+			Error::Config(format!("Failed to load YAML file {} because of {}", &self.file.to_string(), e))
+		})?;
 		//println!("{:?}", cfg);
+
+		Ok(())
+	}
+
+	/// This function returns the user-defined symbol classes (the `symbol-classes` setting) of the configuration file.
+	/// If the setting is missing, no symbol classes are returned.
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::configs::Configs;
+	///
+	/// fn main() {
+	///		let cfg = Configs::new(&String::from("./tests/config/tdg-symbol-classes.yaml"));
+	///		let classes = cfg.symbol_classes().unwrap();
+	///
+	///		assert_eq!(classes[0].name, "HexDigit");
+	/// }
+	/// ```
+	pub fn symbol_classes(&self) -> Result<Vec<SymbolClass>, Error> {
+		Ok(self.read_settings("symbol classes")?.symbol_classes)
+	}
+
+	/// This function returns the dialect of the csv data (the `csv-dialect` setting) of the configuration file.
+	/// If the setting is missing, None is returned. The missing settings of the dialect have their default values.
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::configs::Configs;
+	///
+	/// fn main() {
+	///		let cfg = Configs::new(&String::from("./tests/config/tdg-csv-dialect.yaml"));
+	///		let dialect = cfg.csv_dialect().unwrap().unwrap();
+	///
+	///		assert_eq!(dialect.delimiter, '|');
+	/// }
+	/// ```
+	pub fn csv_dialect(&self) -> Result<Option<CsvDialect>, Error> {
+		Ok(self.read_settings("csv dialect")?.csv_dialect)
+	}
+
+	/// This function reads the settings of the configuration file
+	fn read_settings(&self, setting: &str) -> Result<ConfigFile, Error> {
+		let contents = self.read_config_file()?;
+		let cfg_file: Option<ConfigFile> = serde_yaml::from_str(&contents).map_err(|e| {
+			error!("Failed to read the {} of the YAML file {}", setting, &self.file.to_string());
+			Error::Config(format!("Failed to read the {} of the YAML file {} because of {}", setting, &self.file.to_string(), e))
+		})?;
+
+		Ok(cfg_file.unwrap_or_default())
+	}
+
+	/// This function reads the contents of the configuration file
+	fn read_config_file(&self) -> Result<String, Error> {
+		let mut f = File::open(&self.file).map_err(|_e| {
+			error!("Configuration file not found at {}", &self.file.to_string());
+			Error::Config(format!("Configuration file not found at {}", &self.file.to_string()))
+		})?;
+		let mut contents = String::new();
+		f.read_to_string(&mut contents)?;
+
+		Ok(contents)
 	}
 
 	/// This function converts the Configs object to a serialize JSON string.
@@ -142,7 +248,7 @@ impl Configs {
 	/// fn main() {
 	/// 	//create a Configs object from a configuration file
     ///    	let mut cfg =  Configs::new(&String::from("./tests/config/tdg.yaml"));
-    ///		cfg.load_config_file();
+    ///		cfg.load_config_file().unwrap();
     ///
     ///     println!("{}", cfg.serialize());
     ///     // {"key":"r","prior_key":null,"next_key":null,"pattern_placeholder":"c","starts_with":0,"ends_with":0,"index_offset":2}
@@ -162,22 +268,53 @@ mod tests {
     fn create_config_good_cfg_file(){
     	let mut cfg =  Configs::new(&String::from("./tests/config/tdg.yaml"));
 
-    	cfg.load_config_file();
+    	assert!(cfg.load_config_file().is_ok());
     }
 
     #[test]
-    #[should_panic(expected = "Error: Configuration file not found at ./badpath/tdg.yaml")]
     // ensure Configs errors when reading an invalid configuration file
     fn create_config_bad_cfg_file(){
     	let mut cfg =  Configs::new(&String::from("./badpath/tdg.yaml"));
 
-    	cfg.load_config_file();
+    	match cfg.load_config_file() {
+    		Err(Error::Config(msg)) => assert_eq!(msg, "Configuration file not found at ./badpath/tdg.yaml"),
+    		_ => panic!("expected a configuration error"),
+    	}
+    }
+
+    #[test]
+    // ensure Configs reads the symbol classes of a configuration file
+    fn symbol_classes(){
+    	let cfg =  Configs::new(&String::from("./tests/config/tdg-symbol-classes.yaml"));
+    	let classes = cfg.symbol_classes().unwrap();
+
+    	assert_eq!(classes.len(), 3);
+    	assert_eq!(classes[1].symbol, '¤');
+    	assert_eq!(classes[1].regex, Some(String::from("\\p{Sc}")));
+    	assert_eq!(classes[2].priority, -1);
+    }
+
+    #[test]
+    // ensure Configs returns no symbol classes if the configuration file doesn't define any
+    fn symbol_classes_missing(){
+    	let cfg =  Configs::new(&String::from("./tests/config/tdg.yaml"));
+
+    	assert!(cfg.symbol_classes().unwrap().is_empty());
+    }
+
+    #[test]
+    // ensure Configs reads the csv dialect of a configuration file
+    fn csv_dialect(){
+    	let cfg =  Configs::new(&String::from("./tests/config/tdg-csv-dialect.yaml"));
+
+    	assert_eq!(cfg.csv_dialect().unwrap(), Some(CsvDialect::new().with_delimiter('|').with_quote('\'').with_escape('\\').with_trim(true)));
+    	assert_eq!(Configs::new(&String::from("./tests/config/tdg.yaml")).csv_dialect().unwrap(), None);
     }
 
     #[test]
     fn new_fact_from_serialized(){
     	let serialized = "{\"file\":\"./tests/config/tdg.yaml\"}";
-    	let cfg = Configs::from_serialized(&serialized);
+    	let cfg = Configs::from_serialized(&serialized).unwrap();
 
     	assert_eq!(cfg.get_config_file_path(), "./tests/config/tdg.yaml");
     }
@@ -186,7 +323,7 @@ mod tests {
     // ensure a Configs object can be exported (to be archived) as JSON
     fn serialize(){
         let mut cfg =  Configs::new(&String::from("./tests/config/tdg.yaml"));
-        cfg.load_config_file();
+        cfg.load_config_file().unwrap();
 
     	let serialized = cfg.serialize();
     	println!("serialized : {}",serialized);
@@ -0,0 +1,309 @@
+//! The `constraints` module provides the `Constraints` that generated data must satisfy, (e.g.: the maximum length and validation rules of a database column).
+//! The length and char constraints are applied by restricting the patterns, formats, tokens, run lengths and chars that are selected while generating data,
+//! (see _Profile::generate_with()_). The regular expression and the forbidden values can only be checked after the data is generated,
+//! so a value that doesn't satisfy them is regenerated.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::{Constraints, Profile};
+//!
+//! fn main() {
+//!		let mut profile = Profile::new();
+//!		profile.analyze("Smith, John");
+//!		profile.analyze("O'Brian, Henny");
+//!		profile.analyze("Dale, Danny");
+//!		profile.analyze("Rickets, Ronney");
+//!		profile.pre_generate();
+//!
+//!		let constraints = Constraints::new()
+//!			.with_max_length(14)
+//!			.with_regex("^[A-Z]").unwrap()
+//!			.with_forbidden_chars(&['x', 'z']);
+//!
+//!		let generated = profile.generate_with(&constraints).unwrap();
+//!		assert!(generated.chars().count() <= 14);
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+use crate::Error;
+
+/// The default number of times a value that doesn't satisfy the constraints is regenerated before an error is returned
+pub const DEFAULT_RETRIES: u32 = 100;
+
+#[derive(Clone, Debug)]
+/// Represents the constraints that generated data must satisfy
+pub struct Constraints {
+	/// The minimum length (number of chars) of the generated data
+	pub min_length: Option<usize>,
+	/// The maximum length (number of chars) of the generated data
+	pub max_length: Option<usize>,
+	/// The regular expression that the generated data must match
+	pub regex: Option<Regex>,
+	/// The pattern placeholders (character classes, e.g.: 'C', 'v', '#') that the generated data may contain (None = all)
+	pub char_classes: Option<BTreeSet<char>>,
+	/// The chars that the generated data must not contain
+	pub forbidden_chars: BTreeSet<char>,
+	/// The values that must not be generated
+	pub forbidden_values: BTreeSet<String>,
+	/// The number of times a value that doesn't satisfy the constraints is regenerated before an error is returned
+	pub retries: u32,
+}
+
+impl Default for Constraints {
+	fn default() -> Self {
+		Constraints::new()
+	}
+}
+
+impl Constraints {
+	/// Constructs a new Constraints that doesn't constrain the generated data
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Constraints;
+	///
+	/// fn main() {
+	///		let constraints = Constraints::new();
+	///
+	///		assert!(constraints.allows_value("anything"));
+	/// }
+	/// ```
+	pub fn new() -> Constraints {
+		Constraints {
+			min_length: None,
+			max_length: None,
+			regex: None,
+			char_classes: None,
+			forbidden_chars: BTreeSet::new(),
+			forbidden_values: BTreeSet::new(),
+			retries: DEFAULT_RETRIES,
+		}
+	}
+
+	/// This function sets the minimum length (number of chars) of the generated data
+	///
+	/// # Arguments
+	///
+	/// * `len: usize` - The minimum length.</br>
+	pub fn with_min_length(mut self, len: usize) -> Constraints {
+		self.min_length = Some(len);
+		self
+	}
+
+	/// This function sets the maximum length (number of chars) of the generated data
+	///
+	/// # Arguments
+	///
+	/// * `len: usize` - The maximum length.</br>
+	pub fn with_max_length(mut self, len: usize) -> Constraints {
+		self.max_length = Some(len);
+		self
+	}
+
+	/// This function sets the regular expression that the generated data must match
+	///
+	/// # Arguments
+	///
+	/// * `regex: &str` - The regular expression.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Constraints;
+	///
+	/// fn main() {
+	///		assert!(Constraints::new().with_regex("^[0-9]+$").is_ok());
+	///		assert!(Constraints::new().with_regex("[0-9").is_err());
+	/// }
+	/// ```
+	pub fn with_regex(mut self, regex: &str) -> Result<Constraints, Error> {
+		self.regex = Some(Regex::new(regex).map_err(|e| Error::Parse(e.to_string()))?);
+		Ok(self)
+	}
+
+	/// This function sets the pattern placeholders (character classes) that the generated data may contain
+	///
+	/// # Arguments
+	///
+	/// * `classes: &[char]` - The pattern placeholders (e.g.: 'C', 'c', 'V', 'v', '#').</br>
+	pub fn with_char_classes(mut self, classes: &[char]) -> Constraints {
+		self.char_classes = Some(classes.iter().cloned().collect());
+		self
+	}
+
+	/// This function sets the chars that the generated data must not contain
+	///
+	/// # Arguments
+	///
+	/// * `chars: &[char]` - The forbidden chars.</br>
+	pub fn with_forbidden_chars(mut self, chars: &[char]) -> Constraints {
+		self.forbidden_chars.extend(chars.iter());
+		self
+	}
+
+	/// This function sets the values that must not be generated
+	///
+	/// # Arguments
+	///
+	/// * `values: &[&str]` - The forbidden values.</br>
+	pub fn with_forbidden_values(mut self, values: &[&str]) -> Constraints {
+		self.forbidden_values.extend(values.iter().map(|v| v.to_string()));
+		self
+	}
+
+	/// This function sets the number of times a value that doesn't satisfy the constraints is regenerated before an error is returned
+	///
+	/// # Arguments
+	///
+	/// * `retries: u32` - The number of retries.</br>
+	pub fn with_retries(mut self, retries: u32) -> Constraints {
+		self.retries = retries;
+		self
+	}
+
+	/// This function returns the range of the lengths (number of chars) that the generated data may have
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Constraints;
+	///
+	/// fn main() {
+	///		assert_eq!(Constraints::new().with_min_length(2).with_max_length(5).lengths(), 2..=5);
+	///		assert_eq!(Constraints::new().lengths(), 0..=usize::MAX);
+	/// }
+	/// ```
+	pub fn lengths(&self) -> RangeInclusive<usize> {
+		self.min_length.unwrap_or(0)..=self.max_length.unwrap_or(usize::MAX)
+	}
+
+	/// This function returns the constraints of a part of the generated data (e.g.: a token), which have the length bounds of the part
+	/// and the char constraints, but not the regular expression and the forbidden values, since those only apply to the whole value
+	///
+	/// # Arguments
+	///
+	/// * `lengths: RangeInclusive<usize>` - The range of the lengths of the part.</br>
+	pub(crate) fn for_part(&self, lengths: RangeInclusive<usize>) -> Constraints {
+		Constraints {
+			min_length: Some(*lengths.start()),
+			max_length: Some(*lengths.end()),
+			regex: None,
+			char_classes: self.char_classes.clone(),
+			forbidden_chars: self.forbidden_chars.clone(),
+			forbidden_values: BTreeSet::new(),
+			retries: self.retries,
+		}
+	}
+
+	/// This function returns true if data generated from the pattern can satisfy the length and character class constraints
+	///
+	/// # Arguments
+	///
+	/// * `pattern: &str` - The symbolic pattern.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Constraints;
+	///
+	/// fn main() {
+	///		let constraints = Constraints::new().with_max_length(3).with_char_classes(&['C', 'v']);
+	///
+	///		assert!(constraints.allows_pattern("Cv"));
+	///		assert!(!constraints.allows_pattern("Cvc"));
+	///		assert!(!constraints.allows_pattern("CvCv"));
+	/// }
+	/// ```
+	pub fn allows_pattern(&self, pattern: &str) -> bool {
+		let len = pattern.chars().count();
+
+		self.min_length.iter().all(|min| len >= *min) &&
+		self.max_length.iter().all(|max| len <= *max) &&
+		self.char_classes.iter().all(|classes| pattern.chars().all(|c| classes.contains(&c)))
+	}
+
+	/// This function returns true if the char can be generated
+	///
+	/// # Arguments
+	///
+	/// * `c: char` - The char.</br>
+	pub fn allows_char(&self, c: char) -> bool {
+		!self.forbidden_chars.contains(&c)
+	}
+
+	/// This function returns true if the generated value satisfies the regular expression and isn't a forbidden value
+	///
+	/// # Arguments
+	///
+	/// * `value: &str` - The generated value.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Constraints;
+	///
+	/// fn main() {
+	///		let constraints = Constraints::new().with_regex("^[A-Z]").unwrap().with_forbidden_values(&["Admin"]);
+	///
+	///		assert!(constraints.allows_value("Bob"));
+	///		assert!(!constraints.allows_value("bob"));
+	///		assert!(!constraints.allows_value("Admin"));
+	/// }
+	/// ```
+	pub fn allows_value(&self, value: &str) -> bool {
+		!self.forbidden_values.contains(value) &&
+		self.regex.iter().all(|r| r.is_match(value)) &&
+		value.chars().all(|c| self.allows_char(c))
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn allows_pattern_by_length(){
+		let constraints = Constraints::new().with_min_length(2).with_max_length(3);
+
+		assert!(!constraints.allows_pattern("C"));
+		assert!(constraints.allows_pattern("Cv"));
+		assert!(constraints.allows_pattern("Cvc"));
+		assert!(!constraints.allows_pattern("Cvcv"));
+	}
+
+	#[test]
+	fn allows_char(){
+		let constraints = Constraints::new().with_forbidden_chars(&['\'', '"']);
+
+		assert!(constraints.allows_char('a'));
+		assert!(!constraints.allows_char('"'));
+		assert!(!constraints.allows_value("O'Brian"));
+	}
+
+	#[test]
+	fn bad_regex(){
+		match Constraints::new().with_regex("(") {
+			Err(Error::Parse(_)) => {},
+			_ => panic!("expected a parse error"),
+		}
+	}
+}
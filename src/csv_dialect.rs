@@ -0,0 +1,310 @@
+//! The `csv_dialect` module provides the `CsvDialect`, which describes how the csv data is delimited, quoted and escaped,
+//! so that csv files that aren't comma-delimited (e.g.: pipe- or tab-delimited extracts) can be analyzed and generated.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::CsvDialect;
+//! use test_data_generation::data_sample_parser::DataSampleParser;
+//!
+//! fn main() {
+//!		let mut dsp = DataSampleParser::new();
+//!		dsp.set_csv_dialect(CsvDialect::new().with_delimiter('|').with_quote('\''));
+//!
+//!		dsp.analyze_csv_data(&String::from("firstname|lastname\n'Aaron'|'Aaberg'\n'Abbey'|'Aadland'")).unwrap();
+//!
+//!		assert_eq!(dsp.extract_headers(), vec!["firstname", "lastname"]);
+//! }
+//! ```
+//!
+//! The dialect can also be set in the `csv-dialect` setting of the configuration file.
+//!
+//! ```yaml
+//! csv-dialect:
+//!   delimiter: "\t"
+//!   quote: "'"
+//!   escape: "\\"
+//!   trim: true
+//!   comment: "#"
+//! ```
+
+use csv::{ReaderBuilder, Terminator, Trim, WriterBuilder};
+
+use crate::Error;
+
+/// This function returns the default delimiter
+fn default_delimiter() -> char {
+	','
+}
+
+/// This function returns the default quote
+fn default_quote() -> char {
+	'"'
+}
+
+/// This function returns true
+fn default_true() -> bool {
+	true
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents the dialect of csv data. The default dialect is comma-delimited, with a header row, and double quotes that are escaped by doubling them.
+pub struct CsvDialect {
+	/// The char that separates the fields
+	#[serde(default = "default_delimiter")]
+	pub delimiter: char,
+	/// The char that quotes the fields
+	#[serde(default = "default_quote")]
+	pub quote: char,
+	/// The char that escapes a quote in a quoted field (None = a quote is escaped by doubling it)
+	#[serde(default)]
+	pub escape: Option<char>,
+	/// The char that terminates the records (None = any of `\r`, `\n` or `\r\n` when reading, and `\n` when writing)
+	#[serde(default)]
+	pub terminator: Option<char>,
+	/// If true, the leading and trailing white spaces of the headers and fields are removed when reading
+	#[serde(default)]
+	pub trim: bool,
+	/// If true, the first record is the header row
+	#[serde(default = "default_true", rename = "has-headers")]
+	pub has_headers: bool,
+	/// The char that starts a comment line, which is ignored when reading (None = no comments)
+	#[serde(default)]
+	pub comment: Option<char>,
+}
+
+impl Default for CsvDialect {
+	fn default() -> Self {
+		CsvDialect::new()
+	}
+}
+
+impl CsvDialect {
+	/// Constructs a new CsvDialect with the default settings
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::CsvDialect;
+	///
+	/// fn main() {
+	///		let dialect = CsvDialect::new();
+	///
+	///		assert_eq!(dialect.delimiter, ',');
+	///		assert_eq!(dialect.has_headers, true);
+	/// }
+	/// ```
+	pub fn new() -> CsvDialect {
+		CsvDialect {
+			delimiter: default_delimiter(),
+			quote: default_quote(),
+			escape: None,
+			terminator: None,
+			trim: false,
+			has_headers: true,
+			comment: None,
+		}
+	}
+
+	/// This function sets the char that separates the fields
+	///
+	/// # Arguments
+	///
+	/// * `delimiter: char` - The delimiter, (e.g.: '|' or '\t').</br>
+	pub fn with_delimiter(mut self, delimiter: char) -> Self {
+		self.delimiter = delimiter;
+		self
+	}
+
+	/// This function sets the char that quotes the fields
+	///
+	/// # Arguments
+	///
+	/// * `quote: char` - The quote, (e.g.: '\'').</br>
+	pub fn with_quote(mut self, quote: char) -> Self {
+		self.quote = quote;
+		self
+	}
+
+	/// This function sets the char that escapes a quote in a quoted field, instead of doubling the quote
+	///
+	/// # Arguments
+	///
+	/// * `escape: char` - The escape, (e.g.: '\\').</br>
+	pub fn with_escape(mut self, escape: char) -> Self {
+		self.escape = Some(escape);
+		self
+	}
+
+	/// This function sets the char that terminates the records
+	///
+	/// # Arguments
+	///
+	/// * `terminator: char` - The terminator, (e.g.: ';').</br>
+	pub fn with_terminator(mut self, terminator: char) -> Self {
+		self.terminator = Some(terminator);
+		self
+	}
+
+	/// This function sets if the white spaces around the headers and fields are removed when reading
+	///
+	/// # Arguments
+	///
+	/// * `trim: bool` - If true, the white spaces are removed.</br>
+	pub fn with_trim(mut self, trim: bool) -> Self {
+		self.trim = trim;
+		self
+	}
+
+	/// This function sets if the first record is the header row
+	///
+	/// # Arguments
+	///
+	/// * `has_headers: bool` - If true, the first record is the header row.</br>
+	pub fn with_headers(mut self, has_headers: bool) -> Self {
+		self.has_headers = has_headers;
+		self
+	}
+
+	/// This function sets the char that starts a comment line
+	///
+	/// # Arguments
+	///
+	/// * `comment: char` - The comment char, (e.g.: '#').</br>
+	pub fn with_comment(mut self, comment: char) -> Self {
+		self.comment = Some(comment);
+		self
+	}
+
+	/// This function returns the csv::ReaderBuilder that reads csv data of the dialect
+	///
+	/// #Errors
+	/// If a char of the dialect isn't a single byte (ASCII) char, an Error::Config is returned.
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::CsvDialect;
+	///
+	/// fn main() {
+	///		let dialect = CsvDialect::new().with_delimiter('\t').with_headers(false);
+	///		let mut rdr = dialect.reader_builder().unwrap().from_reader("Aaron\tAaberg".as_bytes());
+	///
+	///		assert_eq!(rdr.records().next().unwrap().unwrap().get(1), Some("Aaberg"));
+	///		assert!(CsvDialect::new().with_delimiter('¦').reader_builder().is_err());
+	/// }
+	/// ```
+	pub fn reader_builder(&self) -> Result<ReaderBuilder, Error> {
+		let mut builder = ReaderBuilder::new();
+		builder.delimiter(byte("delimiter", self.delimiter)?)
+			.quote(byte("quote", self.quote)?)
+			.double_quote(self.escape.is_none())
+			.escape(self.escape.map(|e| byte("escape", e)).transpose()?)
+			.trim(if self.trim { Trim::All } else { Trim::None })
+			.has_headers(self.has_headers)
+			.comment(self.comment.map(|c| byte("comment", c)).transpose()?);
+
+		if let Some(terminator) = self.terminator {
+			builder.terminator(Terminator::Any(byte("terminator", terminator)?));
+		}
+
+		Ok(builder)
+	}
+
+	/// This function returns the csv::WriterBuilder that writes csv data of the dialect
+	///
+	/// #Errors
+	/// If a char of the dialect isn't a single byte (ASCII) char, an Error::Config is returned.
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::CsvDialect;
+	///
+	/// fn main() {
+	///		let dialect = CsvDialect::new().with_delimiter('|').with_escape('\\');
+	///		let mut wtr = dialect.writer_builder().unwrap().from_writer(vec![]);
+	///		wtr.write_record(&["O\"Brian", "a|b"]).unwrap();
+	///
+	///		assert_eq!(String::from_utf8(wtr.into_inner().unwrap()).unwrap(), "\"O\\\"Brian\"|\"a|b\"\n");
+	/// }
+	/// ```
+	pub fn writer_builder(&self) -> Result<WriterBuilder, Error> {
+		let mut builder = WriterBuilder::new();
+		builder.delimiter(byte("delimiter", self.delimiter)?)
+			.quote(byte("quote", self.quote)?)
+			.double_quote(self.escape.is_none())
+			.has_headers(self.has_headers);
+
+		if let Some(escape) = self.escape {
+			builder.escape(byte("escape", escape)?);
+		}
+
+		if let Some(terminator) = self.terminator {
+			builder.terminator(Terminator::Any(byte("terminator", terminator)?));
+		}
+
+		Ok(builder)
+	}
+}
+
+/// This function returns the byte of a single byte (ASCII) char of the dialect
+fn byte(setting: &str, c: char) -> Result<u8, Error> {
+	if c.is_ascii() {
+		Ok(c as u8)
+	} else {
+		Err(Error::Config(format!("The csv dialect {} {:?} is not a single byte (ASCII) char", setting, c)))
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn read_with_escape_and_comments(){
+		let dialect = CsvDialect::new().with_delimiter('|').with_quote('\'').with_escape('\\').with_trim(true).with_comment('#');
+		let data = "# an extract\n name | note \n'O\\'Brian'|' a|b '\n";
+		let mut rdr = dialect.reader_builder().unwrap().from_reader(data.as_bytes());
+
+		assert_eq!(rdr.headers().unwrap().iter().collect::<Vec<&str>>(), vec!["name", "note"]);
+		let record = rdr.records().next().unwrap().unwrap();
+		assert_eq!(record.iter().collect::<Vec<&str>>(), vec!["O'Brian", "a|b"]);
+	}
+
+	#[test]
+	fn terminator(){
+		let dialect = CsvDialect::new().with_terminator(';').with_headers(false);
+		let mut rdr = dialect.reader_builder().unwrap().from_reader("a,b;c,d".as_bytes());
+
+		assert_eq!(rdr.records().count(), 2);
+
+		let mut wtr = dialect.writer_builder().unwrap().from_writer(vec![]);
+		wtr.write_record(["a", "b"]).unwrap();
+		assert_eq!(wtr.into_inner().unwrap(), b"a,b;");
+	}
+
+	#[test]
+	fn not_a_byte(){
+		match CsvDialect::new().with_quote('«').writer_builder() {
+			Err(Error::Config(msg)) => assert_eq!(msg, "The csv dialect quote '«' is not a single byte (ASCII) char"),
+			_ => panic!("expected a configuration error"),
+		}
+	}
+
+	#[test]
+	fn deserialize_defaults(){
+		let dialect: CsvDialect = serde_yaml::from_str("delimiter: \"\\t\"\nhas-headers: false").unwrap();
+
+		assert_eq!(dialect, CsvDialect::new().with_delimiter('\t').with_headers(false));
+	}
+}
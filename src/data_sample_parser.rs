@@ -16,8 +16,8 @@
 //!		let dsp = DataSampleParser::new();
 //!
 //!		// generate some test data using the demo functions
-//!		println!("generate date:{}", dsp.demo_date());
-//!		println!("generate person:{}", dsp.demo_person_name());
+//!		println!("generate date:{}", dsp.demo_date().unwrap());
+//!		println!("generate person:{}", dsp.demo_person_name().unwrap());
 //! }
 //! ```
 //!
@@ -52,9 +52,9 @@
 //! use test_data_generation::data_sample_parser::DataSampleParser;
 //!
 //! fn main() {
-//!		let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
+//!		let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp")).unwrap();
 //!
-//!		assert_eq!(dsp.generate_record()[0], "OK".to_string());
+//!		assert_eq!(dsp.generate_record().unwrap()[0], "OK".to_string());
 //! }
 //! ```
 //!
@@ -76,26 +76,46 @@
 
 use std::collections::BTreeMap;
 use crate::configs::Configs;
-use crate::Profile;
-use crate::engine::{Engine, EngineContainer};
+use crate::{CsvDialect, Error, NovelMode, Profile, ProfileSummary, RegexForm};
+use crate::engine::{Engine, SymbolClass, WorkerPool};
+use crate::json_structure::{JsonFormat, JsonKind, JsonStructure, JsonValue};
+use crate::sql::{SqlColumn, SqlFormat};
 use crate::shared::CsvManipulator;
 use std::fs::File;
-use std::io;
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::io::prelude::*;
 use std::result::Result;
-use csv;
-//use csv::StringRecord;
-use std::error::Error;
-use csv::WriterBuilder;
 use serde_json;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 
-use std::sync::mpsc::{Sender, Receiver};
-use std::sync::mpsc;
-use std::thread;
 
 type ProfilesMap = BTreeMap<String, Profile>;
 
+/// Represents an (infinite) iterator of the test data records that are generated by a DataSampleParser.
+/// The iterator ends if there are no fields, or a field fails to be generated.
+pub struct RecordIter<'a> {
+	dsp: &'a mut DataSampleParser,
+}
+
+impl<'a> Iterator for RecordIter<'a> {
+	type Item = Vec<String>;
+
+	fn next(&mut self) -> Option<Vec<String>> {
+		if self.dsp.profiles.is_empty() {
+			return None;
+		}
+
+		match self.dsp.generate_record() {
+			Ok(record) => Some(record),
+			Err(e) => {
+				error!("The data sample parser stopped generating records. Error Message: {}", e);
+				None
+			},
+		}
+	}
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 /// Represents the Parser for sample data to be used
 pub struct DataSampleParser{
@@ -105,6 +125,43 @@ pub struct DataSampleParser{
 	cfg: Option<Configs>,
 	/// List of Profiles objects identified by a unique profile name BTreeMap<String, Profile>
 	profiles: ProfilesMap,
+	/// The names of the fields in the order of the columns of the analyzed data (e.g.: the header of the csv file),
+	/// which is the order of the fields of the generated records
+	#[serde(default)]
+	schema: Vec<String>,
+	/// The dialect of the analyzed and generated csv data (None = the `csv-dialect` setting of the configuration file, or the default dialect)
+	#[serde(default)]
+	dialect: Option<CsvDialect>,
+	/// The names of the columns of the analyzed csv data, which are used instead of the header row (see _set_column_names()_)
+	#[serde(default)]
+	column_names: Vec<String>,
+	/// If the generated csv files start with a header row (None = if the csv dialect has a header row)
+	#[serde(default)]
+	write_headers: Option<bool>,
+	/// The structure of the analyzed JSON documents (None if no JSON documents have been analyzed)
+	#[serde(default)]
+	structure: Option<JsonStructure>,
+	/// The format of the generated JSON documents (see _set_json_format()_)
+	#[serde(default)]
+	json_format: JsonFormat,
+	/// The format of the generated SQL (see _set_sql_format()_)
+	#[serde(default)]
+	sql_format: SqlFormat,
+	/// If the hashes of the analyzed entities are archived with the Profiles (see _set_archive_entity_hashes()_)
+	#[serde(default)]
+	archive_entity_hashes: bool,
+	/// The seed used to make the generated test data reproducible (not archived)
+	#[serde(skip)]
+	seed: Option<u64>,
+	/// The random number generator used to generate the structure of the JSON documents (not archived)
+	#[serde(skip)]
+	rng: Option<StdRng>,
+	/// The novel-value mode used when generating test data (not archived)
+	#[serde(skip)]
+	novel: Option<NovelMode>,
+	/// The pool of worker threads used to analyze the data (not archived)
+	#[serde(skip)]
+	pool: WorkerPool,
 }
 
 impl CsvManipulator for DataSampleParser {}
@@ -131,6 +188,18 @@ impl DataSampleParser {
 			issues: false,
             cfg: None,
             profiles: ProfilesMap::new(),
+            schema: Vec::new(),
+            dialect: None,
+            column_names: Vec::new(),
+            write_headers: None,
+            structure: None,
+            json_format: JsonFormat::new(),
+            sql_format: SqlFormat::new(),
+            archive_entity_hashes: false,
+            seed: None,
+            rng: None,
+            novel: None,
+            pool: WorkerPool::default(),
 		}
 	}
 
@@ -139,6 +208,7 @@ impl DataSampleParser {
 	/// # Arguments
 	///
 	/// * `path: &String - The full path name (including the file name and extension) to the configuration file.</br>
+	///   NOTE: The profiles of the analyzed fields use the symbol classes that are defined in the configuration file (see _Configs::symbol_classes()_).
 	///
 	/// #Example
 	///
@@ -158,6 +228,18 @@ impl DataSampleParser {
 			issues: false,
             cfg: Some(Configs::new(path)),
             profiles: ProfilesMap::new(),
+            schema: Vec::new(),
+            dialect: None,
+            column_names: Vec::new(),
+            write_headers: None,
+            structure: None,
+            json_format: JsonFormat::new(),
+            sql_format: SqlFormat::new(),
+            archive_entity_hashes: false,
+            seed: None,
+            rng: None,
+            novel: None,
+            pool: WorkerPool::default(),
 		}
 	}
 
@@ -175,17 +257,17 @@ impl DataSampleParser {
 	/// use test_data_generation::data_sample_parser::DataSampleParser;
 	///
 	/// fn main() {
-	///		let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
+	///		let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp")).unwrap();
     ///
-    ///		assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    ///		assert_eq!(dsp.generate_record().unwrap()[0], "OK".to_string());
 	/// }
     /// ```
-	pub fn from_file(path: &String) -> DataSampleParser {
+	pub fn from_file(path: &String) -> Result<DataSampleParser, Error> {
 		// open the archive file
 		let mut file = match File::open(format!("{}.json",&path)) {
-			Err(_e) => {
+			Err(e) => {
 				error!("Could not open file {:?}", &path.to_string());
-				panic!("Could not open file {:?}", &path.to_string());
+				return Err(Error::Io(e));
 			},
 			Ok(f) => {
 				info!("Successfully opened file {:?}", &path.to_string());
@@ -198,7 +280,7 @@ impl DataSampleParser {
 		match file.read_to_string(&mut serialized) {
 			Err(e) => {
 				error!("Could not read file {:?} because of {:?}", &path.to_string(), e.to_string());
-				panic!("Could not read file {:?} because of {:?}", &path.to_string(), e.to_string());
+				return Err(Error::Io(e));
 			},
 			Ok(s) => {
 				info!("Successfully read file {:?}", &path.to_string());
@@ -206,67 +288,42 @@ impl DataSampleParser {
 			},
 		};
 
-		serde_json::from_str(&serialized).unwrap()
+		serde_json::from_str(&serialized).map_err(|e| {
+			error!("Could not parse file {:?} because of {:?}", &path.to_string(), e.to_string());
+			Error::from(e)
+		})
 	}
 
-	fn analyze_columns(&mut self, profile_keys: Vec<String>, columns: Vec<Vec<String>>) {
-		let col_cnt = columns.len();
-		let (tx, rx): (Sender<Result<Profile, String>>, Receiver<Result<Profile, String>>) = mpsc::channel();
-		let mut jobs = Vec::new();
-		
-	    //iterate through all the columns
-	    for (idx, column) in columns.iter().enumerate() {
-			let thread_tx = tx.clone();
-			let container = EngineContainer {
-				profile: self.profiles.get(&profile_keys[idx]).unwrap().clone(),
-				entities: column.to_vec(),
-			};
-
-			let job = thread::spawn(move || {
-				let result = Self::profile_entities_with_container(container);
-				thread_tx.send(result).unwrap();
-			});
-
-			jobs.push(job);
-		}
-		
-		let mut results = Vec::with_capacity(col_cnt);
-		for _ in 0..col_cnt {
-			results.push(rx.recv());
+	fn analyze_columns(&mut self, headers: Vec<String>, columns: Vec<Vec<String>>) -> Result<(), Error> {
+		// the columns are analyzed at the same time by the worker threads of a single (bounded) scope,
+		// so no more threads than the size of the pool are running at any time
+		let mut profiles = Vec::with_capacity(headers.len());
+		for header in headers.iter().take(columns.len()) {
+			match self.profiles.get(header) {
+				Some(p) => profiles.push(p.clone()),
+				None => return Err(Error::UnknownField(header.clone())),
+			}
 		}
 
-		for job in jobs {
-			job.join().expect("Error: Could not run the job");
+		match Self::profile_columns_with_pool(profiles, columns, &self.pool) {
+			Ok(profiles) => {
+				for p in profiles {
+					let id = p.id.clone().unwrap_or_default();
+					debug!("Profile {} has finished analyzing the entities.", id);
+					self.profiles.insert(id, p);
+				}
+			},
+			Err(e) => {
+				error!("Profile wasn't able to analyzing the entities. Error: {}", e);
+				return Err(e);
+			},
 		}
 
-		for result in results {
-			match result {
-				Ok(msg) => {
-					//received from sender
-					match msg {
-						Ok(p) => {
-							let id = p.clone().id.unwrap();
-							debug!("Profile {} has finished analyzing the entities.", id);
-							self.profiles.insert(id, p);
-						},
-						Err(e) => {
-							error!("Profile wasn't able to analyzing the entities. Error: {}", e);
-						}
-					}
-					
-				},
-				Err(e) => {
-					// could not receive from sender
-					error!("Receiver wasn't able to receive message from sender which was analyzing entities for the profile. Error: {}", e);
-					panic!("Receiver wasn't able to receive message from sender which was analyzing entities for the profile. Error: {}", e);
-				},
-			}
-		}		
-		// Multi-Threading END
+		Ok(())
 	}
 
 	/// This function analyzes sample data that is a csv formatted file and returns a boolean if successful.
-	/// _NOTE:_ The csv properties are as follows:
+	/// _NOTE:_ The csv properties are defined by the csv dialect (see _set_csv_dialect()_). By default they are as follows:
 	///       + headers are included as first line
 	///       + double quote wrap text
 	///       + double quote escapes is enabled
@@ -291,24 +348,14 @@ impl DataSampleParser {
     /// 	assert_eq!(dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap(),1);
 	/// }
 	/// ```
-	pub fn analyze_csv_file(&mut self, path: &String) -> Result<i32, String>  {
+	pub fn analyze_csv_file(&mut self, path: &String) -> Result<i32, Error>  {
 		info!("Starting to analyzed the csv file {}",path);
-
-    	let mut file = (File::open(path).map_err(|e| {
-			error!("csv file {} couldn't be opened!",path);
-    		e.to_string()
-		}))?;
-
-		let mut data = String::new();
-    	file.read_to_string(&mut data).map_err(|e| {
-			error!("csv file {} couldn't be read!",path);
-    		e.to_string()
-		}).unwrap();
+		let data = Self::read_file(path)?;
 		self.analyze_csv_data(&data)
 	}
 
 	/// This function analyzes sample data that is a csv formatted string and returns a boolean if successful.
-	/// _NOTE:_ The csv properties are as follows:
+	/// _NOTE:_ The csv properties are defined by the csv dialect (see _set_csv_dialect()_). By default they are as follows:
 	///       + headers are included as first line
 	///       + double quote wrap text
 	///       + double quote escapes is enabled
@@ -340,46 +387,290 @@ impl DataSampleParser {
     /// 	assert_eq!(dsp.analyze_csv_data(&data).unwrap(),1);
 	/// }
 	/// ```
-	pub fn analyze_csv_data(&mut self, data: &String) -> Result<i32, String>  {
+	pub fn analyze_csv_data(&mut self, data: &String) -> Result<i32, Error>  {
 		debug!("Starting to analyzed the csv data {}",data);
 
-		let mut rdr = csv::ReaderBuilder::new()
-        	.has_headers(true)
-        	.quote(b'"')
-        	.double_quote(true)
-        	.delimiter(b',')
-        	.from_reader(data.as_bytes());
-
-		//iterate through the headers
-		for headers in rdr.headers() {
-			for header in headers.iter() {
-	        	//add a Profile to the list of profiles to represent the field (indexed using the header label)
-	        	let p = Profile::new_with_id(format!("{}",header));
-				self.profiles.insert(format!("{}",header), p);
-	        }
-		}
-
-		//create a Vec from all the keys (headers) in the profiles list
-		let profile_keys: Vec<_> = self.profiles.keys().cloned().collect();
-		//let mut rec_cnt: u16 = <u16>::min_value();
+		let dialect = self.csv_dialect()?;
 
-		debug!("CSV headers: {:?}",profile_keys);
+		//the symbol classes of the configuration file are used to analyze the fields
+		let classes = self.symbol_classes()?;
 
 		// Multi-Threading START
-		let columns = Self::read_as_columns(rdr);
+		let (header_row, columns) = Self::read_as_columns_with_dialect(data, &dialect)?;
+		let headers = self.column_headers(header_row, columns.len());
+
+		debug!("CSV headers: {:?}",headers);
+
 		//let col_cnt = columns.len();
-		let rec_cnt = columns[0].len();
-		self.analyze_columns(profile_keys, columns);
+		let rec_cnt = columns.first().map_or(0, |c| c.len());
+		self.analyze_fields(headers, columns, &classes)?;
 
 	    debug!("Successfully analyzed the csv data");
 		debug!("Analyzed {} records, {} fields", rec_cnt, self.profiles.len());
 
+		Ok(1)
+	}
+
+	/// This function analyzes the values of the fields with a new Profile for each field, and prepares the profiles for data generation
+	fn analyze_fields(&mut self, headers: Vec<String>, columns: Vec<Vec<String>>, classes: &[SymbolClass]) -> Result<(), Error> {
+		//iterate through the headers (in the order of the columns)
+		for header in headers.iter() {
+	       	//add a Profile to the list of profiles to represent the field (indexed using the header label)
+	       	let mut p = Profile {
+	       		id: Some(header.clone()),
+	       		..Profile::new_with_symbol_classes(classes)?
+	       	};
+			p.set_archive_entity_hashes(self.archive_entity_hashes);
+			self.profiles.insert(header.clone(), p);
+
+			//keep the order of the columns, fields of data that was analyzed before stay in their position
+			if !self.schema.contains(header) {
+				self.schema.push(header.clone());
+			}
+		}
+
+		self.analyze_columns(headers, columns)?;
+
 		//prepare the profiles for data generation
 		self.profiles.iter_mut().for_each(|p|p.1.pre_generate());
+		self.seed_profiles();
+		self.novel_profiles();
+
+		Ok(())
+	}
+
+	/// This function analyzes sample data that is a JSON formatted file and returns a boolean if successful.
+	/// The file contains either one JSON document (object) or an array of JSON documents.
+	/// The nested objects and arrays are flattened into field paths (e.g.: `customer.address.city` or `items[].sku`), a Profile is built for each path,
+	/// and the structure of the documents (e.g.: optional keys and array lengths) is remembered (see _json_structure()_).
+	///
+	/// # Arguments
+	///
+	/// * `path: &String` - The full path name of the JSON formatted sample data file.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///
+	/// 	assert_eq!(dsp.analyze_json_file(&String::from("./tests/samples/sample-orders.json")).unwrap(), 1);
+	///		assert_eq!(dsp.extract_headers()[..3], ["id", "customer.name", "customer.address.city"]);
+	/// }
+	/// ```
+	pub fn analyze_json_file(&mut self, path: &String) -> Result<i32, Error> {
+		info!("Starting to analyzed the JSON file {}",path);
+		let data = Self::read_file(path)?;
+		self.analyze_json_data(&data)
+	}
+
+	/// This function analyzes sample data that is a JSON formatted string and returns a boolean if successful.
+	/// The data is either one JSON document (object) or an array of JSON documents (see _analyze_json_file()_).
+	///
+	/// # Arguments
+	///
+	/// * `data: &String` - The textual content of a JSON formatted sample data file.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		let data = String::from(r#"[{"name": "Aaron", "tags": ["a", "b"]}, {"name": "Abbey", "tags": []}]"#);
+	///
+	/// 	assert_eq!(dsp.analyze_json_data(&data).unwrap(), 1);
+	///		assert_eq!(dsp.extract_headers(), vec!["name", "tags[]"]);
+	/// }
+	/// ```
+	pub fn analyze_json_data(&mut self, data: &String) -> Result<i32, Error> {
+		debug!("Starting to analyzed the JSON data {}",data);
+
+		// the documents are parsed into JsonValues, so that the keys keep the order of the documents
+		let documents = match serde_json::from_str(data)? {
+			JsonValue::Array(documents) => documents,
+			document => vec![document],
+		};
+
+		self.analyze_json_documents(documents)
+	}
+
+	/// This function analyzes sample data that is a newline-delimited JSON (NDJSON) formatted file, (i.e.: a JSON document on each line),
+	/// and returns a boolean if successful (see _analyze_json_file()_).
+	///
+	/// # Arguments
+	///
+	/// * `path: &String` - The full path name of the NDJSON formatted sample data file.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///
+	/// 	assert_eq!(dsp.analyze_ndjson_file(&String::from("./tests/samples/sample-orders.ndjson")).unwrap(), 1);
+	///		println!("Generated data record: {:?}", dsp.generate_record().unwrap());
+	/// }
+	/// ```
+	pub fn analyze_ndjson_file(&mut self, path: &String) -> Result<i32, Error> {
+		info!("Starting to analyzed the NDJSON file {}",path);
+		let data = Self::read_file(path)?;
+		self.analyze_ndjson_data(&data)
+	}
+
+	/// This function analyzes sample data that is a newline-delimited JSON (NDJSON) formatted string and returns a boolean if successful.
+	/// The blank lines are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `data: &String` - The textual content of a NDJSON formatted sample data file.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		let data = String::from("{\"name\": \"Aaron\"}\n\n{\"name\": \"Abbey\", \"age\": 42}\n");
+	///
+	/// 	assert_eq!(dsp.analyze_ndjson_data(&data).unwrap(), 1);
+	///		assert_eq!(dsp.json_structure().unwrap().presence("", "age"), 0.5);
+	/// }
+	/// ```
+	pub fn analyze_ndjson_data(&mut self, data: &String) -> Result<i32, Error> {
+		debug!("Starting to analyzed the NDJSON data {}",data);
+
+		let mut documents = Vec::new();
+		for (idx, line) in data.lines().enumerate() {
+			if line.trim().is_empty() {
+				continue;
+			}
+
+			let document = serde_json::from_str(line).map_err(|e| {
+				error!("Line {} of the NDJSON data couldn't be parsed!", idx + 1);
+				Error::Parse(format!("Line {} of the NDJSON data couldn't be parsed because of {}", idx + 1, e))
+			})?;
+			documents.push(document);
+		}
+
+		self.analyze_json_documents(documents)
+	}
+
+	/// This function flattens the JSON documents into field paths, remembers their structure and analyzes the values of each path
+	fn analyze_json_documents(&mut self, documents: Vec<JsonValue>) -> Result<i32, Error> {
+		let classes = self.symbol_classes()?;
+		let mut structure = JsonStructure::new();
+		let mut headers: Vec<String> = Vec::new();
+		let mut columns: Vec<Vec<String>> = Vec::new();
+
+		for (idx, document) in documents.iter().enumerate() {
+			if !document.is_object() {
+				error!("The JSON document {} isn't an object!", idx + 1);
+				return Err(Error::Parse(format!("The JSON document {} isn't an object", idx + 1)));
+			}
+
+			let mut values = Vec::new();
+			structure.analyze(document, &mut values);
+
+			// the paths are in the order they have first been analyzed
+			for (path, value) in values.into_iter() {
+				match headers.iter().position(|h| *h == path) {
+					Some(col) => columns[col].push(value),
+					None => {
+						headers.push(path);
+						columns.push(vec![value]);
+					},
+				}
+			}
+		}
+
+		debug!("JSON field paths: {:?}",headers);
+
+		self.analyze_fields(headers, columns, &classes)?;
+		self.structure = Some(structure);
+
+		debug!("Analyzed {} documents, {} fields", documents.len(), self.profiles.len());
 
 		Ok(1)
 	}
 
+	/// This function returns the structure of the analyzed JSON documents, (None if no JSON documents have been analyzed)
+	pub fn json_structure(&self) -> Option<&JsonStructure> {
+		self.structure.as_ref()
+	}
+
+	/// This function creates a (buffered) file for the generated test data
+	fn create_file(path: &String) -> Result<BufWriter<File>, Error> {
+		let file = File::create(path).map_err(|e| {
+			error!("file {} couldn't be created!",path);
+			Error::Io(e)
+		})?;
+
+		Ok(BufWriter::new(file))
+	}
+
+	/// This function reads the contents of a sample data file
+	fn read_file(path: &String) -> Result<String, Error> {
+    	let mut file = (File::open(path).map_err(|e| {
+			error!("file {} couldn't be opened!",path);
+    		Error::Io(e)
+		}))?;
+
+		let mut data = String::new();
+    	file.read_to_string(&mut data).map_err(|e| {
+			error!("file {} couldn't be read!",path);
+    		Error::Io(e)
+		})?;
+
+		Ok(data)
+	}
+
+	/// This function returns the names of the columns, which are the column names that have been set, or else the labels of the header row.
+	/// The columns that have neither are named by their position (e.g.: column_1, column_2, ...).
+	fn column_headers(&self, header_row: Vec<String>, column_count: usize) -> Vec<String> {
+		(0..column_count.max(header_row.len())).map(|idx| {
+			match self.column_names.get(idx).or_else(|| header_row.get(idx)) {
+				Some(name) => name.clone(),
+				None => format!("column_{}", idx + 1),
+			}
+		}).collect()
+	}
+
+	/// This function returns the dialect of the csv data, which is the dialect that has been set, or the dialect of the configuration file,
+	/// or else the default dialect
+	fn csv_dialect(&self) -> Result<CsvDialect, Error> {
+		if let Some(dialect) = &self.dialect {
+			return Ok(dialect.clone());
+		}
+
+		match &self.cfg {
+			Some(cfg) => Ok(cfg.csv_dialect()?.unwrap_or_default()),
+			None => Ok(CsvDialect::new()),
+		}
+	}
+
+	/// This function returns the user-defined symbol classes of the configuration file (none if there is no configuration file)
+	fn symbol_classes(&self) -> Result<Vec<SymbolClass>, Error> {
+		match &self.cfg {
+			Some(cfg) => cfg.symbol_classes(),
+			None => Ok(Vec::new()),
+		}
+	}
+
 	/// This function generates date as strings using the a `demo` profile
 	///
 	/// # Example
@@ -394,10 +685,10 @@ impl DataSampleParser {
 	///		let dsp = DataSampleParser::new();
 	///
 	///		// generate some test data using the demo functions
-	///		println!("generate date:{}", dsp.demo_date());
+	///		println!("generate date:{}", dsp.demo_date().unwrap());
 	/// }
 	/// ```
-	pub fn demo_date(&self) -> String{
+	pub fn demo_date(&self) -> Result<String, Error> {
 		let mut profil =  Profile::new();
 
     	profil.analyze("01/04/2017");
@@ -442,9 +733,9 @@ impl DataSampleParser {
 	///		let dsp = DataSampleParser::new();
 	///
 	///		// generate some test data using the demo functions
-	///		println!("generate date:{}", dsp.demo_person_name());
+	///		println!("generate date:{}", dsp.demo_person_name().unwrap());
 	/// }
-	pub fn demo_person_name(&self) -> String{
+	pub fn demo_person_name(&self) -> Result<String, Error> {
 	    let mut profil =  Profile::new();
 
     	profil.analyze("Smith, John");
@@ -459,7 +750,7 @@ impl DataSampleParser {
     	profil.generate()
 	}
 
-	/// This function returns a vector of header names
+	/// This function returns a vector of header names, in the order of the columns of the analyzed data
 	///
 	/// # Example
 	///
@@ -475,16 +766,20 @@ impl DataSampleParser {
     /// 	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
     ///     let headers = dsp.extract_headers();
     ///
-    ///		assert_eq!(headers.len(), 2);
+    ///		assert_eq!(headers, vec!["firstname", "lastname"]);
 	/// }
 	pub fn extract_headers(&mut self) -> Vec<String> {
-		let mut headers = vec!();
+		self.fields()
+	}
 
-		for profile in self.profiles.iter_mut() {
-			headers.push(profile.0.to_string());
+	/// This function returns the names of the fields in the order of the columns of the analyzed data.
+	/// Archives of earlier versions have no schema, so their fields are in the order of their names.
+	fn fields(&self) -> Vec<String> {
+		if self.schema.is_empty() {
+			return self.profiles.keys().cloned().collect();
 		}
 
-		headers
+		self.schema.clone()
 	}
 
 	/// This function generates test data for the specified field name.
@@ -505,14 +800,22 @@ impl DataSampleParser {
 	///		let mut dsp = DataSampleParser::new();
     ///
     /// 	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
-    ///     println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()));
+    ///     println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()).unwrap());
+    ///
+    ///     assert!(dsp.generate_by_field_name("nickname".to_string()).is_err());
 	/// }
 	/// ```
-	pub fn generate_by_field_name(&mut self, field: String) -> String {
-		self.profiles.get_mut(&field).unwrap().generate().to_string()
+	pub fn generate_by_field_name(&mut self, field: String) -> Result<String, Error> {
+		match self.profiles.get_mut(&field) {
+			Some(profile) => profile.generate(),
+			None => {
+				error!("The field {} is not known to the data sample parser", field);
+				Err(Error::UnknownField(field))
+			},
+		}
 	}
 
-	/// This function Vec of generates test data fields.
+	/// This function Vec of generates test data fields, in the order of the columns of the analyzed data.
 	///
 	/// # Example
 	///
@@ -526,22 +829,52 @@ impl DataSampleParser {
 	///		let mut dsp = DataSampleParser::new();
     ///
     /// 	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
-    ///     println!("Generated data record: {:?}",dsp.generate_record());
+    ///     println!("Generated data record: {:?}",dsp.generate_record().unwrap());
 	/// }
 	/// ```
-	pub fn generate_record(&mut self) -> Vec<String> {
+	pub fn generate_record(&mut self) -> Result<Vec<String>, Error> {
 		let mut record = Vec::new();
 
-		for profile in self.profiles.iter_mut() {
-			record.push(profile.1.generate().to_string());
+		for field in self.fields() {
+			match self.profiles.get_mut(&field) {
+				Some(profile) => record.push(profile.generate()?),
+				None => return Err(Error::UnknownField(field)),
+			}
 		}
 
-		record
+		Ok(record)
+	}
+
+	/// This function returns an (infinite) iterator of generated test data records.
+	/// The iterator ends if there are no fields (e.g.: no data sample has been analyzed), or a field fails to be generated.
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		// initalize a new DataSampelParser
+	///		let mut dsp = DataSampleParser::new();
+    ///
+    /// 	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+    ///
+    ///     for record in dsp.records().take(3) {
+    ///         println!("Generated data record: {:?}", record);
+    ///     }
+	/// }
+	/// ```
+	pub fn records(&mut self) -> RecordIter<'_> {
+		RecordIter {
+			dsp: self,
+		}
 	}
 
 	/// This function creates a csv file of generated test data.
 	/// Prior to calling this funciton, you need to call the analyze_csv_file() function.
-	/// _NOTE:_ The csv properties are as follows:
+	/// _NOTE:_ The csv properties are defined by the csv dialect (see _set_csv_dialect()_). By default they are as follows:
 	///       + headers are included as first line
 	///       + double quotes wrap text
 	///       + double quote escapes is enabled
@@ -568,28 +901,26 @@ impl DataSampleParser {
     ///     dsp.generate_csv(100, &String::from("./tests/samples/generated-01.csv")).unwrap();
 	/// }
 	/// ```
-	pub fn generate_csv(&mut self, row_count: u32, path: &String) -> Result<(), Box<dyn Error>> {
+	pub fn generate_csv(&mut self, row_count: u32, path: &String) -> Result<(), Error> {
 		info!("generating csv file {}", path);
 
-		let mut wtr = (WriterBuilder::new()
-		    .has_headers(true)
-        	.quote(b'"')
-        	.double_quote(true)
-        	.delimiter(b',')
+		let dialect = self.csv_dialect()?;
+		let mut wtr = (dialect.writer_builder()?
         	.from_path(path).map_err(|e| {
 			error!("csv file {} couldn't be created!",path);
-    		e.to_string()
+    		Error::Csv(e)
 		}))?;
 
-		let headers = self.extract_headers();
-		wtr.write_record(&headers)?;
+		if self.write_headers.unwrap_or(dialect.has_headers) {
+			let headers = self.extract_headers();
+			wtr.write_record(&headers)?;
+		}
 
-		for _r in 0..row_count {
-			let mut record = Vec::new();
+		// each csv file is a new batch of (unique) generated data
+		self.novel_profiles();
 
-			for profile in self.profiles.iter_mut() {
-				record.push(profile.1.generate());
-			}
+		for _r in 0..row_count {
+			let record = self.generate_record()?;
 
 			wtr.write_record(&record)?;
 		}
@@ -599,82 +930,693 @@ impl DataSampleParser {
 		Ok(())
 	}
 
-	/// This function calculates the levenshtein distance between 2 strings.
-	/// See: https://crates.io/crates/levenshtein
-	///
-	/// # Arguments
-	///
-	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
-	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the distance.</br>
+	/// This function generates a JSON document (object) of test data. By default, the document is keyed by the field names, in the order of the columns
+	/// of the analyzed data, and all the values are strings (see _set_json_format()_ to rebuild the nested structure or to generate typed values).
 	///
-	/// #Example
+	/// # Example
 	///
 	/// ```
 	/// extern crate test_data_generation;
 	///
+	/// use test_data_generation::JsonFormat;
 	/// use test_data_generation::data_sample_parser::DataSampleParser;
 	///
 	/// fn main() {
-	/// 	// analyze the dataset
-	///		let mut dsp =  DataSampleParser::new();
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_json_data(&String::from(r#"{"customer": {"name": "Aaron"}, "paid": true}"#)).unwrap();
 	///
-	///     assert_eq!(dsp.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()), 3 as usize);
-	/// }
+	///		assert_eq!(dsp.generate_document().unwrap().to_string(), r#"{"customer.name":"Aaron","paid":"true"}"#);
 	///
-	pub fn levenshtein_distance(&mut self, control: &String, experiment: &String) -> usize {
-		// https://docs.rs/levenshtein/1.0.3/levenshtein/fn.levenshtein.html
-		levenshtein_distance!(control, experiment)
+	///		dsp.set_json_format(JsonFormat::new().with_nested(true).with_typed_values(true));
+	///		assert_eq!(dsp.generate_document().unwrap().to_string(), r#"{"customer":{"name":"Aaron"},"paid":true}"#);
+	/// }
+	/// ```
+	pub fn generate_document(&mut self) -> Result<JsonValue, Error> {
+		let format = self.json_format;
+		let fields = self.fields();
+		let rng = self.rng.get_or_insert_with(StdRng::from_entropy);
+		let profiles = &mut self.profiles;
+
+		if let (Some(structure), true) = (self.structure.as_ref(), format.nested) {
+			return structure.generate(rng, format.typed, &mut |path: &str| {
+				profiles.get_mut(path).map(|p| p.generate()).transpose()
+			});
+		}
+
+		let mut document = Vec::new();
+		for field in fields {
+			let profile = match profiles.get_mut(&field) {
+				Some(profile) => profile,
+				None => return Err(Error::UnknownField(field)),
+			};
+
+			let value = if !format.typed {
+				JsonValue::String(profile.generate()?)
+			} else {
+				match self.structure.as_ref().and_then(|s| s.nodes.get(&field)) {
+					Some(node) => match node.scalar_kind(rng) {
+						Some(JsonKind::Null) | None => JsonValue::Null,
+						Some(JsonKind::Boolean) => JsonStructure::boolean(node.boolean(rng), true),
+						Some(kind) => kind.value_of(profile.generate()?),
+					},
+					None => JsonKind::infer(profile.generate()?, profile.is_numeric()),
+				}
+			};
+
+			document.push((field, value));
+		}
+
+		Ok(JsonValue::Object(document))
 	}
 
-	/// This function calculates the percent difference between 2 strings.
+	/// This function creates a JSON file of generated test data, which is an array of JSON documents (see _generate_document()_).
+	/// Prior to calling this funciton, you need to analyze a data sample (e.g.: using the analyze_json_file() or analyze_csv_file() function).
 	///
 	/// # Arguments
 	///
-	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
-	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the percent difference.</br>
+	/// * `row_count: u32` - The number of documents to generate.</br>
+	/// * `path: &String` - The full path name where to save the JSON file.</br>
 	///
-	/// #Example
+	/// # Example
 	///
 	/// ```
 	/// extern crate test_data_generation;
 	///
+	/// use test_data_generation::JsonFormat;
 	/// use test_data_generation::data_sample_parser::DataSampleParser;
 	///
 	/// fn main() {
-	/// 	// analyze the dataset
-	///		let mut dsp =  DataSampleParser::new();
+	///		let mut dsp = DataSampleParser::new();
 	///
-	///     assert_eq!(dsp.realistic_test(&"kitten".to_string(), &"sitting".to_string()), 76.92307692307692 as f64);
+	/// 	dsp.analyze_json_file(&String::from("./tests/samples/sample-orders.json")).unwrap();
+	///		dsp.set_json_format(JsonFormat::new().with_nested(true).with_typed_values(true));
+	///     dsp.generate_json(100, &String::from("./tests/samples/generated-orders.json")).unwrap();
 	/// }
-	///
-	pub fn realistic_test(&mut self, control: &String, experiment: &String) -> f64 {
-		//https://docs.rs/GSL/0.4.31/rgsl/statistics/fn.correlation.html
-		//http://www.statisticshowto.com/probability-and-statistics/correlation-coefficient-formula/
-		// pearson's chi square test
-		// cosine similarity - http://blog.christianperone.com/2013/09/machine-learning-cosine-similarity-for-vector-space-models-part-iii/
-		realistic_test!(control, experiment)
+	/// ```
+	pub fn generate_json(&mut self, row_count: u32, path: &String) -> Result<(), Error> {
+		info!("generating JSON file {}", path);
+
+		// each JSON file is a new batch of (unique) generated data
+		self.novel_profiles();
+
+		let mut documents = Vec::new();
+		for _r in 0..row_count {
+			documents.push(self.generate_document()?);
+		}
+
+		let mut file = Self::create_file(path)?;
+		serde_json::to_writer_pretty(&mut file, &JsonValue::Array(documents))?;
+		file.write_all(b"\n")?;
+
+		Ok(())
 	}
 
-	/// This function returns a boolean that indicates if the data sample parsing had issues
+	/// This function creates a newline-delimited JSON (NDJSON) file of generated test data, which has a JSON document on each line (see _generate_document()_).
+	/// Prior to calling this funciton, you need to analyze a data sample (e.g.: using the analyze_ndjson_file() or analyze_csv_file() function).
+	///
+	/// # Arguments
+	///
+	/// * `row_count: u32` - The number of documents to generate.</br>
+	/// * `path: &String` - The full path name where to save the NDJSON file.</br>
 	///
 	/// # Example
 	///
 	/// ```
 	/// extern crate test_data_generation;
 	///
+	/// use test_data_generation::JsonFormat;
 	/// use test_data_generation::data_sample_parser::DataSampleParser;
 	///
 	/// fn main() {
-	///		// initalize a new DataSampelParser
-	///	    // param: the path to the configuration file is wrong
-	///		let dsp = DataSampleParser::new_with(&String::from("./target/debug/config/tdg.yaml"));
+	///		let mut dsp = DataSampleParser::new();
 	///
-	///		// generate some test data using the demo functions
-	///		assert_eq!(dsp.running_with_issues(), &false);
+	/// 	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///		dsp.set_json_format(JsonFormat::new().with_typed_values(true));
+	///     dsp.generate_ndjson(100, &String::from("./tests/samples/generated-01.ndjson")).unwrap();
 	/// }
-	pub fn running_with_issues(&self) -> &bool{
-		&self.issues
-	}
+	/// ```
+	pub fn generate_ndjson(&mut self, row_count: u32, path: &String) -> Result<(), Error> {
+		info!("generating NDJSON file {}", path);
+
+		// each NDJSON file is a new batch of (unique) generated data
+		self.novel_profiles();
+
+		let mut file = Self::create_file(path)?;
+		for _r in 0..row_count {
+			let document = self.generate_document()?;
+			serde_json::to_writer(&mut file, &document)?;
+			file.write_all(b"\n")?;
+		}
+
+		file.flush()?;
+
+		Ok(())
+	}
+
+	/// This function creates a SQL file of generated test data, which seeds a database table (e.g.: PostgreSQL or SQLite).
+	/// By default, a `CREATE TABLE` statement for the `test_data` table is followed by `INSERT` statements of 100 records each (see _set_sql_format()_).
+	/// The columns are named by the field names, and their types are inferred from the Profiles and the generated values (see _SqlColumn::infer()_).
+	/// All the records are generated before they are written (in batches of the batch size), so that the types of the columns fit all the generated values.
+	/// Prior to calling this funciton, you need to analyze a data sample (e.g.: using the analyze_csv_file() function).
+	///
+	/// # Arguments
+	///
+	/// * `row_count: u32` - The number of rows to generate.</br>
+	/// * `path: &String` - The full path name where to save the SQL file.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::SqlFormat;
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		// initalize a new DataSampelParser
+	///		let mut dsp = DataSampleParser::new();
+	///
+	/// 	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///		dsp.set_sql_format(SqlFormat::new().with_table("people").with_batch_size(50));
+	///     dsp.generate_sql(100, &String::from("./tests/samples/generated-01.sql")).unwrap();
+	/// }
+	/// ```
+	pub fn generate_sql(&mut self, row_count: u32, path: &String) -> Result<(), Error> {
+		info!("generating SQL file {}", path);
+
+		// each SQL file is a new batch of (unique) generated data
+		self.novel_profiles();
+
+		// the types of the columns must fit all the generated values before the CREATE TABLE statement is written,
+		// so the records are generated before they are written
+		let records = (0..row_count).map(|_| self.generate_record()).collect::<Result<Vec<Vec<String>>, Error>>()?;
+
+		let mut columns = Vec::new();
+		for (idx, field) in self.fields().iter().enumerate() {
+			let values = records.iter().map(|r| r[idx].as_str()).collect::<Vec<&str>>();
+
+			match self.profiles.get(field) {
+				Some(profile) => columns.push(SqlColumn::infer(field, profile, &values)),
+				None => return Err(Error::UnknownField(field.clone())),
+			}
+		}
+
+		let mut file = Self::create_file(path)?;
+		self.sql_format.write(&mut file, &columns, &records)?;
+		file.flush()?;
+
+		Ok(())
+	}
+
+	/// This function returns a summary (report) for each field (e.g.: csv column) of what its Profile has learned from the data sample,
+	/// in the order of the columns of the analyzed data.
+	///
+	/// # Arguments
+	///
+	/// * `top_n: usize` - The number of most frequent patterns to report for each field.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///
+	///		for (field, summary) in dsp.summary(3).iter() {
+	///			println!("{}:\n{}", field, summary);
+	///		}
+	/// }
+	/// ```
+	pub fn summary(&self, top_n: usize) -> Vec<(String, ProfileSummary)> {
+		self.fields().into_iter().filter_map(|field| {
+			let summary = self.profiles.get(&field)?.summary(top_n);
+			Some((field, summary))
+		}).collect()
+	}
+
+	/// This function returns a regular expression for each field (e.g.: csv column) that matches the data its Profile has analyzed,
+	/// in the order of the columns of the analyzed data, so that the fields can be validated (see _Profile::to_regex()_).
+	///
+	/// # Arguments
+	///
+	/// * `form: RegexForm` - The form of the regular expressions.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	/// use test_data_generation::RegexForm;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_data(&String::from("code,number\nAB,12\nXY,345")).unwrap();
+	///
+	///		let regexes = dsp.to_regex(RegexForm::Generalized).unwrap();
+	///
+	///		// the numbers are generated from their range, so any number matches
+	///		assert_eq!(regexes[0].0, "code");
+	///		assert_eq!(regexes[1], ("number".to_string(), "^(?:[0-9]+|[1-5]{2,3})$".to_string()));
+	/// }
+	/// ```
+	pub fn to_regex(&self, form: RegexForm) -> Result<Vec<(String, String)>, Error> {
+		self.fields().into_iter().map(|field| {
+			let regex = match self.profiles.get(&field) {
+				Some(profile) => profile.to_regex(form)?,
+				None => return Err(Error::UnknownField(field)),
+			};
+			Ok((field, regex))
+		}).collect()
+	}
+
+	/// This function calculates the levenshtein distance between 2 strings.
+	/// See: https://crates.io/crates/levenshtein
+	///
+	/// # Arguments
+	///
+	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
+	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the distance.</br>
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	/// 	// analyze the dataset
+	///		let mut dsp =  DataSampleParser::new();
+	///
+	///     assert_eq!(dsp.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()), 3 as usize);
+	/// }
+	///
+	pub fn levenshtein_distance(&mut self, control: &String, experiment: &String) -> usize {
+		// https://docs.rs/levenshtein/1.0.3/levenshtein/fn.levenshtein.html
+		levenshtein_distance!(control, experiment)
+	}
+
+	/// This function calculates the percent difference between 2 strings.
+	///
+	/// # Arguments
+	///
+	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
+	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the percent difference.</br>
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	/// 	// analyze the dataset
+	///		let mut dsp =  DataSampleParser::new();
+	///
+	///     assert_eq!(dsp.realistic_test(&"kitten".to_string(), &"sitting".to_string()), 76.92307692307692 as f64);
+	/// }
+	///
+	pub fn realistic_test(&mut self, control: &String, experiment: &String) -> f64 {
+		//https://docs.rs/GSL/0.4.31/rgsl/statistics/fn.correlation.html
+		//http://www.statisticshowto.com/probability-and-statistics/correlation-coefficient-formula/
+		// pearson's chi square test
+		// cosine similarity - http://blog.christianperone.com/2013/09/machine-learning-cosine-similarity-for-vector-space-models-part-iii/
+		realistic_test!(control, experiment)
+	}
+
+	/// This function merges (unions) the analyzed data of another DataSampleParser into this DataSampleParser, column by column.
+	/// The Profiles of the columns that both parsers have analyzed are merged, and the Profiles of the columns that only the other
+	/// parser has analyzed are added.
+	///
+	/// # Arguments
+	///
+	/// * `other: &DataSampleParser` - The DataSampleParser to merge into this DataSampleParser.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_data(&String::from("\"firstname\",\"lastname\"\n\"Aaron\",\"Aaberg\"")).unwrap();
+	///
+	///		let mut other = DataSampleParser::new();
+	///		other.analyze_csv_data(&String::from("\"firstname\",\"lastname\"\n\"Abbey\",\"Aadland\"")).unwrap();
+	///
+	///		dsp.merge(&other);
+	///
+	///		println!("Generated data record: {:?}", dsp.generate_record().unwrap());
+	/// }
+	/// ```
+	pub fn merge(&mut self, other: &DataSampleParser) {
+		// the fields that are only known to the other DataSampleParser are added after the known fields
+		let mut schema = self.fields();
+		for field in other.fields() {
+			if !schema.contains(&field) {
+				schema.push(field);
+			}
+		}
+		self.schema = schema;
+
+		for (name, profile) in other.profiles.iter() {
+			match self.profiles.get_mut(name) {
+				Some(p) => p.merge(profile),
+				None => {
+					self.profiles.insert(name.clone(), profile.clone());
+				},
+			}
+		}
+
+		if let Some(other_structure) = other.structure.as_ref() {
+			self.structure.get_or_insert_with(JsonStructure::new).merge(other_structure);
+		}
+
+		self.issues = self.issues || other.issues;
+		self.seed_profiles();
+		self.novel_profiles();
+	}
+
+	/// This function returns a boolean that indicates if the data sample parsing had issues
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		// initalize a new DataSampelParser
+	///	    // param: the path to the configuration file is wrong
+	///		let dsp = DataSampleParser::new_with(&String::from("./target/debug/config/tdg.yaml"));
+	///
+	///		// generate some test data using the demo functions
+	///		assert_eq!(dsp.running_with_issues(), &false);
+	/// }
+	pub fn running_with_issues(&self) -> &bool{
+		&self.issues
+	}
+
+	/// This function seeds the random number generators of all the Profiles, so that the same seed and DataSampleParser
+	/// will always generate the same records (and csv files). The seed is also applied to any data that is analyzed afterwards.
+	///
+	/// # Arguments
+	///
+	/// * `seed: u64` - The number used to seed the random number generators.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///
+	///		dsp.set_seed(1234);
+	///		let first = dsp.generate_record().unwrap();
+	///
+	///		dsp.set_seed(1234);
+	///		assert_eq!(dsp.generate_record().unwrap(), first);
+	/// }
+	/// ```
+	pub fn set_seed(&mut self, seed: u64) {
+		self.seed = Some(seed);
+		self.seed_profiles();
+	}
+
+	/// This function sets the dialect of the analyzed and generated csv data (e.g.: the delimiter, quote and escape chars).
+	/// The dialect takes precedence over the `csv-dialect` setting of the configuration file.
+	///
+	/// # Arguments
+	///
+	/// * `dialect: CsvDialect` - The dialect of the csv data.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::CsvDialect;
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.set_csv_dialect(CsvDialect::new().with_delimiter('\t'));
+	///
+	///		assert_eq!(dsp.analyze_csv_data(&String::from("firstname\tlastname\nAaron\tAaberg")).unwrap(), 1);
+	///		assert_eq!(dsp.extract_headers(), vec!["firstname", "lastname"]);
+	/// }
+	/// ```
+	pub fn set_csv_dialect(&mut self, dialect: CsvDialect) {
+		self.dialect = Some(dialect);
+	}
+
+	/// This function sets the names of the columns of the csv data that is analyzed afterwards, (e.g.: for csv data that has no header row).
+	/// The names are used instead of the labels of the header row, and the columns that have no name are named by their position
+	/// (e.g.: column_1, column_2, ...).
+	///
+	/// # Arguments
+	///
+	/// * `names: Vec<String>` - The names of the columns, in the order of the columns.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::CsvDialect;
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.set_csv_dialect(CsvDialect::new().with_headers(false));
+	///		dsp.set_column_names(vec!["firstname".to_string()]);
+	///
+	///		dsp.analyze_csv_data(&String::from("Aaron,Aaberg,42\nAbbey,Aadland,37")).unwrap();
+	///
+	///		assert_eq!(dsp.extract_headers(), vec!["firstname", "column_2", "column_3"]);
+	/// }
+	/// ```
+	pub fn set_column_names(&mut self, names: Vec<String>) {
+		self.column_names = names;
+	}
+
+	/// This function sets if the generated csv files start with a header row, regardless of the header row of the csv dialect.
+	/// By default, the generated csv files have a header row if the csv dialect has a header row.
+	///
+	/// # Arguments
+	///
+	/// * `write_headers: bool` - If true, the header row is written.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///
+	///		dsp.set_write_headers(false);
+	///		dsp.generate_csv(10, &String::from("./tests/samples/generated-01-no-headers.csv")).unwrap();
+	/// }
+	/// ```
+	pub fn set_write_headers(&mut self, write_headers: bool) {
+		self.write_headers = Some(write_headers);
+	}
+
+	/// This function sets the format of the generated JSON documents, (e.g.: to rebuild the nested structure of the analyzed JSON documents,
+	/// or to generate numbers, booleans and nulls as typed values instead of strings).
+	///
+	/// # Arguments
+	///
+	/// * `format: JsonFormat` - The format of the generated JSON documents.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::JsonFormat;
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_data(&String::from("code,count\nAA,12\nAA,12")).unwrap();
+	///
+	///		dsp.set_json_format(JsonFormat::new().with_typed_values(true));
+	///		assert_eq!(dsp.generate_document().unwrap().to_string(), r#"{"code":"AA","count":12}"#);
+	/// }
+	/// ```
+	pub fn set_json_format(&mut self, format: JsonFormat) {
+		self.json_format = format;
+	}
+
+	/// This function sets the format of the generated SQL, (e.g.: the name of the table, the number of records of each `INSERT` statement,
+	/// or the PostgreSQL `COPY` statement instead of `INSERT` statements).
+	///
+	/// # Arguments
+	///
+	/// * `format: SqlFormat` - The format of the generated SQL.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::{SqlFormat, SqlStatement};
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///
+	///		dsp.set_sql_format(SqlFormat::new().with_table("people").with_statement(SqlStatement::Copy).with_create_table(false));
+	///		dsp.generate_sql(10, &String::from("./tests/samples/generated-01-copy.sql")).unwrap();
+	/// }
+	/// ```
+	pub fn set_sql_format(&mut self, format: SqlFormat) {
+		self.sql_format = format;
+	}
+
+	/// This function sets the maximum number of worker threads that are used to analyze the data.
+	/// By default, the number of available cores is used.
+	/// NOTE: All the columns are analyzed at the same time by the worker threads, which are started for each analyzed data sample and joined once it is analyzed.
+	///
+	/// # Arguments
+	///
+	/// * `size: usize` - The maximum number of worker threads (0 = the number of available cores).</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.set_worker_count(2);
+	///
+	///		assert_eq!(dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap(), 1);
+	/// }
+	/// ```
+	pub fn set_worker_count(&mut self, size: usize) {
+		self.pool = WorkerPool::new(size);
+	}
+
+	/// This function sets the novel-value mode of all the Profiles, so that the generated value of a field never equals any of the
+	/// values of the field in the data sample that was analyzed, and optionally is unique within the generated csv file.
+	/// The mode is also applied to any data that is analyzed afterwards.
+	///
+	/// NOTE: The data sample can only be recognized if the Profiles hold the hashes of all the analyzed values. This isn't the case for a DataSampleParser
+	/// that is restored from an archive without the hashes (see _set_archive_entity_hashes()_), or for Profiles that analyzed more values than their
+	/// limit of hashes (see _Profile::set_entity_hash_limit()_). Such a DataSampleParser can't be set to (or generate data in) the novel-value mode.
+	///
+	/// # Arguments
+	///
+	/// * `mode: Option<NovelMode>` - The novel-value mode to use, or None to turn it off.</br>
+	///
+	/// #Errors
+	/// If a Profile has analyzed values without holding all of their hashes, an Error::Config is returned and the mode isn't changed.
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::NovelMode;
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///
+	///		dsp.set_novel_mode(Some(NovelMode::new(false, 1000))).unwrap();
+	///		println!("generated record: {:?}", dsp.generate_record().unwrap());
+	/// }
+	/// ```
+	pub fn set_novel_mode(&mut self, mode: Option<NovelMode>) -> Result<(), Error> {
+		if mode.is_some() {
+			for profile in self.profiles.values() {
+				profile.check_entity_hashes()?;
+			}
+		}
+
+		self.novel = mode;
+		self.novel_profiles();
+		Ok(())
+	}
+
+	/// This function sets if the hashes of the analyzed entities are archived with the Profiles, so that the novel-value mode also recognizes
+	/// the data sample after the DataSampleParser is restored from the archive (see _Profile::set_archive_entity_hashes()_).
+	/// By default, the hashes aren't archived. The setting is also applied to any data that is analyzed afterwards.
+	///
+	/// # Arguments
+	///
+	/// * `archive: bool` - If true, the hashes are archived.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::data_sample_parser::DataSampleParser;
+	///
+	/// fn main() {
+	///		let mut dsp = DataSampleParser::new();
+	///		dsp.set_archive_entity_hashes(true);
+	///		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+	///
+	///		dsp.save(&String::from("./tests/samples/sample-01-hashes-dsp")).unwrap();
+	/// }
+	/// ```
+	pub fn set_archive_entity_hashes(&mut self, archive: bool) {
+		self.archive_entity_hashes = archive;
+
+		for profile in self.profiles.values_mut() {
+			profile.set_archive_entity_hashes(archive);
+		}
+	}
+
+	/// This function applies the novel-value mode of the DataSampleParser to the Profiles, which starts a new batch of generated data.
+	fn novel_profiles(&mut self) {
+		for profile in self.profiles.values_mut() {
+			// a Profile that can't recognize all of its values is still set to the mode, so that it returns an error when it generates data
+			if let Err(e) = profile.set_novel_mode(self.novel.clone()) {
+				warn!("The profile {:?} can't generate novel values. Error Message: {}", profile.id, e);
+				profile.novel = self.novel.clone();
+				profile.generated.clear();
+			}
+		}
+	}
+
+	/// This function derives a seed for each of the Profiles (in order of their names) from the seed of the DataSampleParser.
+	fn seed_profiles(&mut self) {
+		if let Some(seed) = self.seed {
+			let mut rng = StdRng::seed_from_u64(seed);
+
+			for profile in self.profiles.values_mut() {
+				profile.set_seed(rng.gen::<u64>());
+			}
+
+			self.rng = Some(StdRng::seed_from_u64(rng.gen::<u64>()));
+		}
+	}
 
 	/// This function saves (exports) the DataSampleParser to a JSON file.
 	/// This is useful when you wish to reuse the algorithm to generate more test data later.
@@ -702,14 +1644,14 @@ impl DataSampleParser {
     ///     assert_eq!(dsp.save(&String::from("./tests/samples/sample-00-dsp")).unwrap(), true);
 	/// }
 	///
-	pub fn save(&mut self, path: &String) -> Result<bool, io::Error>  {
-		let dsp_json = serde_json::to_string(&self).unwrap();
+	pub fn save(&mut self, path: &String) -> Result<bool, Error>  {
+		let dsp_json = serde_json::to_string(&self)?;
 
 		// Create the archive file
 		let mut file = match File::create(format!("{}.json",&path)) {
 			Err(e) => {
 				error!("Could not create file {:?}", &path.to_string());
-				return Err(e);
+				return Err(Error::Io(e));
 			},
 			Ok(f) => {
 				info!("Successfully exported to {:?}", &path.to_string());
@@ -721,7 +1663,7 @@ impl DataSampleParser {
     	match file.write_all(dsp_json.as_bytes()) {
         	Err(e) => {
             	error!("Could not write to file {}", &path.to_string());
-            	return Err(e);
+            	return Err(Error::Io(e));
         	},
         	Ok(_) => {
         		info!("Successfully exported to {}", &path.to_string());
@@ -736,16 +1678,35 @@ impl DataSampleParser {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::sql::SqlStatement;
 	use std::fs::File;
 	use std::io::BufReader;
 
     #[test]
     // ensure the Data Sample Parser can be restored from archived file
     fn test_from_file(){
-    	let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp"));
-    	println!("Sample data is [{:?}]", dsp.generate_record()[0]);
+    	let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-00-dsp")).unwrap();
+    	println!("Sample data is [{:?}]", dsp.generate_record().unwrap()[0]);
+
+    	assert_eq!(dsp.generate_record().unwrap()[0], "OK".to_string());
+    }
+
+    #[test]
+    // ensure the Data Sample Parser returns an error when the archived file doesn't exist
+    fn test_from_file_bad_path(){
+    	match DataSampleParser::from_file(&String::from("./tests/samples/bad-path")) {
+    		Err(Error::Io(_)) => {},
+    		_ => panic!("expected an I/O error"),
+    	}
+    }
 
-    	assert_eq!(dsp.generate_record()[0], "OK".to_string());
+    #[test]
+    // ensure the Data Sample Parser returns an error when the archived file can't be parsed
+    fn test_from_file_bad_data(){
+    	match DataSampleParser::from_file(&String::from("./tests/samples/not-readable")) {
+    		Err(Error::Parse(_)) => {},
+    		_ => panic!("expected a parse error"),
+    	}
     }
 
 	#[test]
@@ -788,7 +1749,34 @@ mod tests {
     	let mut dsp =  DataSampleParser::new();
 
     	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
-    	println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()));
+    	println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()).unwrap());
+    }
+
+    #[test]
+    // ensure DataSampleParser returns an error for an unknown field
+    fn test_generate_field_unknown(){
+    	let mut dsp =  DataSampleParser::new();
+
+    	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+
+    	match dsp.generate_by_field_name("nickname".to_string()) {
+    		Err(Error::UnknownField(field)) => assert_eq!(field, "nickname"),
+    		_ => panic!("expected an unknown field error"),
+    	}
+    }
+
+    #[test]
+    // ensure DataSampleParser returns an error for malformed csv data
+    fn test_parse_csv_data_bad(){
+		let mut dsp =  DataSampleParser::new();
+		let mut data = String::from("");
+		data.push_str("\"firstname\",\"lastname\"\n");
+		data.push_str("\"Aaron\",\"Aaberg\",\"Aaby\"\n");
+
+		match dsp.analyze_csv_data(&data) {
+			Err(Error::Csv(_)) => {},
+			_ => panic!("expected a csv error"),
+		}
     }
 
     #[test]
@@ -797,7 +1785,7 @@ mod tests {
     	let mut dsp =  DataSampleParser::new();
 
     	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
-    	assert_eq!(dsp.generate_record().len(), 2);
+    	assert_eq!(dsp.generate_record().unwrap().len(), 2);
     }
 
     #[test]
@@ -808,6 +1796,23 @@ mod tests {
     	assert_eq!(dsp.analyze_csv_file(&String::from("./badpath/sample-01.csv")).is_err(), true);
     }
 
+    #[test]
+    // ensure the DataSampleParser can merge the profiles of another DataSampleParser
+    fn test_merge(){
+    	let mut dsp =  DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("\"firstname\",\"lastname\"\n\"Aaron\",\"Aaberg\"\n\"Abby\",\"Aakre\"")).unwrap();
+
+    	let mut other =  DataSampleParser::new();
+    	other.analyze_csv_data(&String::from("\"firstname\",\"nickname\"\n\"Abbey\",\"Abs\"")).unwrap();
+
+    	dsp.merge(&other);
+
+    	assert_eq!(dsp.extract_headers(), vec!("firstname", "lastname", "nickname"));
+    	assert_eq!(dsp.profiles.get("firstname").unwrap().pattern_total, 3);
+    	assert_eq!(dsp.profiles.get("lastname").unwrap().pattern_total, 2);
+    	assert_eq!(dsp.generate_by_field_name("nickname".to_string()).unwrap(), "Abs");
+    }
+
     #[test]
     // ensure the DataSampleParser object can be saved to file
     fn test_save(){
@@ -817,6 +1822,379 @@ mod tests {
     	assert_eq!(dsp.save(&String::from("./tests/samples/sample-00-dsp")).unwrap(), true);
     }
 
+    #[test]
+    // ensure the DataSampleParser summarizes each field
+    fn test_summary(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("code,number\nAA,12\nAE,345")).unwrap();
+
+    	let summary = dsp.summary(1);
+
+    	assert_eq!(summary.iter().map(|s| s.0.as_str()).collect::<Vec<&str>>(), vec!["code", "number"]);
+    	assert_eq!(summary[0].1.distinct_patterns, 1);
+    	assert_eq!(summary[1].1.size_max, 3);
+    }
+
+    #[test]
+    // ensure the summaries and regular expressions of the fields are in the order of the columns
+    fn test_summary_column_order(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("zip,name,amount
+02108,Aaron,12
+80202,Abbey,345")).unwrap();
+
+    	let fields = dsp.summary(1).into_iter().map(|s| s.0).collect::<Vec<String>>();
+    	let regexes = dsp.to_regex(RegexForm::Exact).unwrap().into_iter().map(|r| r.0).collect::<Vec<String>>();
+
+    	assert_eq!(fields, vec!["zip", "name", "amount"]);
+    	assert_eq!(regexes, fields);
+    }
+
+    #[test]
+    // ensure the DataSampleParser analyzes the fields using the symbol classes of the configuration file
+    fn test_symbol_classes_from_config(){
+    	let mut dsp = DataSampleParser::new_with(&String::from("./tests/config/tdg-symbol-classes.yaml"));
+    	dsp.analyze_csv_data(&String::from("id,price\n0a-ff,$12")).unwrap();
+
+    	assert_eq!(dsp.profiles["id"].patterns.keys().collect::<Vec<&String>>(), vec!["hh-hh"]);
+    	assert_eq!(dsp.profiles["price"].patterns.keys().collect::<Vec<&String>>(), vec!["¤hh"]);
+    	assert_eq!(dsp.profiles["price"].symbol_classes.len(), 3);
+    }
+
+    #[test]
+    // ensure the DataSampleParser analyzes the same data regardless of the number of worker threads
+    fn test_analyze_with_worker_count(){
+    	let mut single = DataSampleParser::new();
+    	single.set_worker_count(1);
+    	single.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+
+    	let mut pooled = DataSampleParser::new();
+    	pooled.set_worker_count(3);
+    	pooled.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+
+    	for (name, profile) in single.profiles.iter() {
+    		assert_eq!(profile.patterns, pooled.profiles[name].patterns);
+    		assert_eq!(profile.facts.total(), pooled.profiles[name].facts.total());
+    	}
+    }
+
+    #[test]
+    // ensure the DataSampleParser keeps the order of the csv columns when analyzing and generating data
+    fn test_column_order(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("zip,name,age\nAB-1,Smith,7\nCD-2,Dale,8")).unwrap();
+
+    	assert_eq!(dsp.extract_headers(), vec!["zip", "name", "age"]);
+    	assert_eq!(dsp.profiles["zip"].patterns.keys().collect::<Vec<&String>>(), vec!["CC@#", "VC@#"]);
+    	assert_eq!(dsp.profiles["age"].patterns.keys().collect::<Vec<&String>>(), vec!["#"]);
+
+    	dsp.save(&String::from("./tests/samples/column-order-dsp")).unwrap();
+    	let mut restored = DataSampleParser::from_file(&String::from("./tests/samples/column-order-dsp")).unwrap();
+    	let record = restored.generate_record().unwrap();
+
+    	assert_eq!(restored.extract_headers(), vec!["zip", "name", "age"]);
+    	assert_eq!(record[0].len(), 4);
+    	assert!(record[2].parse::<u32>().is_ok());
+
+    	// the new fields of merged data are added after the known fields
+    	let mut other = DataSampleParser::new();
+    	other.analyze_csv_data(&String::from("city,age\nParis,9")).unwrap();
+    	restored.merge(&other);
+
+    	assert_eq!(restored.extract_headers(), vec!["zip", "name", "age", "city"]);
+    }
+
+    #[test]
+    // ensure the DataSampleParser analyzes and generates csv data of the dialect of the configuration file
+    fn test_csv_dialect_from_config(){
+    	let mut dsp = DataSampleParser::new_with(&String::from("./tests/config/tdg-csv-dialect.yaml"));
+    	dsp.analyze_csv_data(&String::from(" code | name \n'A|1'|'O\\'Brian '\n'A|1'|'O\\'Brian'")).unwrap();
+
+    	assert_eq!(dsp.extract_headers(), vec!["code", "name"]);
+    	assert_eq!(dsp.generate_record().unwrap(), vec!["A|1", "O'Brian"]);
+
+    	dsp.generate_csv(2, &String::from("./tests/samples/generated-dialect.csv")).unwrap();
+    	let mut generated = String::new();
+    	File::open("./tests/samples/generated-dialect.csv").unwrap().read_to_string(&mut generated).unwrap();
+
+    	assert_eq!(generated, "code|name\n'A|1'|'O\\'Brian'\n'A|1'|'O\\'Brian'\n");
+    }
+
+    #[test]
+    // ensure the DataSampleParser names the columns of csv data without a header row by their position
+    fn test_csv_dialect_without_headers(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.set_csv_dialect(CsvDialect::new().with_headers(false));
+    	dsp.analyze_csv_data(&String::from("Aaron,Aaberg\nAaron,Aaberg")).unwrap();
+
+    	assert_eq!(dsp.extract_headers(), vec!["column_1", "column_2"]);
+    	assert_eq!(dsp.generate_by_field_name("column_2".to_string()).unwrap(), "Aaberg");
+
+    	// the generated csv file has no header row, unless it is written explicitly
+    	dsp.generate_csv(1, &String::from("./tests/samples/generated-no-headers.csv")).unwrap();
+    	let mut generated = String::new();
+    	File::open("./tests/samples/generated-no-headers.csv").unwrap().read_to_string(&mut generated).unwrap();
+    	assert_eq!(generated, "Aaron,Aaberg\n");
+
+    	dsp.set_write_headers(true);
+    	dsp.generate_csv(1, &String::from("./tests/samples/generated-no-headers.csv")).unwrap();
+    	let mut generated = String::new();
+    	File::open("./tests/samples/generated-no-headers.csv").unwrap().read_to_string(&mut generated).unwrap();
+    	assert_eq!(generated, "column_1,column_2\nAaron,Aaberg\n");
+    }
+
+    #[test]
+    // ensure the column names that have been set are used instead of the header row
+    fn test_column_names(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.set_column_names(vec!["first".to_string(), "last".to_string()]);
+    	dsp.analyze_csv_data(&String::from("firstname,lastname\nAaron,Aaberg")).unwrap();
+
+    	assert_eq!(dsp.extract_headers(), vec!["first", "last"]);
+    	assert!(dsp.generate_by_field_name("firstname".to_string()).is_err());
+    }
+
+    #[test]
+    // ensure the DataSampleParser flattens the JSON documents into field paths and remembers their structure
+    fn test_analyze_json_file(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_json_file(&String::from("./tests/samples/sample-orders.json")).unwrap();
+
+    	assert_eq!(dsp.extract_headers(), vec!["id", "customer.name", "customer.address.city", "customer.address.zip", "items[].sku", "items[].qty", "items[].price", "paid", "note"]);
+    	assert_eq!(dsp.generate_record().unwrap().len(), 9);
+
+    	let structure = dsp.json_structure().unwrap();
+    	assert_eq!(structure.documents, 4);
+    	assert_eq!(structure.presence("", "note"), 0.5);
+    	assert_eq!(structure.nodes["items"].lengths.keys().cloned().collect::<Vec<u32>>(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    // ensure the DataSampleParser analyzes NDJSON the same as a JSON array of the documents
+    fn test_analyze_ndjson_file(){
+    	let mut json = DataSampleParser::new();
+    	json.analyze_json_file(&String::from("./tests/samples/sample-orders.json")).unwrap();
+    	let mut ndjson = DataSampleParser::new();
+    	ndjson.analyze_ndjson_file(&String::from("./tests/samples/sample-orders.ndjson")).unwrap();
+
+    	assert_eq!(ndjson.extract_headers(), json.extract_headers());
+    	assert_eq!(ndjson.json_structure(), json.json_structure());
+    }
+
+    #[test]
+    // ensure the DataSampleParser returns a parse error for JSON documents that aren't objects
+    fn test_analyze_json_not_objects(){
+    	let mut dsp = DataSampleParser::new();
+
+    	match dsp.analyze_json_data(&String::from("[{\"id\": 1}, 2]")) {
+    		Err(Error::Parse(msg)) => assert_eq!(msg, "The JSON document 2 isn't an object"),
+    		_ => panic!("expected a parse error"),
+    	}
+    	match dsp.analyze_ndjson_data(&String::from("{\"id\": 1}\n{\"id\": ")) {
+    		Err(Error::Parse(msg)) => assert!(msg.starts_with("Line 2 of the NDJSON data")),
+    		_ => panic!("expected a parse error"),
+    	}
+    }
+
+    #[test]
+    // ensure the DataSampleParser merges the structures of the JSON documents
+    fn test_merge_json_structure(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_json_data(&String::from("{\"id\": 1}")).unwrap();
+    	let mut other = DataSampleParser::new();
+    	other.analyze_json_data(&String::from("{\"id\": 2, \"note\": \"x\"}")).unwrap();
+
+    	dsp.merge(&other);
+
+    	assert_eq!(dsp.extract_headers(), vec!["id", "note"]);
+    	assert_eq!(dsp.json_structure().unwrap().presence("", "note"), 0.5);
+    }
+
+    #[test]
+    // ensure the DataSampleParser generates JSON files of flat documents keyed by the field names
+    fn test_generate_json_file(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("code,count,valid,note\nAA,12,true,\nAA,12,true,")).unwrap();
+    	dsp.generate_json(5, &String::from("./tests/samples/generated-flat.json")).unwrap();
+
+    	let mut data = String::new();
+    	File::open("./tests/samples/generated-flat.json").unwrap().read_to_string(&mut data).unwrap();
+    	let documents = match serde_json::from_str(&data).unwrap() {
+    		JsonValue::Array(documents) => documents,
+    		_ => panic!("expected an array of documents"),
+    	};
+
+    	assert_eq!(documents.len(), 5);
+    	assert_eq!(documents[0].to_string(), r#"{"code":"AA","count":"12","valid":"true","note":""}"#);
+
+    	dsp.set_json_format(JsonFormat::new().with_typed_values(true));
+    	assert_eq!(dsp.generate_document().unwrap().to_string(), r#"{"code":"AA","count":12,"valid":true,"note":null}"#);
+    }
+
+    #[test]
+    // ensure the DataSampleParser rebuilds the nested structure of the analyzed JSON documents
+    fn test_generate_ndjson_nested(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_json_file(&String::from("./tests/samples/sample-orders.json")).unwrap();
+    	dsp.set_json_format(JsonFormat::new().with_nested(true).with_typed_values(true));
+    	dsp.generate_ndjson(20, &String::from("./tests/samples/generated-orders.ndjson")).unwrap();
+
+    	let mut data = String::new();
+    	File::open("./tests/samples/generated-orders.ndjson").unwrap().read_to_string(&mut data).unwrap();
+    	assert_eq!(data.lines().count(), 20);
+
+    	for line in data.lines() {
+    		let document: serde_json::Value = serde_json::from_str(line).unwrap();
+    		assert!(document["id"].is_number());
+    		assert!(document["customer"]["address"]["zip"].is_string());
+    		assert!(document["paid"].is_boolean());
+    		assert!(document["items"].as_array().unwrap().len() <= 3);
+    		for item in document["items"].as_array().unwrap() {
+    			assert!(item["qty"].is_number());
+    		}
+    	}
+    }
+
+    #[test]
+    // ensure the DataSampleParser generates the same nested JSON documents when using the same seed
+    fn test_generate_document_with_seed(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_json_file(&String::from("./tests/samples/sample-orders.json")).unwrap();
+    	dsp.set_json_format(JsonFormat::new().with_nested(true));
+
+    	dsp.set_seed(2020);
+    	let first = (0..5).map(|_| dsp.generate_document().unwrap()).collect::<Vec<JsonValue>>();
+    	dsp.set_seed(2020);
+    	let second = (0..5).map(|_| dsp.generate_document().unwrap()).collect::<Vec<JsonValue>>();
+
+    	assert_eq!(first, second);
+    }
+
+    #[test]
+    // ensure the DataSampleParser generates a SQL file with the inferred column types and escaped values
+    fn test_generate_sql_file(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("last name,code,qty,price\nO'Brian,000,2,19.99\nO'Brian,000,2,19.99")).unwrap();
+    	dsp.set_sql_format(SqlFormat::new().with_table("orders").with_batch_size(2));
+    	dsp.generate_sql(3, &String::from("./tests/samples/generated-orders.sql")).unwrap();
+
+    	let mut sql = String::new();
+    	File::open("./tests/samples/generated-orders.sql").unwrap().read_to_string(&mut sql).unwrap();
+
+    	assert_eq!(sql, concat!(
+    		"CREATE TABLE \"orders\" (\n  \"last name\" VARCHAR(7),\n  \"code\" VARCHAR(3),\n  \"qty\" INTEGER,\n  \"price\" NUMERIC(4, 2)\n);\n\n",
+    		"INSERT INTO \"orders\" (\"last name\", \"code\", \"qty\", \"price\") VALUES\n('O''Brian', '000', 2, 19.99),\n('O''Brian', '000', 2, 19.99);\n",
+    		"INSERT INTO \"orders\" (\"last name\", \"code\", \"qty\", \"price\") VALUES\n('O''Brian', '000', 2, 19.99);\n",
+    	));
+    }
+
+    #[test]
+    // ensure the DataSampleParser writes the generated records in batches with the column types inferred from all the records
+    fn test_generate_sql_batches(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+    	dsp.set_sql_format(SqlFormat::new().with_table("people").with_batch_size(3));
+    	dsp.set_seed(5);
+    	dsp.generate_sql(10, &String::from("./tests/samples/generated-people.sql")).unwrap();
+
+    	let mut sql = String::new();
+    	File::open("./tests/samples/generated-people.sql").unwrap().read_to_string(&mut sql).unwrap();
+
+    	dsp.set_seed(5);
+    	let records = (0..10).map(|_| dsp.generate_record().unwrap()).collect::<Vec<Vec<String>>>();
+    	let columns = dsp.fields().iter().enumerate().map(|(idx, field)| {
+    		SqlColumn::infer(field, &dsp.profiles[field], &records.iter().map(|r| r[idx].as_str()).collect::<Vec<&str>>())
+    	}).collect::<Vec<SqlColumn>>();
+    	let mut expected = Vec::new();
+    	dsp.sql_format.write(&mut expected, &columns, &records).unwrap();
+
+    	assert_eq!(sql, String::from_utf8(expected).unwrap());
+    	assert_eq!(sql.matches("INSERT INTO").count(), 4);
+    }
+
+    #[test]
+    // ensure the DataSampleParser generates the records of a SQL file in the PostgreSQL COPY text format
+    fn test_generate_sql_copy(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("path\nC:\\temp\nC:\\temp")).unwrap();
+    	dsp.set_sql_format(SqlFormat::new().with_statement(SqlStatement::Copy).with_create_table(false));
+    	dsp.generate_sql(2, &String::from("./tests/samples/generated-copy.sql")).unwrap();
+
+    	let mut sql = String::new();
+    	File::open("./tests/samples/generated-copy.sql").unwrap().read_to_string(&mut sql).unwrap();
+
+    	assert_eq!(sql, "COPY \"test_data\" (\"path\") FROM STDIN;\nC:\\\\temp\nC:\\\\temp\n\\.\n");
+    }
+
+    #[test]
+    // ensure the DataSampleParser doesn't generate a SQL file of a table without columns
+    fn test_generate_sql_no_columns(){
+    	let mut dsp = DataSampleParser::new();
+
+    	match dsp.generate_sql(2, &String::from("./tests/samples/generated-empty.sql")) {
+    		Err(Error::Config(msg)) => assert_eq!(msg, "The sql table \"test_data\" must have at least 1 column"),
+    		_ => panic!("expected a configuration error"),
+    	}
+    }
+
+    #[test]
+    // ensure the DataSampleParser generates records using an iterator
+    fn test_records(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("code,number\nAA,12\nAA,12")).unwrap();
+
+    	let records = dsp.records().take(3).collect::<Vec<Vec<String>>>();
+
+    	assert_eq!(records, vec![vec!["AA".to_string(), "12".to_string()]; 3]);
+    	assert_eq!(DataSampleParser::new().records().count(), 0);
+    }
+
+    #[test]
+    // ensure the DataSampleParser doesn't generate values of the data sample in the novel-value mode
+    fn test_generate_record_novel(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("code\nAA\nAA")).unwrap();
+
+    	dsp.set_novel_mode(Some(NovelMode::new(false, 10))).unwrap();
+
+    	match dsp.generate_record() {
+    		Err(Error::NoNovelValue(_)) => {},
+    		_ => panic!("expected no novel value"),
+    	}
+    }
+
+    #[test]
+    // ensure the DataSampleParser can't be set to the novel-value mode without the hashes of the data sample
+    fn test_novel_mode_without_entity_hashes(){
+    	let mut dsp = DataSampleParser::new();
+    	dsp.analyze_csv_data(&String::from("code\nAA\nAB")).unwrap();
+
+    	let mut restored: DataSampleParser = serde_json::from_str(&serde_json::to_string(&dsp).unwrap()).unwrap();
+
+    	match restored.set_novel_mode(Some(NovelMode::new(false, 10))) {
+    		Err(Error::Config(_)) => {},
+    		_ => panic!("expected a configuration error"),
+    	}
+    }
+
+    #[test]
+    // ensure the DataSampleParser generates the same csv file when using the same seed
+    fn test_generate_csv_with_seed(){
+    	let mut dsp =  DataSampleParser::from_file(&String::from("./tests/samples/sample-01-dsp")).unwrap();
+
+    	dsp.set_seed(2020);
+    	dsp.generate_csv(50, &String::from("./tests/samples/generated-seed-a.csv")).unwrap();
+    	dsp.set_seed(2020);
+    	dsp.generate_csv(50, &String::from("./tests/samples/generated-seed-b.csv")).unwrap();
+
+    	let mut first = String::new();
+    	let mut second = String::new();
+    	File::open("./tests/samples/generated-seed-a.csv").unwrap().read_to_string(&mut first).unwrap();
+    	File::open("./tests/samples/generated-seed-b.csv").unwrap().read_to_string(&mut second).unwrap();
+
+    	assert_eq!(first, second);
+    }
+
     #[test]
     // ensure the DataSampleParser object can recognize the difference between realistic data and unrealistic generated data
     fn test_levenshtein_test(){
@@ -839,7 +2217,7 @@ mod tests {
     	let mut dsp = DataSampleParser::new();
     	dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
 
-    	println!("My new name is {} {}", dsp.generate_record()[0], dsp.generate_record()[1]);
+    	println!("My new name is {} {}", dsp.generate_record().unwrap()[0], dsp.generate_record().unwrap()[1]);
 
     	assert!(true);
     }
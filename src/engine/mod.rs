@@ -23,6 +23,30 @@
 //! }
 //! ```
 //! 
+//! # MarkovChain
+//! The MarkovChain is a character model (n-gram) that counts which chars follow the preceding chars of an entity.
+//! It is built from the Facts of the analyzed entities and used to condition each generated char on the chars that were generated before it.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::engine::{MarkovChain, PatternDefinition};
+//!
+//! fn main() {
+//!     // a chain that uses up to 2 preceding chars as the context
+//!     let mut chain = MarkovChain::new(2);
+//!     let rslt = PatternDefinition::new().analyze("Hello");
+//!
+//!     chain.learn(&rslt.1);
+//!
+//!     // the lower case consonant that follows "He" is 'l'
+//!     let options = chain.next_options('c', &['H','e']).unwrap();
+//!     assert_eq!(options.get(&'l'), Some(&1));
+//! }
+//! ```
+//!
 //! # PatternDefinition
 //! The PatternDefinition provides functionality to retrieve symbols that are used in defining a pattern.
 //!
@@ -36,6 +60,19 @@
 //! ~ = special regex character [RegExSpcChar]</br>
 //! S = white space [WhiteSpace]</br>
 //! p = punctuation [Punctuation]</br>
+//! L = letter without case, (e.g.: 東) [LetterOther]</br>
+//! M = combining mark, (e.g.: the accent of é when written as e + ◌́) [Mark]</br>
+//! $ = symbol, (e.g.: € or ©) [Symbol]</br>
+//!
+//! Characters are symbolized by their Unicode general category, so that accented and non-Latin letters keep their case,
+//! (e.g.: 'É' is an upper case vowel and 'Ж' an upper case consonant). The punctuation and symbol categories only apply to non-ASCII characters,
+//! so the symbols of ASCII characters are the same as in earlier versions and saved Profiles still match newly analyzed data,
+//! (i.e.: `. , / # ! $ % & * ; : { } = ( ) ?`, the backtick, `~` and `\ ] ^ _` are punctuation `p`, and the other ASCII characters that aren't letters,
+//! digits or white space, e.g.: `' - @ + |`, are unknown `@`). The only exception is `|`, which was matched as a vowel by mistake.
+//! The special regex character `~` [RegExSpcChar] is kept for compatibility, but no character is symbolized as it by the built-in rules.
+//!
+//! The vowels are the vowels of the Latin alphabet (with or without accents), so the letters of other scripts (e.g.: Cyrillic or Greek vowels)
+//! are symbolized as consonants. Use a user-defined symbol class (see _SymbolClass_) to symbolize the vowels of other scripts.
 //!
 //! ## Example
 //!
@@ -46,24 +83,40 @@
 //! 
 //! fn main() {
 //! 	let pttrn_def = PatternDefinition::new();
-//!     println!("Upper case vowel symbol: {:?}", pttrn_def.get(&"VowelUpper".to_string()));
+//!     println!("Upper case vowel symbol: {:?}", pttrn_def.get(&"VowelUpper".to_string()).unwrap());
 //! }
 //! ```
 
+use rand::Rng;
 use regex::Regex;
 use serde_json;
-use std::collections::BTreeMap;
-use std::sync::mpsc::{Sender, Receiver};
-use std::sync::mpsc;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
+use std::io::{BufRead, BufReader, Read};
 use std::thread;
 
-use crate::Profile;
+use crate::{Error, Profile};
+use crate::shared::AliasTable;
 //use async_trait::async_trait;
 
 #[allow(dead_code)]
 type PatternMap  = BTreeMap<String, char>;
+type TransitionMap = BTreeMap<String, BTreeMap<char, u32>>;
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+type FactKey = (char, u32, u32, u32);
+type PriorFactKey = (char, u32, u32, u32, char);
+
+/// The number of entities that are analyzed at a time when the entities are streamed (see _Engine::profile_entities_from_iter()_)
+pub const STREAM_BATCH_SIZE: usize = 10_000;
+/// The default number of preceding chars used as the context of a MarkovChain
+pub const DEFAULT_MARKOV_ORDER: u8 = 2;
+/// The weight of a Fact that matches the pattern placeholder, index offset, starts_with and ends_with of the char to generate
+const FACT_WEIGHT: u64 = 3;
+/// The additional weight of a matching Fact whose prior_key is the char that was generated before
+const PRIOR_KEY_WEIGHT: u64 = 2;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Represents a Fact for a character in a sample data entity that has been analyzed
 pub struct Fact{
 	/// the char that the fact defines (.e.g: 'a', '1', '%', etc.)
@@ -132,15 +185,15 @@ impl Fact {
 	///
 	/// fn main() {
 	///		let serialized = "{\"key\":\"r\",\"prior_key\":null,\"next_key\":null,\"pattern_placeholder\":\"c\",\"starts_with\":0,\"ends_with\":0,\"index_offset\":2}";
-    ///		let mut fact = Fact::from_serialized(&serialized);
+    ///		let mut fact = Fact::from_serialized(&serialized).unwrap();
     ///     fact.set_prior_key('a');
     ///		fact.set_next_key('e');
     ///
     ///		assert_eq!(fact.pattern_placeholder, 'c');
     /// }
     /// ```
-	pub fn from_serialized(serialized: &str) -> Fact {
-		serde_json::from_str(&serialized).unwrap()
+	pub fn from_serialized(serialized: &str) -> Result<Fact, Error> {
+		serde_json::from_str(serialized).map_err(Error::from)
 	}
 
     /// This function converts the Fact to a serialize JSON string.
@@ -211,6 +264,675 @@ impl Fact {
 	}
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents a distinct Fact with its count of occurrence, as it is archived
+pub struct CountedFact {
+	/// the Fact
+	#[serde(flatten)]
+	pub fact: Fact,
+	/// the number of times the Fact occurred in the analyzed entities
+	pub count: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+/// Represents the archived Facts of a Profile, which are either counted or (in archives of earlier versions) listed per processor
+enum FactArchive {
+	Counted(Vec<CountedFact>),
+	Listed(Vec<Vec<Fact>>),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(from = "FactArchive", into = "Vec<CountedFact>")]
+/// Represents the distinct Facts of the analyzed entities with their count of occurrence
+pub struct FactCounts {
+	counts: BTreeMap<Fact, u32>,
+}
+
+impl From<FactArchive> for FactCounts {
+	fn from(archive: FactArchive) -> Self {
+		let mut facts = FactCounts::new();
+
+		match archive {
+			FactArchive::Counted(list) => {
+				for cf in list {
+					facts.add_count(cf.fact, cf.count);
+				}
+			},
+			FactArchive::Listed(processors) => {
+				facts = processors.into_iter().flatten().collect();
+			},
+		}
+
+		facts
+	}
+}
+
+impl FromIterator<Fact> for FactCounts {
+	fn from_iter<I: IntoIterator<Item = Fact>>(iter: I) -> Self {
+		let mut facts = FactCounts::new();
+
+		for fact in iter {
+			facts.add(fact);
+		}
+
+		facts
+	}
+}
+
+impl From<FactCounts> for Vec<CountedFact> {
+	fn from(facts: FactCounts) -> Self {
+		facts.counts.into_iter().map(|(fact, count)| CountedFact { fact, count }).collect()
+	}
+}
+
+impl FactCounts {
+	/// Constructs a new, empty FactCounts
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::FactCounts;
+	///
+	/// fn main() {
+	/// 	let facts = FactCounts::new();
+	///
+	///		assert!(facts.is_empty());
+	/// }
+	/// ```
+	pub fn new() -> FactCounts {
+		FactCounts {
+			counts: BTreeMap::new(),
+		}
+	}
+
+	/// This function counts an occurrence of a Fact
+	///
+	/// # Arguments
+	///
+	/// * `fact: Fact` - The Fact that occurred.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{Fact, FactCounts};
+	///
+	/// fn main() {
+	/// 	let mut facts = FactCounts::new();
+	///		facts.add(Fact::new('r','c',0,0,2));
+	///		facts.add(Fact::new('r','c',0,0,2));
+	///
+	///		assert_eq!(facts.len(), 1);
+	///		assert_eq!(facts.total(), 2);
+	/// }
+	/// ```
+	pub fn add(&mut self, fact: Fact) {
+		self.add_count(fact, 1);
+	}
+
+	/// This function counts a number of occurrences of a Fact
+	///
+	/// # Arguments
+	///
+	/// * `fact: Fact` - The Fact that occurred.</br>
+	/// * `count: u32` - The number of times the Fact occurred.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{Fact, FactCounts};
+	///
+	/// fn main() {
+	/// 	let mut facts = FactCounts::new();
+	///		facts.add_count(Fact::new('r','c',0,0,2), 5);
+	///
+	///		assert_eq!(facts.total(), 5);
+	/// }
+	/// ```
+	pub fn add_count(&mut self, fact: Fact, count: u32) {
+		*self.counts.entry(fact).or_insert(0) += count;
+	}
+
+	/// This function adds the counts of another FactCounts to this FactCounts
+	///
+	/// # Arguments
+	///
+	/// * `other: &FactCounts` - The FactCounts to add.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{Fact, FactCounts};
+	///
+	/// fn main() {
+	/// 	let mut facts = FactCounts::new();
+	/// 	let mut other = FactCounts::new();
+	///		facts.add(Fact::new('r','c',0,0,2));
+	///		other.add(Fact::new('r','c',0,0,2));
+	///
+	///		facts.merge(&other);
+	///
+	///		assert_eq!(facts.total(), 2);
+	/// }
+	/// ```
+	pub fn merge(&mut self, other: &FactCounts) {
+		for (fact, count) in other.iter() {
+			self.add_count(fact.clone(), *count);
+		}
+	}
+
+	/// This function returns an iterator over the distinct Facts and their counts of occurrence
+	pub fn iter(&self) -> impl Iterator<Item = (&Fact, &u32)> {
+		self.counts.iter()
+	}
+
+	/// This function returns the number of distinct Facts
+	pub fn len(&self) -> usize {
+		self.counts.len()
+	}
+
+	/// This function returns true if no Facts have been counted
+	pub fn is_empty(&self) -> bool {
+		self.counts.is_empty()
+	}
+
+	/// This function returns the total number of occurrences of all the Facts
+	pub fn total(&self) -> u64 {
+		self.counts.values().map(|c| *c as u64).sum()
+	}
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents a Markov chain (n-gram model) of the chars in the sample data entities that have been analyzed
+pub struct MarkovChain {
+	/// the highest number of preceding chars that are used as the context for the next char (0 = disabled)
+	pub order: u8,
+	/// the count of the next chars for each state (the pattern placeholder of the next char followed by the preceding chars)
+	pub transitions: TransitionMap,
+}
+
+impl Default for MarkovChain {
+	fn default() -> Self {
+		MarkovChain::new(DEFAULT_MARKOV_ORDER)
+	}
+}
+
+impl MarkovChain {
+	/// Constructs a new MarkovChain
+	///
+	/// # Arguments
+	///
+	/// * `order: u8` - The highest number of preceding chars to use as the context for the next char (e.g.: 1 = bigrams, 2 = trigrams).</br>
+	///   An order of 0 disables the chain.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::MarkovChain;
+	///
+	/// fn main() {
+	/// 	let chain = MarkovChain::new(3);
+	///
+	///		assert_eq!(chain.order, 3);
+	/// }
+	/// ```
+	pub fn new(order: u8) -> MarkovChain {
+		MarkovChain {
+			order,
+			transitions: TransitionMap::new(),
+		}
+	}
+
+	/// This function returns the key of a state in the chain
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the next char.</br>
+	/// * `context: &[char]` - The preceding chars (in the order they appear in the entity).</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::MarkovChain;
+	///
+	/// fn main() {
+	///		assert_eq!(MarkovChain::state('v', &['S','m']), "vSm");
+	/// }
+	/// ```
+	pub fn state(placeholder: char, context: &[char]) -> String {
+		let mut state = String::with_capacity(context.len() + 1);
+		state.push(placeholder);
+		state.extend(context.iter());
+		state
+	}
+
+	/// This function counts the transitions between the chars of an analyzed entity, for every order up to the order of the chain
+	///
+	/// # Arguments
+	///
+	/// * `facts: &[Fact]` - The Facts of an analyzed entity (one for each char in the entity, in order).</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{MarkovChain, PatternDefinition};
+	///
+	/// fn main() {
+	/// 	let mut chain = MarkovChain::new(1);
+	///		let rslt = PatternDefinition::new().analyze("OK");
+	///
+	///		chain.learn(&rslt.1);
+	///
+	///		assert_eq!(chain.transitions.len(), 1);
+	/// }
+	/// ```
+	pub fn learn(&mut self, facts: &[Fact]) {
+		let keys = facts.iter().map(|f| f.key).collect::<Vec<char>>();
+
+		for (i, fact) in facts.iter().enumerate() {
+			for k in 1..=(self.order as usize).min(i) {
+				let state = MarkovChain::state(fact.pattern_placeholder, &keys[i-k..i]);
+				*self.transitions.entry(state).or_default().entry(fact.key).or_insert(0) += 1;
+			}
+		}
+	}
+
+	/// This function adds the transitions of another MarkovChain to this chain.
+	/// When the chains have a different order, the lower order is kept and the states of the higher orders are removed,
+	/// (since they would never be used to generate the chars).
+	///
+	/// # Arguments
+	///
+	/// * `other: &MarkovChain` - The MarkovChain to add.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{MarkovChain, PatternDefinition};
+	///
+	/// fn main() {
+	/// 	let mut chain = MarkovChain::new(1);
+	/// 	let mut other = MarkovChain::new(1);
+	///		chain.learn(&PatternDefinition::new().analyze("OK").1);
+	///		other.learn(&PatternDefinition::new().analyze("OK").1);
+	///
+	///		chain.merge(&other);
+	///
+	///		assert_eq!(chain.transitions.get("CO").unwrap().get(&'K'), Some(&2));
+	/// }
+	/// ```
+	pub fn merge(&mut self, other: &MarkovChain) {
+		let order = self.order.min(other.order);
+		// a state is the pattern placeholder followed by its context
+		let within_order = |state: &String| state.chars().count() <= order as usize + 1;
+
+		self.order = order;
+		self.transitions.retain(|state, _next| within_order(state));
+
+		for (state, next) in other.transitions.iter().filter(|(state, _next)| within_order(state)) {
+			let counts = self.transitions.entry(state.clone()).or_default();
+
+			for (key, cnt) in next.iter() {
+				*counts.entry(*key).or_insert(0) += cnt;
+			}
+		}
+	}
+
+	/// This function returns the counts of the chars that can follow the generated chars for the specified pattern placeholder.
+	/// The longest known context (up to the order of the chain) is used, backing off to shorter contexts if the state is unknown.
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the next char.</br>
+	/// * `generated: &[char]` - The chars that have been generated so far.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{MarkovChain, PatternDefinition};
+	///
+	/// fn main() {
+	/// 	let mut chain = MarkovChain::new(2);
+	///		chain.learn(&PatternDefinition::new().analyze("Hello").1);
+	///
+	///		// "xe" is unknown, so the chain backs off to the context "e"
+	///		assert_eq!(chain.next_options('c', &['x','e']).unwrap().get(&'l'), Some(&1));
+	///		assert!(chain.next_options('v', &['x','e']).is_none());
+	/// }
+	/// ```
+	pub fn next_options(&self, placeholder: char, generated: &[char]) -> Option<&BTreeMap<char, u32>> {
+		let n = generated.len();
+
+		for k in (1..=(self.order as usize).min(n)).rev() {
+			if let Some(options) = self.transitions.get(&MarkovChain::state(placeholder, &generated[n-k..])) {
+				return Some(options);
+			}
+		}
+
+		None
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+/// Represents an index of the Facts and the MarkovChain of a Profile, which is used to select each generated char in constant time
+pub struct FactIndex {
+	/// the chars of the Facts, keyed by (pattern placeholder, index offset, starts_with, ends_with)
+	facts: HashMap<FactKey, AliasTable<char>>,
+	/// the chars of the Facts, keyed by (pattern placeholder, index offset, starts_with, ends_with, prior key)
+	prior_facts: HashMap<PriorFactKey, AliasTable<char>>,
+	/// the next chars of the states of the MarkovChain
+	transitions: HashMap<String, AliasTable<char>>,
+	/// the order of the MarkovChain
+	order: u8,
+	/// the chars of the Facts, keyed by (pattern placeholder, starts_with, ends_with)
+	positions: HashMap<(char, u32, u32), AliasTable<char>>,
+	/// the chars of the Facts, keyed by pattern placeholder
+	placeholders: HashMap<char, AliasTable<char>>,
+}
+
+impl FactIndex {
+	/// Constructs a new FactIndex
+	///
+	/// # Arguments
+	///
+	/// * `facts: &FactCounts` - The Facts to index, which are weighted by their count of occurrence.</br>
+	/// * `markov: &MarkovChain` - The MarkovChain to index.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::engine::{FactCounts, FactIndex, MarkovChain, PatternDefinition};
+	/// use rand::thread_rng;
+	///
+	/// fn main() {
+	/// 	let facts = PatternDefinition::new().analyze("Hi").1.into_iter().collect::<FactCounts>();
+	///		let index = FactIndex::new(&facts, &MarkovChain::new(0));
+	///
+	///		assert_eq!(index.pick_fact('C', 0, 2, None, &mut thread_rng()), Some('H'));
+	/// }
+	/// ```
+	pub fn new(facts: &FactCounts, markov: &MarkovChain) -> FactIndex {
+		let mut fact_counts: BTreeMap<FactKey, BTreeMap<char, u64>> = BTreeMap::new();
+		let mut prior_counts: BTreeMap<PriorFactKey, BTreeMap<char, u64>> = BTreeMap::new();
+		let mut position_counts: BTreeMap<(char, u32, u32), BTreeMap<char, u64>> = BTreeMap::new();
+		let mut placeholder_counts: BTreeMap<char, BTreeMap<char, u64>> = BTreeMap::new();
+
+		for (fact, count) in facts.iter() {
+			let key = (fact.pattern_placeholder, fact.index_offset, fact.starts_with, fact.ends_with);
+			let prior = (key.0, key.1, key.2, key.3, fact.prior_key.unwrap_or(' '));
+
+			*fact_counts.entry(key).or_default().entry(fact.key).or_insert(0) += *count as u64;
+			*prior_counts.entry(prior).or_default().entry(fact.key).or_insert(0) += *count as u64;
+			*position_counts.entry((key.0, key.2, key.3)).or_default().entry(fact.key).or_insert(0) += *count as u64;
+			*placeholder_counts.entry(key.0).or_default().entry(fact.key).or_insert(0) += *count as u64;
+		}
+
+		FactIndex {
+			facts: fact_counts.into_iter().map(|(k, v)| (k, AliasTable::new(v.into_iter().collect()))).collect(),
+			prior_facts: prior_counts.into_iter().map(|(k, v)| (k, AliasTable::new(v.into_iter().collect()))).collect(),
+			transitions: markov.transitions.iter().map(|(k, v)| {
+				(k.clone(), AliasTable::new(v.iter().map(|(c, n)| (*c, *n as u64)).collect()))
+			}).collect(),
+			order: markov.order,
+			positions: position_counts.into_iter().map(|(k, v)| (k, AliasTable::new(v.into_iter().collect()))).collect(),
+			placeholders: placeholder_counts.into_iter().map(|(k, v)| (k, AliasTable::new(v.into_iter().collect()))).collect(),
+		}
+	}
+
+	/// This function selects a char from the Facts that match the pattern placeholder and position of the char to generate.
+	/// Facts whose prior key is the char that was generated before are given a higher weight.
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+	/// * `idx: usize` - The index of the char in the pattern.</br>
+	/// * `len: usize` - The length of the pattern.</br>
+	/// * `prior: Option<char>` - The char that was generated before (None for the first char).</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::engine::{FactCounts, FactIndex, MarkovChain, PatternDefinition};
+	/// use rand::thread_rng;
+	///
+	/// fn main() {
+	/// 	let facts = PatternDefinition::new().analyze("Hi").1.into_iter().collect::<FactCounts>();
+	///		let index = FactIndex::new(&facts, &MarkovChain::new(0));
+	///
+	///		assert_eq!(index.pick_fact('v', 1, 2, Some('H'), &mut thread_rng()), Some('i'));
+	///		assert_eq!(index.pick_fact('v', 0, 2, None, &mut thread_rng()), None);
+	/// }
+	/// ```
+	pub fn pick_fact<R: Rng>(&self, placeholder: char, idx: usize, len: usize, prior: Option<char>, rng: &mut R) -> Option<char> {
+		let starts = if idx == 0 { 1 } else { 0 };
+		let ends = if idx + 1 == len { 1 } else { 0 };
+		let key = (placeholder, idx as u32, starts, ends);
+		let facts = self.facts.get(&key)?;
+
+		// the chance of using a Fact whose prior key matches is the sum of both weights
+		let weight = FACT_WEIGHT * facts.total();
+		match self.prior_facts.get(&(key.0, key.1, key.2, key.3, prior.unwrap_or(' '))) {
+			Some(prior_facts) => {
+				let prior_weight = PRIOR_KEY_WEIGHT * prior_facts.total();
+
+				if rng.gen_range(0, weight + prior_weight) < weight {
+					facts.sample(rng).cloned()
+				} else {
+					prior_facts.sample(rng).cloned()
+				}
+			},
+			None => facts.sample(rng).cloned(),
+		}
+	}
+
+	/// This function selects a char, that satisfies the filter, from the Facts that match the pattern placeholder and position of the char to generate.
+	/// See _pick_fact()_.
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+	/// * `idx: usize` - The index of the char in the pattern.</br>
+	/// * `len: usize` - The length of the pattern.</br>
+	/// * `prior: Option<char>` - The char that was generated before (None for the first char).</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	/// * `filter: F` - The function that returns true for the chars that can be selected.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::engine::{FactCounts, FactIndex, MarkovChain, PatternDefinition};
+	/// use rand::thread_rng;
+	///
+	/// fn main() {
+	/// 	let facts = ["Hi", "Ho"].iter().flat_map(|e| PatternDefinition::new().analyze(e).1).collect::<FactCounts>();
+	///		let index = FactIndex::new(&facts, &MarkovChain::new(0));
+	///
+	///		assert_eq!(index.pick_fact_where('v', 1, 2, Some('H'), &mut thread_rng(), |c| *c != 'i'), Some('o'));
+	/// }
+	/// ```
+	pub fn pick_fact_where<R: Rng, F: Fn(&char) -> bool>(&self, placeholder: char, idx: usize, len: usize, prior: Option<char>, rng: &mut R, filter: F) -> Option<char> {
+		let starts = if idx == 0 { 1 } else { 0 };
+		let ends = if idx + 1 == len { 1 } else { 0 };
+		let key = (placeholder, idx as u32, starts, ends);
+		let facts = self.facts.get(&key)?;
+		let prior_facts = self.prior_facts.get(&(key.0, key.1, key.2, key.3, prior.unwrap_or(' ')));
+
+		let weight = FACT_WEIGHT * facts.total_where(&filter);
+		let prior_weight = prior_facts.map_or(0, |p| PRIOR_KEY_WEIGHT * p.total_where(&filter));
+
+		if weight + prior_weight == 0 {
+			return None;
+		}
+
+		if rng.gen_range(0, weight + prior_weight) < weight {
+			facts.sample_where(rng, &filter).cloned()
+		} else {
+			prior_facts.and_then(|p| p.sample_where(rng, &filter)).cloned()
+		}
+	}
+
+	/// This function selects the next char from the MarkovChain based on the chars that have been generated so far.
+	/// The longest known context (up to the order of the chain) is used, backing off to shorter contexts if the state is unknown.
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the next char.</br>
+	/// * `generated: &[char]` - The chars that have been generated so far.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::engine::{FactCounts, FactIndex, MarkovChain, PatternDefinition};
+	/// use rand::thread_rng;
+	///
+	/// fn main() {
+	/// 	let mut chain = MarkovChain::new(2);
+	///		chain.learn(&PatternDefinition::new().analyze("Hello").1);
+	///		let index = FactIndex::new(&FactCounts::new(), &chain);
+	///
+	///		assert_eq!(index.pick_transition('c', &['x','e'], &mut thread_rng()), Some('l'));
+	///		assert_eq!(index.pick_transition('v', &['x','e'], &mut thread_rng()), None);
+	/// }
+	/// ```
+	pub fn pick_transition<R: Rng>(&self, placeholder: char, generated: &[char], rng: &mut R) -> Option<char> {
+		let n = generated.len();
+
+		for k in (1..=(self.order as usize).min(n)).rev() {
+			if let Some(options) = self.transitions.get(&MarkovChain::state(placeholder, &generated[n-k..])) {
+				return options.sample(rng).cloned();
+			}
+		}
+
+		None
+	}
+
+	/// This function selects the next char, that satisfies the filter, from the MarkovChain. See _pick_transition()_.
+	/// If none of the next chars of a state satisfy the filter, the chain backs off to a shorter context.
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the next char.</br>
+	/// * `generated: &[char]` - The chars that have been generated so far.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	/// * `filter: F` - The function that returns true for the chars that can be selected.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::engine::{FactCounts, FactIndex, MarkovChain, PatternDefinition};
+	/// use rand::thread_rng;
+	///
+	/// fn main() {
+	/// 	let mut chain = MarkovChain::new(2);
+	///		chain.learn(&PatternDefinition::new().analyze("Hello").1);
+	///		let index = FactIndex::new(&FactCounts::new(), &chain);
+	///
+	///		assert_eq!(index.pick_transition_where('c', &['e','l'], &mut thread_rng(), |c| *c == 'l'), Some('l'));
+	///		assert_eq!(index.pick_transition_where('c', &['e','l'], &mut thread_rng(), |c| *c != 'l'), None);
+	/// }
+	/// ```
+	pub fn pick_transition_where<R: Rng, F: Fn(&char) -> bool>(&self, placeholder: char, generated: &[char], rng: &mut R, filter: F) -> Option<char> {
+		let n = generated.len();
+
+		for k in (1..=(self.order as usize).min(n)).rev() {
+			if let Some(options) = self.transitions.get(&MarkovChain::state(placeholder, &generated[n-k..])) {
+				if let Some(c) = options.sample_where(rng, &filter) {
+					return Some(*c);
+				}
+			}
+		}
+
+		None
+	}
+
+	/// This function selects a char from the Facts that match the pattern placeholder, regardless of the index of the char.
+	/// The Facts that also match whether the char starts or ends the pattern are preferred.
+	/// This is the fallback for patterns of lengths that haven't been analyzed (see _Profile::set_pattern_generalization()_).
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+	/// * `idx: usize` - The index of the char in the pattern.</br>
+	/// * `len: usize` - The length of the pattern.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::engine::{FactCounts, FactIndex, MarkovChain, PatternDefinition};
+	/// use rand::thread_rng;
+	///
+	/// fn main() {
+	/// 	let facts = PatternDefinition::new().analyze("Hi").1.into_iter().collect::<FactCounts>();
+	///		let index = FactIndex::new(&facts, &MarkovChain::new(0));
+	///
+	///		assert_eq!(index.pick_fact('v', 3, 4, None, &mut thread_rng()), None);
+	///		assert_eq!(index.pick_any('v', 3, 4, &mut thread_rng()), Some('i'));
+	/// }
+	/// ```
+	pub fn pick_any<R: Rng>(&self, placeholder: char, idx: usize, len: usize, rng: &mut R) -> Option<char> {
+		self.pick_any_where(placeholder, idx, len, rng, |_| true)
+	}
+
+	/// This function selects a char, that satisfies the filter, from the Facts that match the pattern placeholder, regardless of the index of the char.
+	/// See _pick_any()_.
+	///
+	/// # Arguments
+	///
+	/// * `placeholder: char` - The pattern placeholder of the char to generate.</br>
+	/// * `idx: usize` - The index of the char in the pattern.</br>
+	/// * `len: usize` - The length of the pattern.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	/// * `filter: F` - The function that returns true for the chars that can be selected.</br>
+	pub fn pick_any_where<R: Rng, F: Fn(&char) -> bool>(&self, placeholder: char, idx: usize, len: usize, rng: &mut R, filter: F) -> Option<char> {
+		let starts = if idx == 0 { 1 } else { 0 };
+		let ends = if idx + 1 == len { 1 } else { 0 };
+
+		self.positions.get(&(placeholder, starts, ends)).and_then(|p| p.sample_where(rng, &filter))
+			.or_else(|| self.placeholders.get(&placeholder).and_then(|p| p.sample_where(rng, &filter)))
+			.cloned()
+	}
+}
+
+#[derive(Clone, Debug)]
 /// Represents a symbolic pattern of an entity (String)
 pub struct Pattern {
 	/// The regex rule used to find upper case consonants
@@ -227,26 +949,147 @@ pub struct Pattern {
 	regex_punctuation: Regex,
 	/// The regex rule used to find white spaces
 	regex_space: Regex,
+	/// The regex rule used to find (non-ASCII) symbols
+	regex_symbol: Regex,
+	/// The regex rule used to find letters that have no case
+	regex_letter_other: Regex,
+	/// The regex rule used to find combining marks
+	regex_mark: Regex,
+}
+
+lazy_static! {
+    // the regex rules are compiled once, since compiling the Unicode classes is expensive
+    static ref DEFAULT_PATTERN: Pattern = Pattern {
+        // NOTE: the vowels are matched before the consonants, so the consonants are all the other cased letters,
+        //       (only the Latin vowels are listed, so the vowels of other scripts, e.g.: Cyrillic or Greek, are consonants)
+        regex_consonant_upper: Regex::new(r"[\p{Lu}\p{Lt}]").unwrap(),
+        regex_consonant_lower: Regex::new(r"\p{Ll}").unwrap(),
+        regex_vowel_upper: Regex::new(r"[AEIOUÀÁÂÃÄÅÆÈÉÊËÌÍÎÏÒÓÔÕÖØÙÚÛÜĀĂĄĒĔĖĘĚĨĪĬĮİŌŎŐŒŨŪŬŮŰŲ]").unwrap(),
+        regex_vowel_lower: Regex::new(r"[aeiouàáâãäåæèéêëìíîïòóôõöøùúûüāăąēĕėęěĩīĭįıōŏőœũūŭůűų]").unwrap(),
+        regex_numeric: Regex::new(r"\p{N}").unwrap(),
+        // NOTE: the ASCII chars keep the symbols of earlier versions, so only the non-ASCII punctuation and symbols are matched by their category
+        regex_punctuation: Regex::new(r"[.,\\/#!$%\\^&\\*;:{}=\\-_`~()\\?]|[\p{P}&&[^\x00-\x7F]]").unwrap(),
+        regex_space: Regex::new(r"[\s\p{Z}]").unwrap(),
+        regex_symbol: Regex::new(r"[\p{S}&&[^\x00-\x7F]]").unwrap(),
+        regex_letter_other: Regex::new(r"[\p{Lo}\p{Lm}]").unwrap(),
+        regex_mark: Regex::new(r"\p{M}").unwrap(),
+    };
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        DEFAULT_PATTERN.clone()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents a user-defined class of chars that are represented by the same pattern symbol (e.g.: hex digits, currency symbols).
+/// A class matches a char if the char is in its set of chars or matches its regex.
+/// A class whose symbol is the char it matches (e.g.: `-` for `-`) keeps the char literal in the generated data.
+pub struct SymbolClass {
+	/// The (unique) name of the class, (e.g.: "HexDigit")
+	pub name: String,
+	/// The pattern symbol that represents the chars of the class
+	pub symbol: char,
+	/// The chars of the class
+	#[serde(default)]
+	pub chars: Option<String>,
+	/// The regex rule used to find the chars of the class
+	#[serde(default)]
+	pub regex: Option<String>,
+	/// The priority of the class. The built-in classes have a priority of 0, and the classes are matched in decreasing order of priority
+	/// (a user-defined class is matched before a built-in class of the same priority)
+	#[serde(default)]
+	pub priority: i32,
+}
+
+impl SymbolClass {
+	/// Constructs a new SymbolClass from a set of chars
+	///
+	/// # Arguments
+	///
+	/// * `name: &str` - The name of the class.</br>
+	/// * `symbol: char` - The pattern symbol that represents the chars of the class.</br>
+	/// * `chars: &str` - The chars of the class.</br>
+	/// * `priority: i32` - The priority of the class (the built-in classes have a priority of 0).</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::SymbolClass;
+	///
+	/// fn main() {
+	/// 	let hex = SymbolClass::from_chars("HexDigit", 'h', "0123456789abcdef", 1);
+	///
+	///		assert_eq!(hex.symbol, 'h');
+	/// }
+	/// ```
+	pub fn from_chars(name: &str, symbol: char, chars: &str, priority: i32) -> SymbolClass {
+		SymbolClass {
+			name: name.to_string(),
+			symbol,
+			chars: Some(chars.to_string()),
+			regex: None,
+			priority,
+		}
+	}
+
+	/// Constructs a new SymbolClass from a regex rule
+	///
+	/// # Arguments
+	///
+	/// * `name: &str` - The name of the class.</br>
+	/// * `symbol: char` - The pattern symbol that represents the chars of the class.</br>
+	/// * `regex: &str` - The regex rule used to find the chars of the class.</br>
+	/// * `priority: i32` - The priority of the class (the built-in classes have a priority of 0).</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::SymbolClass;
+	///
+	/// fn main() {
+	/// 	let currency = SymbolClass::from_regex("Currency", '¤', r"\p{Sc}", 1);
+	///
+	///		assert_eq!(currency.regex, Some(r"\p{Sc}".to_string()));
+	/// }
+	/// ```
+	pub fn from_regex(name: &str, symbol: char, regex: &str, priority: i32) -> SymbolClass {
+		SymbolClass {
+			name: name.to_string(),
+			symbol,
+			chars: None,
+			regex: Some(regex.to_string()),
+			priority,
+		}
+	}
 }
 
-impl Default for Pattern {
-    fn default() -> Self {
-        Pattern {
-            regex_consonant_upper: Regex::new(r"[B-DF-HJ-NP-TV-Z]").unwrap(),
-            regex_consonant_lower: Regex::new(r"[b-df-hj-np-tv-z]").unwrap(),
-            regex_vowel_upper: Regex::new(r"[A|E|I|O|U]").unwrap(),
-            regex_vowel_lower: Regex::new(r"[a|e|i|o|u]").unwrap(),
-            regex_numeric: Regex::new(r"[0-9]").unwrap(),
-            regex_punctuation: Regex::new(r"[.,\\/#!$%\\^&\\*;:{}=\\-_`~()\\?]").unwrap(),
-            regex_space: Regex::new(r"[\s]").unwrap(),
-        }
-    }
+#[derive(Clone, Debug)]
+/// Represents a SymbolClass with its compiled regex rule
+struct CustomClass {
+	class: SymbolClass,
+	regex: Option<Regex>,
 }
 
+impl CustomClass {
+	fn is_match(&self, c: char, s: &str) -> bool {
+		self.class.chars.iter().any(|chars| chars.contains(c)) ||
+		self.regex.iter().any(|regex| regex.is_match(s))
+	}
+}
+
+#[derive(Clone, Debug)]
 /// Represents the object managing all the symbols used in pattern definitions
 pub struct PatternDefinition {
     pattern_map: PatternMap,
     pattern: Pattern,
+    /// The user-defined classes, in decreasing order of priority
+    classes: Vec<CustomClass>,
 }
 
 impl PatternDefinition {
@@ -264,7 +1107,7 @@ impl PatternDefinition {
 	/// }
 	/// ```
 	pub fn new() -> PatternDefinition {	
-        let symbols: [char; 9] = ['@','C','c','V','v','#','~','S','p'];
+        let symbols: [char; 12] = ['@','C','c','V','v','#','~','S','p','L','M','$'];
         let mut pttrn_def = PatternMap::new();
 
         pttrn_def.insert("Unknown".to_string(),        symbols[0]);
@@ -276,12 +1119,88 @@ impl PatternDefinition {
 	    pttrn_def.insert("RegExSpcChar".to_string(),   symbols[6]);
 	    pttrn_def.insert("WhiteSpace".to_string(),     symbols[7]);
         pttrn_def.insert("Punctuation".to_string(),    symbols[8]);
+        pttrn_def.insert("LetterOther".to_string(),    symbols[9]);
+        pttrn_def.insert("Mark".to_string(),           symbols[10]);
+        pttrn_def.insert("Symbol".to_string(),         symbols[11]);
 
 		PatternDefinition{
             pattern_map: pttrn_def,
             pattern: Pattern::default(),
+            classes: Vec::new(),
+		}
+    }
+
+    /// Constructs a new PatternDefinition that symbolizes chars using user-defined classes in addition to the built-in classes
+	///
+	/// # Arguments
+	///
+	/// * `classes: &[SymbolClass]` - The user-defined classes.</br>
+	///   NOTE: A class must have a unique name and a set of chars or a (valid) regex rule, otherwise a Parse Error is returned.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::{PatternDefinition, SymbolClass};
+	///
+	/// fn main() {
+	/// 	let classes = vec![
+	///			SymbolClass::from_chars("HexDigit", 'h', "0123456789abcdef", 1),
+	///			SymbolClass::from_chars("Dash", '-', "-", 1),
+	///		];
+	/// 	let mut pttrn_def = PatternDefinition::new_with_classes(&classes).unwrap();
+	///
+	///		assert_eq!(pttrn_def.analyze("0f-A1").0, "hh-Vh");
+	///		assert_eq!(pttrn_def.get("HexDigit").unwrap(), 'h');
+	/// }
+	/// ```
+	pub fn new_with_classes(classes: &[SymbolClass]) -> Result<PatternDefinition, Error> {
+		let mut pttrn_def = PatternDefinition::new();
+
+		for class in classes.iter() {
+			if pttrn_def.pattern_map.contains_key(&class.name) {
+				return Err(Error::Parse(format!("The symbol class {} is already defined", class.name)));
+			}
+
+			if class.chars.is_none() && class.regex.is_none() {
+				return Err(Error::Parse(format!("The symbol class {} has neither chars nor a regex", class.name)));
+			}
+
+			let regex = match &class.regex {
+				Some(r) => Some(Regex::new(r).map_err(|e| Error::Parse(format!("The symbol class {} has an invalid regex: {}", class.name, e)))?),
+				None => None,
+			};
+
+			pttrn_def.pattern_map.insert(class.name.clone(), class.symbol);
+			pttrn_def.classes.push(CustomClass {
+				class: class.clone(),
+				regex,
+			});
 		}
-    }	
+
+		// the sort is stable, so classes of the same priority are matched in the order they were defined
+		pttrn_def.classes.sort_by_key(|c| Reverse(c.class.priority));
+
+		Ok(pttrn_def)
+	}
+
+	/// This function returns the user-defined classes of the PatternDefinition, in decreasing order of priority
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::PatternDefinition;
+	///
+	/// fn main() {
+	/// 	assert!(PatternDefinition::new().classes().is_empty());
+	/// }
+	/// ```
+	pub fn classes(&self) -> Vec<SymbolClass> {
+		self.classes.iter().map(|c| c.class.clone()).collect()
+	}
 
     /// This function converts an entity (&str) into a tuplet (String, Vec<Fact>)</br>
 	///
@@ -305,6 +1224,12 @@ impl PatternDefinition {
 	/// }
 	/// ```
 	pub fn analyze(&mut self, entity: &str) -> (String, Vec<Fact>) {
+		self.factualize_entity(entity)
+	}
+
+	/// This function converts an entity (&str) into a tuplet (String, Vec<Fact>) without borrowing the PatternDefinition mutably,
+	/// so that it can be shared by the worker threads
+	fn factualize_entity(&self, entity: &str) -> (String, Vec<Fact>) {
 		// record the length of the passed value
 		//self.size = entity.len() as u32;
 
@@ -315,11 +1240,11 @@ impl PatternDefinition {
 		let mut facts = Vec::new();
 
 		// record the pattern of the passed value
-		for (i, _c) in entity.chars().enumerate() {
-			//let fact = self.factualize(&entity, i as u32);
-			let idx: u32 = i as u32;
-			let fact = self.factualize(entity, idx);
-			pttrn.push_str(&*fact.pattern_placeholder.to_string());
+		// NOTE: the entity is indexed by chars (not bytes), so multi-byte chars are handled consistently
+		let chars = entity.chars().collect::<Vec<char>>();
+		for i in 0..chars.len() {
+			let fact = self.factualize_chars(&chars, i);
+			pttrn.push(fact.pattern_placeholder);
 			facts.push(fact);
 		}
 		
@@ -347,23 +1272,29 @@ impl PatternDefinition {
 	/// }
 	/// ```
 	pub fn factualize(&mut self, entity: &str, idx: u32) -> Fact {
-		let c = entity.chars().nth(idx as usize).unwrap();
+		let chars = entity.chars().collect::<Vec<char>>();
+		self.factualize_chars(&chars, idx as usize)
+	}
+
+	/// This function converts the char at the index (in chars) of the chars of an entity into a Fact
+	fn factualize_chars(&self, chars: &[char], idx: usize) -> Fact {
+		let c = chars[idx];
 		let pp = self.symbolize_char(c);
-		let pk = if idx > 0 {entity.chars().nth(idx as usize -1)} else {None};
-		let nk = if idx < entity.len() as u32 -1 {entity.chars().nth(idx as usize +1)} else {None};
+		let pk = if idx > 0 {Some(chars[idx-1])} else {None};
+		let nk = chars.get(idx+1).cloned();
 		let sw = if idx == 0 {1} else {0};
-		let ew = if idx == entity.len() as u32 -1 {1} else {0};
+		let ew = if idx + 1 == chars.len() {1} else {0};
 
-		let mut fact = Fact::new(c,pp,sw,ew,idx);
+		let mut fact = Fact::new(c,pp,sw,ew,idx as u32);
 
 		// only if there is a next key
-		if nk.is_some() {
-			&fact.set_next_key(nk.unwrap());
+		if let Some(k) = nk {
+			fact.set_next_key(k);
 		}
 
 		// only if there is a prior key
-		if pk.is_some() {
-			&fact.set_prior_key(pk.unwrap());
+		if let Some(k) = pk {
+			fact.set_prior_key(k);
 		}
 
 		fact
@@ -380,11 +1311,16 @@ impl PatternDefinition {
 	///	
 	/// fn main() {
 	/// 	let pttrn_def = PatternDefinition::new();
-	///     println!("Upper case vowel symbol: {:?}", pttrn_def.get(&"VowelUpper".to_string()));
+	///     println!("Upper case vowel symbol: {:?}", pttrn_def.get(&"VowelUpper".to_string()).unwrap());
+	///
+	///     assert!(pttrn_def.get("Vowel").is_err());
 	/// }
 	/// ```
-	pub fn get(&self, key: &str) -> char {
-		*self.pattern_map.get(key).unwrap()
+	pub fn get(&self, key: &str) -> Result<char, Error> {
+		match self.pattern_map.get(key) {
+			Some(symbol) => Ok(*symbol),
+			None => Err(Error::UnknownSymbol(key.to_string())),
+		}
     }
     
     /// This function converts a char into a pattern symbol
@@ -404,106 +1340,270 @@ impl PatternDefinition {
 	/// ```
     pub fn symbolize_char(&self, c: char) -> char {
         // if you have to escape regex special characters: &*regex::escape(&*$c.to_string())
-    	let mut symbol = self.pattern_map.get("Unknown");
-        let mut found = false;
-        
-        if !found && self.pattern.regex_consonant_upper.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("ConsonantUpper");
-            found = true;
-        }
+        let s = c.to_string();
 
-        if !found && self.pattern.regex_consonant_lower.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("ConsonantLower");
-            found = true;
-        }
+        // the rules are matched in order, so the vowels must be matched before the consonants
+        let rules = [
+            (&self.pattern.regex_vowel_upper, "VowelUpper"),
+            (&self.pattern.regex_vowel_lower, "VowelLower"),
+            (&self.pattern.regex_consonant_upper, "ConsonantUpper"),
+            (&self.pattern.regex_consonant_lower, "ConsonantLower"),
+            (&self.pattern.regex_numeric, "Numeric"),
+            (&self.pattern.regex_space, "WhiteSpace"),
+            (&self.pattern.regex_punctuation, "Punctuation"),
+            (&self.pattern.regex_symbol, "Symbol"),
+            (&self.pattern.regex_letter_other, "LetterOther"),
+            (&self.pattern.regex_mark, "Mark"),
+        ];
 
-        if !found && self.pattern.regex_vowel_upper.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("VowelUpper");
-            found = true;
+        // the user-defined classes with a priority of 0 or more are matched before the built-in classes, and the others after them
+        if let Some(class) = self.classes.iter().filter(|class| class.class.priority >= 0).find(|class| class.is_match(c, &s)) {
+            return class.class.symbol;
         }
 
-        if !found && self.pattern.regex_vowel_lower.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("VowelLower");
-            found = true;
-        }
+        let name = match rules.iter().find(|(regex, _)| regex.is_match(&s)) {
+            Some((_, name)) => name,
+            None => {
+                match self.classes.iter().filter(|class| class.class.priority < 0).find(|class| class.is_match(c, &s)) {
+                    Some(class) => return class.class.symbol,
+                    // if not matched, then use "Unknown" placeholder symbol
+                    None => "Unknown",
+                }
+            },
+        };
 
-        if !found && self.pattern.regex_numeric.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("Numeric");
-            found = true;
-        }
+        self.pattern_map[name]
+    }
+}
 
-        if !found && self.pattern.regex_space.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("WhiteSpace");
-            found = true;
-        }
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Represents the bound on the number of worker threads that are used to analyze the entities.
+/// The entities are split into one chunk per worker, so no more threads than the size of the pool are started,
+/// and the results are returned in the order of the entities.
+/// NOTE: The worker threads aren't kept alive between the calls of _map()_. Each call starts its own (scoped) threads and joins them before it returns,
+/// so the work is handed to the pool in as few calls as possible (e.g.: all the columns of a data sample are analyzed by one call, see _Engine::profile_columns_with_pool()_).
+pub struct WorkerPool {
+	size: usize,
+}
 
-        if !found && self.pattern.regex_punctuation.is_match(&c.to_string()) {
-            symbol = self.pattern_map.get("Punctuation");
-            found = true;
-        }
+impl Default for WorkerPool {
+	fn default() -> Self {
+		WorkerPool::new(0)
+	}
+}
 
-        // if not matched, then use "Unknown" placeholder symbol
-        if !found {
-            symbol = self.pattern_map.get("Unknown");
-        }
+impl WorkerPool {
+	/// Constructs a new WorkerPool
+	///
+	/// # Arguments
+	///
+	/// * `size: usize` - The maximum number of worker threads.</br>
+	///   NOTE: A size of 0 uses the number of available cores.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::WorkerPool;
+	///
+	/// fn main() {
+	/// 	let pool = WorkerPool::new(2);
+	///
+	///		assert_eq!(pool.size(), 2);
+	///		assert!(WorkerPool::default().size() >= 1);
+	/// }
+	/// ```
+	pub fn new(size: usize) -> WorkerPool {
+		let size = match size {
+			0 => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+			_ => size,
+		};
 
-        *symbol.unwrap()
-    }
+		WorkerPool {
+			size,
+		}
+	}
+
+	/// This function returns the maximum number of worker threads
+	pub fn size(&self) -> usize {
+		self.size
+	}
+
+	/// This function applies the function to each of the items using the worker threads, and returns the results in the order of the items.
+	/// The worker threads are started for this call only, (at most one per chunk of the items), and are joined before the function returns.
+	/// If a worker fails (panics), an Error::Worker is returned.
+	///
+	/// # Arguments
+	///
+	/// * `items: &[T]` - The items to process.</br>
+	/// * `f: F` - The function to apply to each item.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::WorkerPool;
+	///
+	/// fn main() {
+	/// 	let lengths = WorkerPool::new(2).map(&["a", "bb", "ccc"], |s| s.len()).unwrap();
+	///
+	///		assert_eq!(lengths, vec![1, 2, 3]);
+	/// }
+	/// ```
+	pub fn map<T, R, F>(&self, items: &[T], f: F) -> Result<Vec<R>, Error>
+	where
+		T: Sync,
+		R: Send,
+		F: Fn(&T) -> R + Sync,
+	{
+		if items.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let chunk_size = items.len().div_ceil(self.size);
+		let f = &f;
+
+		let results = crossbeam::scope(|scope| {
+			let workers = items.chunks(chunk_size).map(|chunk| {
+				scope.spawn(move |_| chunk.iter().map(f).collect::<Vec<R>>())
+			}).collect::<Vec<_>>();
+
+			// join the workers in the order of the chunks, so the order of the results is preserved
+			workers.into_iter().map(|worker| worker.join()).collect::<Vec<_>>()
+		}).map_err(|_| Error::Worker("The worker pool could not be joined".to_string()))?;
+
+		let mut mapped = Vec::with_capacity(items.len());
+		for (idx, result) in results.into_iter().enumerate() {
+			match result {
+				Ok(chunk) => mapped.extend(chunk),
+				Err(e) => {
+					let msg = e.downcast_ref::<&str>().map(|m| m.to_string())
+						.or_else(|| e.downcast_ref::<String>().cloned())
+						.unwrap_or_else(|| "unknown cause".to_string());
+					error!("The worker of chunk {} failed. Error: {}", idx, msg);
+					return Err(Error::Worker(format!("The worker of chunk {} failed because of {}", idx, msg)));
+				},
+			}
+		}
+
+		Ok(mapped)
+	}
 }
 
 pub trait Engine {
-    fn analyze_entities(entities: Vec<String>) -> Vec<(String, Vec<Fact>)> {
-        let (tx, rx): (Sender<(String, Vec<Fact>)>, Receiver<(String, Vec<Fact>)>) = mpsc::channel();
-        let mut children = Vec::new();
-    
-        for entity in entities.clone() {
-            let thread_tx = tx.clone();
-            let child = thread::spawn(move || {
-                thread_tx.send(PatternDefinition::new().analyze(&entity)).unwrap();
-                debug!("PatternDefinition::analyze thread finished for {}", entity);
-            });
-    
-            children.push(child);
-        }
-    
-        let mut results = Vec::new();
-        for entity in entities {
-            results.push(
-                match rx.recv() {
-                    Ok(result) => result,
-                    Err(_) => {
-                        error!("Error: Could not anaylze the entity: {}", entity);
-                        panic!("Error: Could not anaylze the data!")
-                    }
-                }
-            );
-        }
-        
-        for child in children {
-            child.join().expect("Error: Could not anaylze the data!");
-        }
+    fn analyze_entities(entities: Vec<String>) -> Result<Vec<(String, Vec<Fact>)>, Error> {
+        Self::analyze_entities_with_definition(entities, &PatternDefinition::new())
+    }
+
+    fn analyze_entities_with_definition(entities: Vec<String>, definition: &PatternDefinition) -> Result<Vec<(String, Vec<Fact>)>, Error> {
+        Self::analyze_entities_with_pool(entities, definition, &WorkerPool::default())
+    }
+
+    fn analyze_entities_with_pool(entities: Vec<String>, definition: &PatternDefinition, pool: &WorkerPool) -> Result<Vec<(String, Vec<Fact>)>, Error> {
+        let results = pool.map(&entities, |entity| definition.factualize_entity(entity))?;
+        debug!("PatternDefinition::analyze finished for {} entities", results.len());
+
+        Ok(results)
+	}
 
-        results
+	fn profile_entities(profile: Profile, entities: Vec<String>) -> Result<Profile, Error> {
+		Self::profile_entities_with_pool(profile, entities, &WorkerPool::default())
 	}
 
-	fn profile_entities(mut profile: Profile, entities: Vec<String>) -> Result<Profile, String> {
-		let results = Self::analyze_entities(entities);
+	fn profile_entities_with_pool(mut profile: Profile, entities: Vec<String>, pool: &WorkerPool) -> Result<Profile, Error> {
+		// the entities are analyzed using the symbol classes the profile was built with
+		let results = Self::analyze_entities_with_pool(entities, profile.pattern_definition()?, pool)?;
 
 		for result in results {
-			match profile.apply_facts(result.0, result.1) {
-				Ok(_) => {},
-				Err(e) => {
-					return Err(format!("Error: Couldn't apply the Pattern and Facts to the Profile. Error Message: {}", e.to_string()))
-				}
+			if let Err(e) = profile.apply_facts(result.0, result.1) {
+				error!("Couldn't apply the Pattern and Facts to the Profile. Error Message: {}", e);
+				return Err(e);
 			}
 		}
 
 		Ok(profile)
 	}
 	
-	fn profile_entities_with_container(container: EngineContainer) -> Result<Profile, String> {
+	/// Analyzes the entities of several columns (one profile per column) using a single (bounded) scope of worker threads for all the columns,
+	/// so the columns are analyzed at the same time rather than one after another, and folds the results into the profile of each column.
+	/// The profiles are returned in the order of the columns.
+	fn profile_columns_with_pool(mut profiles: Vec<Profile>, columns: Vec<Vec<String>>, pool: &WorkerPool) -> Result<Vec<Profile>, Error> {
+		// the entities of all the columns are tagged with the index of their column, so they can be split into chunks regardless of the column
+		let entities = columns.into_iter().enumerate()
+			.flat_map(|(idx, column)| column.into_iter().map(move |entity| (idx, entity)))
+			.collect::<Vec<(usize, String)>>();
+
+		let results = {
+			// the entities of each column are analyzed using the symbol classes the profile of the column was built with
+			let definitions = profiles.iter_mut()
+				.map(|p| p.pattern_definition().map(|d| &*d))
+				.collect::<Result<Vec<&PatternDefinition>, Error>>()?;
+
+			pool.map(&entities, |(idx, entity)| definitions[*idx].factualize_entity(entity))?
+		};
+
+		for ((idx, _), result) in entities.iter().zip(results) {
+			if let Err(e) = profiles[*idx].apply_facts(result.0, result.1) {
+				error!("Couldn't apply the Pattern and Facts to the Profile. Error Message: {}", e);
+				return Err(e);
+			}
+		}
+
+		Ok(profiles)
+	}
+
+	fn profile_entities_with_container(container: EngineContainer) -> Result<Profile, Error> {
 		Self::profile_entities(container.profile, container.entities)
 	}
+
+	/// Analyzes the entities of an iterator and folds the results into the profile incrementally.
+	/// The entities are analyzed in batches (see STREAM_BATCH_SIZE), so the entities don't need to fit in memory.
+	/// The profile still grows with the distinct patterns and facts of the entities, and keeps a hash of each distinct entity for the novel-value mode,
+	/// so set a limit for the hashes (see _Profile::set_entity_hash_limit()_) to bound the memory when streaming many distinct entities.
+	fn profile_entities_from_iter<I: IntoIterator<Item = String>>(profile: Profile, entities: I) -> Result<Profile, Error> {
+		Self::profile_entities_from_iter_with_pool(profile, entities, &WorkerPool::default())
+	}
+
+	fn profile_entities_from_iter_with_pool<I: IntoIterator<Item = String>>(mut profile: Profile, entities: I, pool: &WorkerPool) -> Result<Profile, Error> {
+		let mut entities = entities.into_iter();
+
+		loop {
+			let batch = entities.by_ref().take(STREAM_BATCH_SIZE).collect::<Vec<String>>();
+
+			if batch.is_empty() {
+				return Ok(profile);
+			}
+
+			profile = Self::profile_entities_with_pool(profile, batch, pool)?;
+		}
+	}
+
+	/// Analyzes each line of the reader as an entity and folds the results into the profile incrementally (see _profile_entities_from_iter()_).
+	/// If a line can't be read, an Error::Io is returned.
+	fn profile_entities_from_reader<R: Read>(profile: Profile, reader: R) -> Result<Profile, Error> {
+		let mut failure = None;
+		let lines = BufReader::new(reader).lines().map_while(|line| {
+			match line {
+				Ok(l) => Some(l),
+				Err(e) => {
+					failure = Some(e);
+					None
+				},
+			}
+		});
+
+		let profile = Self::profile_entities_from_iter(profile, lines)?;
+
+		match failure {
+			Some(e) => {
+				error!("Couldn't read the entities. Error Message: {}", e);
+				Err(Error::from(e))
+			},
+			None => Ok(profile),
+		}
+	}
 }
 
 pub struct EngineContainer {
@@ -530,9 +1630,14 @@ mod tests {
     #[test]
     fn test_fact_new_from_serialized(){
     	let serialized = "{\"key\":\"r\",\"prior_key\":null,\"next_key\":null,\"pattern_placeholder\":\"c\",\"starts_with\":0,\"ends_with\":0,\"index_offset\":2}";
-    	let fact = Fact::from_serialized(&serialized);
+    	let fact = Fact::from_serialized(&serialized).unwrap();
     	assert_eq!(fact.pattern_placeholder, 'c');
     }
+
+    #[test]
+    fn test_fact_new_from_serialized_bad_data(){
+    	assert!(Fact::from_serialized("{\"key\":\"r\"}").is_err());
+    }
     
     #[test]
     fn test_fact_serialize(){
@@ -560,7 +1665,17 @@ mod tests {
     #[test]
     fn test_pattern_definition_new() {
         let pttrn_def = PatternDefinition::new();
-        assert_eq!(pttrn_def.get("VowelUpper"), 'V');
+        assert_eq!(pttrn_def.get("VowelUpper").unwrap(), 'V');
+    }
+
+    #[test]
+    fn test_pattern_definition_get_unknown() {
+        let pttrn_def = PatternDefinition::new();
+
+        match pttrn_def.get("Vowel") {
+            Err(Error::UnknownSymbol(name)) => assert_eq!(name, "Vowel"),
+            _ => panic!("expected an unknown symbol error"),
+        }
     }
 
     #[test]
@@ -570,6 +1685,70 @@ mod tests {
     	assert_eq!(pttrn_def.symbolize_char('A'), 'V');
     }
 
+    #[test]
+    fn test_pattern_definition_symbolize_char_vowel_bar(){
+    	let pttrn_def = PatternDefinition::new();
+
+    	// the | is not a vowel
+    	assert_eq!(pttrn_def.symbolize_char('|'), '@');
+    	// the symbol of the special regex characters is still defined
+    	assert_eq!(pttrn_def.get("RegExSpcChar").unwrap(), '~');
+    }
+
+    #[test]
+    fn test_pattern_definition_symbolize_char_unicode(){
+    	let pttrn_def = PatternDefinition::new();
+
+    	assert_eq!(pttrn_def.symbolize_char('É'), 'V');
+    	assert_eq!(pttrn_def.symbolize_char('ø'), 'v');
+    	assert_eq!(pttrn_def.symbolize_char('Ž'), 'C');
+    	assert_eq!(pttrn_def.symbolize_char('ß'), 'c');
+    	assert_eq!(pttrn_def.symbolize_char('Ж'), 'C');
+    	assert_eq!(pttrn_def.symbolize_char('λ'), 'c');
+    	// only the Latin vowels are vowels
+    	assert_eq!(pttrn_def.symbolize_char('а'), 'c');
+    	assert_eq!(pttrn_def.symbolize_char('Ω'), 'C');
+    	assert_eq!(pttrn_def.symbolize_char('東'), 'L');
+    	assert_eq!(pttrn_def.symbolize_char('٣'), '#');
+    	assert_eq!(pttrn_def.symbolize_char('\u{0301}'), 'M');
+    	assert_eq!(pttrn_def.symbolize_char('€'), '$');
+    	assert_eq!(pttrn_def.symbolize_char('¿'), 'p');
+    	assert_eq!(pttrn_def.symbolize_char('\u{00A0}'), 'S');
+    	assert_eq!(pttrn_def.symbolize_char('\u{0007}'), '@');
+    }
+
+    #[test]
+    fn test_pattern_definition_symbolize_char_custom_classes(){
+        let classes = vec![
+            SymbolClass::from_chars("HexDigit", 'h', "0123456789abcdef", 1),
+            SymbolClass::from_regex("Currency", '¤', r"\p{Sc}", 0),
+            SymbolClass::from_chars("Fallback", 'x', "-e", -1),
+        ];
+        let pttrn_def = PatternDefinition::new_with_classes(&classes).unwrap();
+
+        assert_eq!(pttrn_def.symbolize_char('e'), 'h');
+        assert_eq!(pttrn_def.symbolize_char('$'), '¤');
+        assert_eq!(pttrn_def.symbolize_char('€'), '¤');
+        // the built-in classes are matched before the classes with a negative priority
+        assert_eq!(pttrn_def.symbolize_char('E'), 'V');
+        assert_eq!(pttrn_def.symbolize_char('-'), 'x');
+        assert_eq!(pttrn_def.get("Fallback").unwrap(), 'x');
+    }
+
+    #[test]
+    fn test_pattern_definition_bad_classes(){
+        let duplicate = vec![SymbolClass::from_chars("Numeric", 'n', "0", 1)];
+        let empty = vec![SymbolClass { chars: None, ..SymbolClass::from_chars("Empty", 'e', "", 1) }];
+        let bad_regex = vec![SymbolClass::from_regex("Bad", 'b', "[", 1)];
+
+        for classes in [duplicate, empty, bad_regex].iter() {
+            match PatternDefinition::new_with_classes(classes) {
+                Err(Error::Parse(_)) => {},
+                _ => panic!("expected a parse error"),
+            }
+        }
+    }
+
     #[test]
     fn test_pattern_definition_factualize(){
     	let mut pttrn_def = PatternDefinition::new();
@@ -581,6 +1760,29 @@ mod tests {
     	assert_eq!(fact1.serialize(), fact2.serialize());
     }
 
+    #[test]
+    fn test_pattern_definition_factualize_multibyte(){
+    	let mut pttrn_def = PatternDefinition::new();
+    	let fact = pttrn_def.factualize("Zoë",2);
+
+    	assert_eq!(fact.key, 'ë');
+    	assert_eq!(fact.pattern_placeholder, 'v');
+    	assert_eq!(fact.ends_with, 1);
+    	assert_eq!(fact.prior_key, Some('o'));
+    	assert_eq!(fact.next_key, None);
+    }
+
+    #[test]
+    fn test_pattern_definition_analyze_unicode(){
+        let mut pttrn_def = PatternDefinition::new();
+        let word = pttrn_def.analyze("Müller-Lüdenscheid, 東京");
+
+        assert_eq!(word.0, "Cvccvc@CvcvccccvvcpSLL");
+        assert_eq!(word.1.len(), 22);
+        assert_eq!(word.1.iter().filter(|f| f.ends_with == 1).count(), 1);
+        assert_eq!(word.1[21].key, '京');
+    }
+
     #[test]
     fn test_pattern_definition_analyze(){
         let mut pttrn_def = PatternDefinition::new(); 
@@ -594,12 +1796,174 @@ mod tests {
     fn test_pattern_definition_analyze_multithread(){
         let words = vec!("word-one".to_string(),"word-two".to_string(),"word-three".to_string(),"word-four".to_string(),"word-five".to_string());
 
-        let results = Xtest::analyze_entities(words);
+        let results = Xtest::analyze_entities(words).unwrap();
 
         println!("{:?}", results);
         assert_eq!(results.len(), 5);
 	} 
+
+    #[test]
+    fn test_analyze_entities_with_pool_preserves_order(){
+        let words = (0..100).map(|i| format!("word{}", i)).collect::<Vec<String>>();
+
+        let results = Xtest::analyze_entities_with_pool(words.clone(), &PatternDefinition::new(), &WorkerPool::new(3)).unwrap();
+
+        assert_eq!(results.iter().map(|r| r.1.iter().map(|f| f.key).collect::<String>()).collect::<Vec<String>>(), words);
+    }
+
+    #[test]
+    fn test_profile_columns_with_pool(){
+        let classes = vec![SymbolClass::from_regex("Currency", '¤', r"\p{Sc}", 1)];
+        let profiles = vec![Profile::new(), Profile::new_with_symbol_classes(&classes).unwrap()];
+        let columns = vec![vec!["OK".to_string(), "Hi".to_string(), "No".to_string()], vec!["$1".to_string()]];
+
+        let results = Xtest::profile_columns_with_pool(profiles, columns, &WorkerPool::new(2)).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].pattern_total, 3);
+        assert_eq!(results[0].patterns.get("Cv"), Some(&2));
+        assert_eq!(results[1].pattern_keys, vec!["¤#".to_string()]);
+    }
+
+    #[test]
+    fn test_profile_entities_from_iter(){
+        let words = (0..(STREAM_BATCH_SIZE + 5)).map(|i| format!("w{}", i % 50));
+
+        let streamed = Xtest::profile_entities_from_iter(Profile::new(), words.clone()).unwrap();
+        let profiled = Xtest::profile_entities(Profile::new(), words.collect()).unwrap();
+
+        assert_eq!(streamed.pattern_total, (STREAM_BATCH_SIZE + 5) as u32);
+        assert_eq!(streamed.patterns, profiled.patterns);
+        assert_eq!(streamed.facts.total(), profiled.facts.total());
+    }
+
+    #[test]
+    fn test_profile_entities_from_reader(){
+        let data = "Smith, John\r\nDale, Danny\nRickets, Ronney\n";
+
+        let profile = Xtest::profile_entities_from_reader(Profile::new(), data.as_bytes()).unwrap();
+
+        assert_eq!(profile.pattern_total, 3);
+        assert_eq!(profile.patterns.get("CvcvpSCvccc"), Some(&1));
+    }
+
+    #[test]
+    fn test_profile_entities_from_reader_failure(){
+        struct Failing {}
+        impl Read for Failing {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disconnected"))
+            }
+        }
+
+        match Xtest::profile_entities_from_reader(Profile::new(), Failing {}) {
+            Err(Error::Io(e)) => assert_eq!(e.to_string(), "disconnected"),
+            _ => panic!("expected an I/O error"),
+        }
+    }
+
+    #[test]
+    fn test_worker_pool_failure(){
+        let result = WorkerPool::new(2).map(&[1, 2, 0, 4], |n| 8 / n);
+
+        match result {
+            Err(Error::Worker(msg)) => assert!(msg.contains("chunk 1")),
+            _ => panic!("expected a worker error"),
+        }
+    }
 	
+    #[test]
+    fn test_markov_chain_learn(){
+        let mut chain = MarkovChain::new(2);
+        chain.learn(&PatternDefinition::new().analyze("Hello").1);
+
+        // H-e, e-l, l-l, l-o (order 1) and He-l, el-l, ll-o (order 2)
+        assert_eq!(chain.transitions.len(), 7);
+        assert_eq!(chain.transitions.get("cl").unwrap().get(&'l'), Some(&1));
+        assert_eq!(chain.transitions.get("cel").unwrap().get(&'l'), Some(&1));
+    }
+
+    #[test]
+    fn test_markov_chain_merge_orders(){
+        let mut chain = MarkovChain::new(3);
+        chain.learn(&PatternDefinition::new().analyze("Hello").1);
+        let mut other = MarkovChain::new(1);
+        other.learn(&PatternDefinition::new().analyze("Hello").1);
+
+        chain.merge(&other);
+
+        // only the states of the merged order 1 are kept: H-e, e-l, l-l, l-o
+        assert_eq!(chain.order, 1);
+        assert_eq!(chain.transitions.len(), 4);
+        assert_eq!(chain.transitions.get("cl").unwrap().get(&'l'), Some(&2));
+
+        let mut merged = MarkovChain::new(1);
+        let mut higher = MarkovChain::new(3);
+        higher.learn(&PatternDefinition::new().analyze("Hello").1);
+        merged.merge(&higher);
+
+        assert_eq!(merged.transitions.len(), 4);
+    }
+
+    #[test]
+    fn test_markov_chain_disabled(){
+        let mut chain = MarkovChain::new(0);
+        chain.learn(&PatternDefinition::new().analyze("Hello").1);
+
+        assert!(chain.transitions.is_empty());
+        assert!(chain.next_options('c', &['H','e']).is_none());
+    }
+
+    #[test]
+    fn test_fact_counts_serialize(){
+        let facts = ["OK", "OK"].iter().flat_map(|e| PatternDefinition::new().analyze(e).1).collect::<FactCounts>();
+
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts.total(), 4);
+        assert_eq!(serde_json::to_string(&facts).unwrap(), "[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1,\"count\":2},{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0,\"count\":2}]");
+    }
+
+    #[test]
+    fn test_fact_counts_from_listed_archive(){
+        let serialized = "[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[],[]]";
+        let facts: FactCounts = serde_json::from_str(serialized).unwrap();
+
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts.total(), 2);
+    }
+
+    #[test]
+    fn test_fact_index_pick_fact(){
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let facts = ["Bad", "Bed", "Bed"].iter().flat_map(|e| PatternDefinition::new().analyze(e).1).collect::<FactCounts>();
+        let index = FactIndex::new(&facts, &MarkovChain::new(0));
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert_eq!(index.pick_fact('C', 0, 3, None, &mut rng), Some('B'));
+        assert_eq!(index.pick_fact('c', 2, 3, Some('e'), &mut rng), Some('d'));
+        assert_eq!(index.pick_fact('c', 1, 3, Some('B'), &mut rng), None);
+
+        // the vowels are weighted by their count of occurrence
+        let es = (0..1000).filter(|_| index.pick_fact('v', 1, 3, Some('B'), &mut rng) == Some('e')).count();
+        assert!(es > 550 && es < 780);
+    }
+
+    #[test]
+    fn test_fact_index_pick_transition(){
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let mut chain = MarkovChain::new(1);
+        chain.learn(&PatternDefinition::new().analyze("Hi").1);
+        let index = FactIndex::new(&FactCounts::new(), &chain);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert_eq!(index.pick_transition('v', &['H'], &mut rng), Some('i'));
+        assert_eq!(index.pick_transition('v', &[], &mut rng), None);
+    }
+
 	#[test]
 	fn test_profile_entities() {
 		//async {
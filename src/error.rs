@@ -0,0 +1,128 @@
+//! The `error` module provides the `Error` type that is returned by all the fallible functions of the library.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::{Error, Profile};
+//!
+//! fn main() {
+//!		// a profile that hasn't analyzed any data cannot generate data
+//!		let mut profile = Profile::new();
+//!
+//!		match profile.generate() {
+//!			Err(Error::EmptyProfile(msg)) => println!("{}", msg),
+//!			_ => panic!("expected an empty profile"),
+//!		}
+//! }
+//! ```
+
+use std::error;
+use std::fmt;
+use std::io;
+use csv;
+use serde_json;
+
+#[derive(Debug)]
+/// Represents the errors that can occur while analyzing sample data or generating test data
+pub enum Error {
+	/// A file could not be opened, read or written
+	Io(io::Error),
+	/// A serialized (archived) object or a data sample could not be parsed
+	Parse(String),
+	/// The Profile has not analyzed any data, or has not been prepared (_pre_generate()_), so it cannot generate data
+	EmptyProfile(String),
+	/// The field (e.g.: csv column) is not known to the DataSampleParser
+	UnknownField(String),
+	/// The symbol name is not known to the PatternDefinition
+	UnknownSymbol(String),
+	/// The csv data could not be read or written
+	Csv(csv::Error),
+	/// The configuration file could not be loaded
+	Config(String),
+	/// A value that satisfies the novel-value mode could not be generated within the number of retries
+	NoNovelValue(String),
+	/// A value that satisfies the constraints could not be generated
+	Unsatisfiable(String),
+	/// A worker thread failed while analyzing the data
+	Worker(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Io(e) => write!(f, "I/O error: {}", e),
+			Error::Parse(msg) => write!(f, "Parse error: {}", msg),
+			Error::EmptyProfile(msg) => write!(f, "Empty profile: {}", msg),
+			Error::UnknownField(field) => write!(f, "Unknown field: {}", field),
+			Error::UnknownSymbol(name) => write!(f, "Unknown symbol: {}", name),
+			Error::Csv(e) => write!(f, "CSV error: {}", e),
+			Error::Config(msg) => write!(f, "Configuration error: {}", msg),
+			Error::NoNovelValue(msg) => write!(f, "No novel value: {}", msg),
+			Error::Unsatisfiable(msg) => write!(f, "Unsatisfiable constraints: {}", msg),
+			Error::Worker(msg) => write!(f, "Worker failed: {}", msg),
+		}
+	}
+}
+
+impl error::Error for Error {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		match self {
+			Error::Io(e) => Some(e),
+			Error::Csv(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+impl From<csv::Error> for Error {
+	fn from(e: csv::Error) -> Self {
+		Error::Csv(e)
+	}
+}
+
+impl From<serde_json::Error> for Error {
+	fn from(e: serde_json::Error) -> Self {
+		Error::Parse(e.to_string())
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display_error(){
+		let err = Error::UnknownField("firstname".to_string());
+
+		assert_eq!(err.to_string(), "Unknown field: firstname");
+	}
+
+	#[test]
+	fn from_io_error(){
+		let err: Error = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+
+		match err {
+			Error::Io(e) => assert_eq!(e.kind(), io::ErrorKind::NotFound),
+			_ => panic!("expected an I/O error"),
+		}
+	}
+
+	#[test]
+	fn from_json_error(){
+		let err: Error = serde_json::from_str::<u32>("not a number").unwrap_err().into();
+
+		match err {
+			Error::Parse(_) => {},
+			_ => panic!("expected a parse error"),
+		}
+	}
+}
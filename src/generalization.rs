@@ -0,0 +1,364 @@
+//! The `generalization` module provides the `GeneralizedPatterns`, which compress the patterns of a Profile into run-length forms
+//! (e.g.: `C v{1,2} c{2,5} p S C v c{2,3}`) with a learned distribution of the number of repetitions of each run.
+//! Patterns that have the same sequence of runs (e.g.: `Cvcc` and `Cvvccc`) are generalized together, and each run length is selected independently,
+//! so that new-but-plausible lengths can be generated.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::Profile;
+//!
+//! fn main() {
+//!		let mut profile = Profile::new();
+//!		profile.analyze("Smith, John");
+//!		profile.analyze("Dale, Danny");
+//!		profile.analyze("Rickets, Ronney");
+//!		profile.set_pattern_generalization(true);
+//!		profile.pre_generate();
+//!
+//!		println!("{}", profile.generalize());
+//!		println!("The generated name is {:?}", profile.generate().unwrap());
+//! }
+//! ```
+
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::shared::AliasTable;
+
+/// The runs of a sequence of runs, with the count of occurrence of each number of repetitions, and the count of occurrence of the sequence
+type SkeletonRuns = (Vec<(char, BTreeMap<u32, u32>)>, u32);
+
+/// This function compresses a pattern into its runs of the same symbol with the number of repetitions of each run
+///
+/// # Arguments
+///
+/// * `pattern: &str` - The symbolic pattern.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::generalization::run_lengths;
+///
+/// fn main() {
+///		assert_eq!(run_lengths("Cvccp"), vec![('C', 1), ('v', 1), ('c', 2), ('p', 1)]);
+/// }
+/// ```
+pub fn run_lengths(pattern: &str) -> Vec<(char, u32)> {
+	let mut runs: Vec<(char, u32)> = Vec::new();
+
+	for c in pattern.chars() {
+		match runs.last_mut() {
+			Some(run) if run.0 == c => run.1 += 1,
+			_ => runs.push((c, 1)),
+		}
+	}
+
+	runs
+}
+
+#[derive(Clone, Debug)]
+/// Represents a run of the same symbol in a generalized pattern, with the distribution of its number of repetitions
+pub struct PatternRun {
+	/// the pattern symbol of the run
+	pub symbol: char,
+	/// the distinct numbers of repetitions of the run with their count of occurrence
+	pub lengths: BTreeMap<u32, u32>,
+	/// the alias table of the numbers of repetitions
+	table: AliasTable<u32>,
+}
+
+impl PatternRun {
+	/// This function returns the lowest number of repetitions of the run
+	pub fn min(&self) -> u32 {
+		self.lengths.keys().next().cloned().unwrap_or(0)
+	}
+
+	/// This function returns the highest number of repetitions of the run
+	pub fn max(&self) -> u32 {
+		self.lengths.keys().last().cloned().unwrap_or(0)
+	}
+}
+
+impl fmt::Display for PatternRun {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match (self.min(), self.max()) {
+			(1, 1) => write!(f, "{}", self.symbol),
+			(min, max) if min == max => write!(f, "{}{{{}}}", self.symbol, min),
+			(min, max) => write!(f, "{}{{{},{}}}", self.symbol, min, max),
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+/// Represents the patterns that have the same sequence of runs
+pub struct RunLengthPattern {
+	/// the runs of the pattern
+	pub runs: Vec<PatternRun>,
+	/// the number of analyzed entities that have one of the patterns
+	pub count: u32,
+}
+
+impl RunLengthPattern {
+	/// This function returns a pattern with the number of repetitions of each run selected by their chance of occurring
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	pub fn sample<R: Rng>(&self, rng: &mut R) -> String {
+		let mut pattern = String::new();
+
+		for run in self.runs.iter() {
+			let len = run.table.sample(rng).cloned().unwrap_or(1);
+			pattern.extend((0..len).map(|_| run.symbol));
+		}
+
+		pattern
+	}
+
+	/// This function returns a pattern with a length in the range of lengths, by selecting the number of repetitions of each run
+	/// from the numbers of repetitions that still allow the pattern to have such a length (None if the pattern can't have such a length)
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	/// * `lengths: &RangeInclusive<usize>` - The range of the lengths (number of symbols) of the pattern.</br>
+	pub fn sample_within<R: Rng>(&self, rng: &mut R, lengths: &RangeInclusive<usize>) -> Option<String> {
+		// the lowest and highest number of symbols of the runs after each run
+		let mut rest = vec![(0, 0); self.runs.len() + 1];
+		for (idx, run) in self.runs.iter().enumerate().rev() {
+			rest[idx] = (rest[idx + 1].0 + run.min() as usize, rest[idx + 1].1 + run.max() as usize);
+		}
+
+		let mut pattern = String::new();
+		let mut len = 0;
+
+		for (idx, run) in self.runs.iter().enumerate() {
+			let (rest_min, rest_max) = rest[idx + 1];
+			let table = AliasTable::new(run.lengths.iter()
+				.filter(|(l, _c)| len + **l as usize + rest_min <= *lengths.end() && len + **l as usize + rest_max >= *lengths.start())
+				.map(|(l, c)| (*l, *c as u64))
+				.collect());
+			let run_len = *table.sample(rng)?;
+
+			pattern.extend((0..run_len).map(|_| run.symbol));
+			len += run_len as usize;
+		}
+
+		Some(pattern)
+	}
+
+	/// This function returns the lowest and highest length (number of symbols) of the patterns
+	pub fn lengths(&self) -> RangeInclusive<usize> {
+		self.runs.iter().map(|r| r.min() as usize).sum::<usize>()..=self.runs.iter().map(|r| r.max() as usize).sum::<usize>()
+	}
+}
+
+impl fmt::Display for RunLengthPattern {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.runs.iter().map(|r| r.to_string()).collect::<Vec<String>>().join(" "))
+	}
+}
+
+#[derive(Clone, Debug)]
+/// Represents the generalized (run-length) patterns of a Profile
+pub struct GeneralizedPatterns {
+	/// the generalized patterns, in decreasing order of occurrence
+	pub patterns: Vec<RunLengthPattern>,
+	/// the alias table of the generalized patterns
+	table: AliasTable<usize>,
+}
+
+impl GeneralizedPatterns {
+	/// Constructs a new GeneralizedPatterns from the patterns (with their count of occurrence) of a Profile
+	///
+	/// # Arguments
+	///
+	/// * `patterns: &BTreeMap<String, u32>` - The symbolic patterns with their count of occurrence.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use std::collections::BTreeMap;
+	/// use test_data_generation::generalization::GeneralizedPatterns;
+	///
+	/// fn main() {
+	///		let mut patterns = BTreeMap::new();
+	///		patterns.insert("Cvcc".to_string(), 2);
+	///		patterns.insert("Cvvcccc".to_string(), 1);
+	///		patterns.insert("##".to_string(), 1);
+	///
+	///		let generalized = GeneralizedPatterns::new(&patterns);
+	///
+	///		assert_eq!(generalized.to_string(), "C v{1,2} c{2,4}\n#{2}");
+	/// }
+	/// ```
+	pub fn new(patterns: &BTreeMap<String, u32>) -> GeneralizedPatterns {
+		let mut skeletons: BTreeMap<String, SkeletonRuns> = BTreeMap::new();
+
+		for (pattern, count) in patterns.iter() {
+			let runs = run_lengths(pattern);
+			let skeleton = runs.iter().map(|r| r.0).collect::<String>();
+			let entry = skeletons.entry(skeleton).or_insert_with(|| (runs.iter().map(|r| (r.0, BTreeMap::new())).collect(), 0));
+
+			for (idx, (_symbol, len)) in runs.iter().enumerate() {
+				*(entry.0)[idx].1.entry(*len).or_insert(0) += *count;
+			}
+			entry.1 += *count;
+		}
+
+		let mut generalized = skeletons.into_iter().map(|(_skeleton, (runs, count))| {
+			RunLengthPattern {
+				runs: runs.into_iter().map(|(symbol, lengths)| {
+					PatternRun {
+						symbol,
+						table: AliasTable::new(lengths.iter().map(|(l, c)| (*l, *c as u64)).collect()),
+						lengths,
+					}
+				}).collect(),
+				count,
+			}
+		}).collect::<Vec<RunLengthPattern>>();
+
+		// the sort is stable, so patterns of the same count stay in the order of their runs
+		generalized.sort_by_key(|p| std::cmp::Reverse(p.count));
+
+		GeneralizedPatterns {
+			table: AliasTable::new(generalized.iter().enumerate().map(|(idx, p)| (idx, p.count as u64)).collect()),
+			patterns: generalized,
+		}
+	}
+
+	/// This function returns a pattern by selecting a generalized pattern, and the number of repetitions of its runs, by their chance of occurring
+	/// (None if there are no patterns)
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use std::collections::BTreeMap;
+	/// use rand::thread_rng;
+	/// use test_data_generation::generalization::GeneralizedPatterns;
+	///
+	/// fn main() {
+	///		let mut patterns = BTreeMap::new();
+	///		patterns.insert("Cvc".to_string(), 1);
+	///		patterns.insert("Cvvccc".to_string(), 1);
+	///
+	///		let pattern = GeneralizedPatterns::new(&patterns).sample(&mut thread_rng()).unwrap();
+	///
+	///		assert!(["Cvc", "Cvccc", "Cvvc", "Cvvccc"].contains(&pattern.as_str()));
+	/// }
+	/// ```
+	pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<String> {
+		let idx = *self.table.sample(rng)?;
+		Some(self.patterns[idx].sample(rng))
+	}
+
+	/// This function returns a pattern, like _sample()_, but only of the generalized patterns whose symbols satisfy the filter,
+	/// and with the numbers of repetitions of the runs limited, so that the length of the pattern is in the range of lengths
+	/// (None if none of the patterns can satisfy the filter and the range of lengths)
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	/// * `lengths: &RangeInclusive<usize>` - The range of the lengths (number of symbols) of the pattern.</br>
+	/// * `filter: &dyn Fn(&char) -> bool` - The filter of the symbols of the pattern.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use std::collections::BTreeMap;
+	/// use rand::thread_rng;
+	/// use test_data_generation::generalization::GeneralizedPatterns;
+	///
+	/// fn main() {
+	///		let mut patterns = BTreeMap::new();
+	///		patterns.insert("Cvc".to_string(), 1);
+	///		patterns.insert("Cvvccc".to_string(), 1);
+	///		patterns.insert("###".to_string(), 1);
+	///
+	///		let pattern = GeneralizedPatterns::new(&patterns).sample_within(&mut thread_rng(), &(0..=4), &|s: &char| *s != '#').unwrap();
+	///
+	///		assert!(["Cvc", "Cvvc"].contains(&pattern.as_str()));
+	/// }
+	/// ```
+	pub fn sample_within<R: Rng>(&self, rng: &mut R, lengths: &RangeInclusive<usize>, filter: &dyn Fn(&char) -> bool) -> Option<String> {
+		let table = AliasTable::new(self.patterns.iter().enumerate()
+			.filter(|(_idx, p)| {
+				let bounds = p.lengths();
+				p.runs.iter().all(|r| filter(&r.symbol)) && *bounds.start() <= *lengths.end() && *bounds.end() >= *lengths.start()
+			})
+			.map(|(idx, p)| (idx, p.count as u64))
+			.collect());
+		let idx = *table.sample(rng)?;
+
+		self.patterns[idx].sample_within(rng, lengths)
+	}
+}
+
+impl fmt::Display for GeneralizedPatterns {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.patterns.iter().map(|p| p.to_string()).collect::<Vec<String>>().join("\n"))
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn run_lengths_of_patterns(){
+		assert_eq!(run_lengths(""), Vec::new());
+		assert_eq!(run_lengths("CvccvccpSCvccvv"), vec![('C', 1), ('v', 1), ('c', 2), ('v', 1), ('c', 2), ('p', 1), ('S', 1), ('C', 1), ('v', 1), ('c', 2), ('v', 2)]);
+	}
+
+	#[test]
+	fn generalize_patterns(){
+		let mut patterns = BTreeMap::new();
+		patterns.insert("Cvccp".to_string(), 1);
+		patterns.insert("Cvvcccccp".to_string(), 3);
+		patterns.insert("###".to_string(), 2);
+
+		let generalized = GeneralizedPatterns::new(&patterns);
+
+		assert_eq!(generalized.patterns.len(), 2);
+		assert_eq!(generalized.patterns[0].count, 4);
+		assert_eq!(generalized.patterns[0].runs[2].lengths.get(&5), Some(&3));
+		assert_eq!(generalized.to_string(), "C v{1,2} c{2,5} p\n#{3}");
+	}
+
+	#[test]
+	fn sample_new_lengths(){
+		let mut patterns = BTreeMap::new();
+		patterns.insert("Cvc".to_string(), 1);
+		patterns.insert("Cvvccc".to_string(), 1);
+
+		let generalized = GeneralizedPatterns::new(&patterns);
+		let mut rng = StdRng::seed_from_u64(3);
+		let sampled = (0..100).map(|_| generalized.sample(&mut rng).unwrap()).collect::<std::collections::BTreeSet<String>>();
+
+		assert_eq!(sampled.into_iter().collect::<Vec<String>>(), vec!["Cvc", "Cvccc", "Cvvc", "Cvvccc"]);
+		assert_eq!(GeneralizedPatterns::new(&BTreeMap::new()).sample(&mut rng), None);
+	}
+}
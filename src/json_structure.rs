@@ -0,0 +1,773 @@
+//! The `json_structure` module provides the `JsonStructure`, which flattens JSON documents into field paths and remembers their structure.
+//!
+//! The fields of nested objects are named by their path (e.g.: `customer.address.city`), and the items of arrays by the path of the array
+//! followed by `[]` (e.g.: `items[].sku`). For each path, the structure counts the kinds of the values (e.g.: string, number or null),
+//! the keys of the objects (so that optional keys are known) and the lengths of the arrays, so that documents of the same structure can be generated.
+//! The `JsonFormat` defines how the generated documents are written, and the `JsonValue` keeps the keys of the objects in the order of the documents.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//! extern crate serde_json;
+//!
+//! use test_data_generation::json_structure::{JsonStructure, JsonValue};
+//!
+//! fn main() {
+//!		let document: JsonValue = serde_json::from_str(r#"{"id": 7, "items": [{"sku": "A-1"}, {"sku": "B-2"}]}"#).unwrap();
+//!		let mut structure = JsonStructure::new();
+//!		let mut values = Vec::new();
+//!
+//!		structure.analyze(&document, &mut values);
+//!
+//!		assert_eq!(values, vec![("id".to_string(), "7".to_string()), ("items[].sku".to_string(), "A-1".to_string()), ("items[].sku".to_string(), "B-2".to_string())]);
+//!		assert_eq!(structure.nodes["items"].lengths.get(&2), Some(&1));
+//! }
+//! ```
+
+use rand::Rng;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde_json::{Map, Number, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::Error;
+
+/// This function returns a key that is selected randomly by the weight of its count
+fn weighted<K: Copy, R: Rng>(counts: &BTreeMap<K, u32>, rng: &mut R) -> Option<K> {
+	let total: u32 = counts.values().sum();
+	if total == 0 {
+		return None;
+	}
+
+	let mut pick = rng.gen_range(0, total);
+	for (key, count) in counts.iter() {
+		if pick < *count {
+			return Some(*key);
+		}
+		pick -= count;
+	}
+
+	None
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents how the generated JSON documents are written. By default, the documents are flat objects keyed by the field names with string values.
+pub struct JsonFormat {
+	/// If true, the nested structure of the analyzed JSON documents is rebuilt (the documents are flat if no JSON documents have been analyzed)
+	#[serde(default)]
+	pub nested: bool,
+	/// If true, numbers, booleans and nulls are written as typed values instead of strings
+	#[serde(default)]
+	pub typed: bool,
+}
+
+impl JsonFormat {
+	/// Constructs a new JsonFormat with the default settings
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::JsonFormat;
+	///
+	/// fn main() {
+	///		let format = JsonFormat::new().with_nested(true).with_typed_values(true);
+	///
+	///		assert!(format.nested && format.typed);
+	/// }
+	/// ```
+	pub fn new() -> JsonFormat {
+		JsonFormat {
+			nested: false,
+			typed: false,
+		}
+	}
+
+	/// This function sets if the nested structure of the analyzed JSON documents is rebuilt
+	///
+	/// # Arguments
+	///
+	/// * `nested: bool` - If true, the nested objects and arrays are rebuilt.</br>
+	pub fn with_nested(mut self, nested: bool) -> Self {
+		self.nested = nested;
+		self
+	}
+
+	/// This function sets if numbers, booleans and nulls are written as typed values
+	///
+	/// # Arguments
+	///
+	/// * `typed: bool` - If true, the values are typed, otherwise all the values are strings.</br>
+	pub fn with_typed_values(mut self, typed: bool) -> Self {
+		self.typed = typed;
+		self
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Represents a JSON value, in which the keys of the objects keep the order of the document (unlike a serde_json::Value, whose keys are sorted)
+pub enum JsonValue {
+	/// The null value
+	Null,
+	/// A boolean value
+	Bool(bool),
+	/// A number
+	Number(Number),
+	/// A string
+	String(String),
+	/// An array
+	Array(Vec<JsonValue>),
+	/// An object, with its keys and values in the order of the document
+	Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+	/// This function returns the value of a key of an object (None if the value isn't an object or doesn't have the key)
+	///
+	/// # Arguments
+	///
+	/// * `key: &str` - The key.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate serde_json;
+	///
+	/// use test_data_generation::json_structure::JsonValue;
+	///
+	/// fn main() {
+	///		let document: JsonValue = serde_json::from_str(r#"{"name": "Aaron", "age": 42}"#).unwrap();
+	///
+	///		assert_eq!(document.get("name"), Some(&JsonValue::String("Aaron".to_string())));
+	///		assert_eq!(document.to_string(), r#"{"name":"Aaron","age":42}"#);
+	/// }
+	/// ```
+	pub fn get(&self, key: &str) -> Option<&JsonValue> {
+		match self {
+			JsonValue::Object(entries) => entries.iter().find(|(k, _v)| k == key).map(|(_k, v)| v),
+			_ => None,
+		}
+	}
+
+	/// This function returns true if the value is an object
+	pub fn is_object(&self) -> bool {
+		matches!(self, JsonValue::Object(_))
+	}
+}
+
+impl From<JsonValue> for Value {
+	fn from(value: JsonValue) -> Self {
+		match value {
+			JsonValue::Null => Value::Null,
+			JsonValue::Bool(b) => Value::Bool(b),
+			JsonValue::Number(n) => Value::Number(n),
+			JsonValue::String(s) => Value::String(s),
+			JsonValue::Array(items) => Value::Array(items.into_iter().map(Value::from).collect()),
+			JsonValue::Object(entries) => Value::Object(entries.into_iter().map(|(k, v)| (k, Value::from(v))).collect::<Map<String, Value>>()),
+		}
+	}
+}
+
+impl fmt::Display for JsonValue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", serde_json::to_string(self).map_err(|_e| fmt::Error)?)
+	}
+}
+
+impl serde::Serialize for JsonValue {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self {
+			JsonValue::Null => serializer.serialize_unit(),
+			JsonValue::Bool(b) => serializer.serialize_bool(*b),
+			JsonValue::Number(n) => n.serialize(serializer),
+			JsonValue::String(s) => serializer.serialize_str(s),
+			JsonValue::Array(items) => items.serialize(serializer),
+			JsonValue::Object(entries) => {
+				// the keys are written in their order, instead of the sorted order of a serde_json::Map
+				let mut map = serializer.serialize_map(Some(entries.len()))?;
+				for (key, value) in entries.iter() {
+					map.serialize_entry(key, value)?;
+				}
+				map.end()
+			},
+		}
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for JsonValue {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_any(JsonValueVisitor)
+	}
+}
+
+/// Represents the Visitor that deserializes a JsonValue, which records the keys of the objects in the order of the document
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+	type Value = JsonValue;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a JSON value")
+	}
+
+	fn visit_unit<E>(self) -> Result<JsonValue, E> {
+		Ok(JsonValue::Null)
+	}
+
+	fn visit_none<E>(self) -> Result<JsonValue, E> {
+		Ok(JsonValue::Null)
+	}
+
+	fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<JsonValue, D::Error> {
+		serde::Deserialize::deserialize(deserializer)
+	}
+
+	fn visit_bool<E>(self, b: bool) -> Result<JsonValue, E> {
+		Ok(JsonValue::Bool(b))
+	}
+
+	fn visit_i64<E>(self, n: i64) -> Result<JsonValue, E> {
+		Ok(JsonValue::Number(n.into()))
+	}
+
+	fn visit_u64<E>(self, n: u64) -> Result<JsonValue, E> {
+		Ok(JsonValue::Number(n.into()))
+	}
+
+	fn visit_f64<E>(self, n: f64) -> Result<JsonValue, E> {
+		Ok(Number::from_f64(n).map_or(JsonValue::Null, JsonValue::Number))
+	}
+
+	fn visit_str<E>(self, s: &str) -> Result<JsonValue, E> {
+		Ok(JsonValue::String(s.to_string()))
+	}
+
+	fn visit_string<E>(self, s: String) -> Result<JsonValue, E> {
+		Ok(JsonValue::String(s))
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+		let mut items = Vec::new();
+		while let Some(item) = seq.next_element()? {
+			items.push(item);
+		}
+
+		Ok(JsonValue::Array(items))
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+		let mut entries: Vec<(String, JsonValue)> = Vec::new();
+		while let Some((key, value)) = map.next_entry::<String, JsonValue>()? {
+			// a duplicate key keeps its first position and its last value, like a serde_json::Map
+			match entries.iter_mut().find(|(k, _v)| *k == key) {
+				Some(entry) => entry.1 = value,
+				None => entries.push((key, value)),
+			}
+		}
+
+		Ok(JsonValue::Object(entries))
+	}
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents the kinds of JSON values
+pub enum JsonKind {
+	/// The null value
+	Null,
+	/// A boolean value (true or false)
+	Boolean,
+	/// A number
+	Number,
+	/// A string
+	String,
+	/// An array
+	Array,
+	/// An object
+	Object,
+}
+
+impl JsonKind {
+	/// This function returns the kind of a JSON value
+	///
+	/// # Arguments
+	///
+	/// * `value: &JsonValue` - The JSON value.</br>
+	pub fn of(value: &JsonValue) -> JsonKind {
+		match value {
+			JsonValue::Null => JsonKind::Null,
+			JsonValue::Bool(_) => JsonKind::Boolean,
+			JsonValue::Number(_) => JsonKind::Number,
+			JsonValue::String(_) => JsonKind::String,
+			JsonValue::Array(_) => JsonKind::Array,
+			JsonValue::Object(_) => JsonKind::Object,
+		}
+	}
+
+	/// This function returns the typed JSON value of the kind for a generated textual value.
+	/// A number or boolean that can't be parsed is a string.
+	///
+	/// # Arguments
+	///
+	/// * `text: String` - The generated textual value.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::json_structure::JsonKind;
+	///
+	/// fn main() {
+	///		assert_eq!(JsonKind::Number.value_of("19.99".to_string()).to_string(), "19.99");
+	///		assert_eq!(JsonKind::Number.value_of("007".to_string()).to_string(), "\"007\"");
+	///		assert_eq!(JsonKind::Boolean.value_of("true".to_string()).to_string(), "true");
+	/// }
+	/// ```
+	pub fn value_of(self, text: String) -> JsonValue {
+		match self {
+			JsonKind::Null => JsonValue::Null,
+			JsonKind::Boolean => match text.as_str() {
+				"true" => JsonValue::Bool(true),
+				"false" => JsonValue::Bool(false),
+				_ => JsonValue::String(text),
+			},
+			JsonKind::Number => match serde_json::from_str::<JsonValue>(&text) {
+				Ok(JsonValue::Number(n)) => JsonValue::Number(n),
+				_ => JsonValue::String(text),
+			},
+			_ => JsonValue::String(text),
+		}
+	}
+
+	/// This function returns the typed JSON value of a generated textual value whose kind isn't known (e.g.: a value of a csv column).
+	/// An empty value is null, a value of a numeric field that can be parsed is a number, `true` and `false` are booleans, and the other values are strings.
+	///
+	/// # Arguments
+	///
+	/// * `text: String` - The generated textual value.</br>
+	/// * `numeric: bool` - If the values of the field are numbers (see _Profile::is_numeric()_).</br>
+	pub fn infer(text: String, numeric: bool) -> JsonValue {
+		if text.is_empty() {
+			JsonValue::Null
+		} else if numeric {
+			JsonKind::Number.value_of(text)
+		} else {
+			JsonKind::Boolean.value_of(text)
+		}
+	}
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents the values of a path of the analyzed JSON documents
+pub struct JsonNode {
+	/// The number of values of the path
+	pub count: u32,
+	/// The number of values of each kind
+	pub kinds: BTreeMap<JsonKind, u32>,
+	/// The keys of the objects of the path, in the order they have first been analyzed
+	#[serde(default)]
+	pub keys: Vec<String>,
+	/// The lengths of the arrays of the path with their count of occurrence
+	#[serde(default)]
+	pub lengths: BTreeMap<u32, u32>,
+	/// The number of boolean values of the path that are true
+	#[serde(default)]
+	pub trues: u32,
+}
+
+impl JsonNode {
+	/// This function returns the number of values of the kind
+	///
+	/// # Arguments
+	///
+	/// * `kind: JsonKind` - The kind of the values.</br>
+	pub fn count_of(&self, kind: JsonKind) -> u32 {
+		self.kinds.get(&kind).cloned().unwrap_or(0)
+	}
+
+	/// This function returns a kind of the scalar (null, boolean, number or string) values of the path,
+	/// which is selected randomly by the weight of its count (None if the path has no scalar values)
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator.</br>
+	pub fn scalar_kind<R: Rng>(&self, rng: &mut R) -> Option<JsonKind> {
+		let scalars = self.kinds.iter()
+			.filter(|(kind, _count)| **kind != JsonKind::Array && **kind != JsonKind::Object)
+			.map(|(kind, count)| (*kind, *count))
+			.collect::<BTreeMap<JsonKind, u32>>();
+
+		weighted(&scalars, rng)
+	}
+
+	/// This function returns a boolean that is true as often as the boolean values of the path
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator.</br>
+	pub fn boolean<R: Rng>(&self, rng: &mut R) -> bool {
+		match self.count_of(JsonKind::Boolean) {
+			0 => false,
+			booleans => rng.gen_range(0, booleans) < self.trues,
+		}
+	}
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+/// Represents the structure of the analyzed JSON documents
+pub struct JsonStructure {
+	/// The number of analyzed documents
+	pub documents: u32,
+	/// The values of each path, (the path of the documents is the empty path)
+	pub nodes: BTreeMap<String, JsonNode>,
+}
+
+impl JsonStructure {
+	/// Constructs a new JsonStructure
+	pub fn new() -> JsonStructure {
+		JsonStructure {
+			documents: 0,
+			nodes: BTreeMap::new(),
+		}
+	}
+
+	/// This function returns the path of the value of a key of the objects of a path, (e.g.: `customer.address` for the key `address` of `customer`)
+	///
+	/// # Arguments
+	///
+	/// * `parent: &str` - The path of the objects.</br>
+	/// * `key: &str` - The key.</br>
+	pub fn key_path(parent: &str, key: &str) -> String {
+		if parent.is_empty() {
+			key.to_string()
+		} else {
+			format!("{}.{}", parent, key)
+		}
+	}
+
+	/// This function returns the path of the items of the arrays of a path, (e.g.: `items[]` for `items`)
+	///
+	/// # Arguments
+	///
+	/// * `parent: &str` - The path of the arrays.</br>
+	pub fn items_path(parent: &str) -> String {
+		format!("{}[]", parent)
+	}
+
+	/// This function analyzes the structure of a JSON document, and adds the paths and textual values of its strings, numbers and booleans
+	/// to the values, in the order of the document. Null values are only counted.
+	///
+	/// # Arguments
+	///
+	/// * `document: &JsonValue` - The JSON document.</br>
+	/// * `values: &mut Vec<(String, String)>` - The paths and textual values of the document.</br>
+	pub fn analyze(&mut self, document: &JsonValue, values: &mut Vec<(String, String)>) {
+		self.documents += 1;
+		self.analyze_value("", document, values);
+	}
+
+	/// This function analyzes a value of a path
+	fn analyze_value(&mut self, path: &str, value: &JsonValue, values: &mut Vec<(String, String)>) {
+		let node = self.nodes.entry(path.to_string()).or_default();
+		node.count += 1;
+		*node.kinds.entry(JsonKind::of(value)).or_insert(0) += 1;
+
+		match value {
+			JsonValue::Null => {},
+			JsonValue::Bool(b) => {
+				if *b {
+					node.trues += 1;
+				}
+				values.push((path.to_string(), b.to_string()));
+			},
+			JsonValue::Number(n) => values.push((path.to_string(), n.to_string())),
+			JsonValue::String(s) => values.push((path.to_string(), s.clone())),
+			JsonValue::Array(items) => {
+				*node.lengths.entry(items.len() as u32).or_insert(0) += 1;
+
+				let items_path = JsonStructure::items_path(path);
+				for item in items.iter() {
+					self.analyze_value(&items_path, item, values);
+				}
+			},
+			JsonValue::Object(entries) => {
+				for (key, _child) in entries.iter() {
+					if !node.keys.contains(key) {
+						node.keys.push(key.clone());
+					}
+				}
+
+				for (key, child) in entries.iter() {
+					self.analyze_value(&JsonStructure::key_path(path, key), child, values);
+				}
+			},
+		}
+	}
+
+	/// This function returns the fraction of the objects of a path that have the key, (i.e.: a key that is optional has a presence below 1.0)
+	///
+	/// # Arguments
+	///
+	/// * `parent: &str` - The path of the objects.</br>
+	/// * `key: &str` - The key.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate serde_json;
+	///
+	/// use test_data_generation::json_structure::JsonStructure;
+	///
+	/// fn main() {
+	///		let mut structure = JsonStructure::new();
+	///		for document in [r#"{"id": 1, "note": "new"}"#, r#"{"id": 2}"#].iter() {
+	///			structure.analyze(&serde_json::from_str(document).unwrap(), &mut Vec::new());
+	///		}
+	///
+	///		assert_eq!(structure.presence("", "id"), 1.0);
+	///		assert_eq!(structure.presence("", "note"), 0.5);
+	/// }
+	/// ```
+	pub fn presence(&self, parent: &str, key: &str) -> f64 {
+		let objects = self.nodes.get(parent).map(|n| n.count_of(JsonKind::Object)).unwrap_or(0);
+		let count = self.nodes.get(&JsonStructure::key_path(parent, key)).map(|n| n.count).unwrap_or(0);
+
+		if objects == 0 {
+			return 0.0;
+		}
+
+		count as f64 / objects as f64
+	}
+
+	/// This function generates a JSON document of the analyzed structure. The kind of each value, the keys of each object (by their presence)
+	/// and the length of each array are selected randomly by the weight of their counts, the booleans are true as often as the analyzed booleans,
+	/// and the text of the other scalar values is generated for their path.
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator.</br>
+	/// * `typed: bool` - If true, the numbers, booleans and nulls are typed values, otherwise all the values are strings (and nulls are empty strings).</br>
+	/// * `text: &mut F` - The function that generates the text of a value of a path (None if the path has no generated values).</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate serde_json;
+	/// extern crate rand;
+	///
+	/// use test_data_generation::json_structure::JsonStructure;
+	///
+	/// fn main() {
+	///		let mut structure = JsonStructure::new();
+	///		structure.analyze(&serde_json::from_str(r#"{"id": 7, "tags": ["a", "b"]}"#).unwrap(), &mut Vec::new());
+	///
+	///		let document = structure.generate(&mut rand::thread_rng(), true, &mut |path: &str| Ok(Some(format!("{}", path.len())))).unwrap();
+	///
+	///		assert_eq!(document.to_string(), r#"{"id":2,"tags":["6","6"]}"#);
+	/// }
+	/// ```
+	pub fn generate<R, F>(&self, rng: &mut R, typed: bool, text: &mut F) -> Result<JsonValue, Error>
+	where
+		R: Rng,
+		F: FnMut(&str) -> Result<Option<String>, Error>,
+	{
+		self.generate_value("", rng, typed, text)
+	}
+
+	/// This function generates a value of a path
+	fn generate_value<R, F>(&self, path: &str, rng: &mut R, typed: bool, text: &mut F) -> Result<JsonValue, Error>
+	where
+		R: Rng,
+		F: FnMut(&str) -> Result<Option<String>, Error>,
+	{
+		let node = match self.nodes.get(path) {
+			Some(node) => node,
+			None => return Ok(JsonStructure::null(typed)),
+		};
+
+		match weighted(&node.kinds, rng) {
+			Some(JsonKind::Object) => {
+				let mut object = Vec::new();
+				for key in node.keys.iter() {
+					if rng.gen::<f64>() < self.presence(path, key) {
+						let value = self.generate_value(&JsonStructure::key_path(path, key), rng, typed, text)?;
+						object.push((key.clone(), value));
+					}
+				}
+				Ok(JsonValue::Object(object))
+			},
+			Some(JsonKind::Array) => {
+				let items_path = JsonStructure::items_path(path);
+				let length = weighted(&node.lengths, rng).unwrap_or(0);
+				let mut items = Vec::new();
+				for _i in 0..length {
+					items.push(self.generate_value(&items_path, rng, typed, text)?);
+				}
+				Ok(JsonValue::Array(items))
+			},
+			Some(JsonKind::Boolean) => Ok(JsonStructure::boolean(node.boolean(rng), typed)),
+			Some(kind) if kind != JsonKind::Null => {
+				match (text(path)?, typed) {
+					(Some(value), true) => Ok(kind.value_of(value)),
+					(Some(value), false) => Ok(JsonValue::String(value)),
+					(None, _) => Ok(JsonStructure::null(typed)),
+				}
+			},
+			_ => Ok(JsonStructure::null(typed)),
+		}
+	}
+
+	/// This function returns a boolean value, which is a string if the values aren't typed
+	pub(crate) fn boolean(b: bool, typed: bool) -> JsonValue {
+		if typed {
+			JsonValue::Bool(b)
+		} else {
+			JsonValue::String(b.to_string())
+		}
+	}
+
+	/// This function returns a null value, which is an empty string if the values aren't typed
+	fn null(typed: bool) -> JsonValue {
+		if typed {
+			JsonValue::Null
+		} else {
+			JsonValue::String(String::new())
+		}
+	}
+
+	/// This function merges (unions) the structure of other JSON documents into this JsonStructure
+	///
+	/// # Arguments
+	///
+	/// * `other: &JsonStructure` - The JsonStructure to merge into this JsonStructure.</br>
+	pub fn merge(&mut self, other: &JsonStructure) {
+		self.documents += other.documents;
+
+		for (path, other_node) in other.nodes.iter() {
+			let node = self.nodes.entry(path.clone()).or_default();
+			node.count += other_node.count;
+
+			for (kind, count) in other_node.kinds.iter() {
+				*node.kinds.entry(*kind).or_insert(0) += *count;
+			}
+			for key in other_node.keys.iter() {
+				if !node.keys.contains(key) {
+					node.keys.push(key.clone());
+				}
+			}
+			for (len, count) in other_node.lengths.iter() {
+				*node.lengths.entry(*len).or_insert(0) += *count;
+			}
+			node.trues += other_node.trues;
+		}
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn analyze(documents: &[&str]) -> (JsonStructure, Vec<(String, String)>) {
+		let mut structure = JsonStructure::new();
+		let mut values = Vec::new();
+
+		for document in documents.iter() {
+			structure.analyze(&serde_json::from_str(document).unwrap(), &mut values);
+		}
+
+		(structure, values)
+	}
+
+	#[test]
+	fn flatten_nested_paths(){
+		let (structure, values) = analyze(&[r#"{"customer": {"name": "Smith", "address": {"city": "Paris"}}, "tags": ["a", "b"], "grid": [[1, 2]], "paid": true, "note": null}"#]);
+		let paths = values.iter().map(|(p, _v)| p.as_str()).collect::<Vec<&str>>();
+
+		assert_eq!(paths, vec!["customer.name", "customer.address.city", "tags[]", "tags[]", "grid[][]", "grid[][]", "paid"]);
+		assert_eq!(values[6].1, "true");
+		assert_eq!(structure.nodes["note"].count_of(JsonKind::Null), 1);
+		assert_eq!(structure.nodes["grid[]"].lengths.get(&2), Some(&1));
+		assert_eq!(structure.nodes["paid"].trues, 1);
+		assert_eq!(structure.nodes[""].keys, vec!["customer", "tags", "grid", "paid", "note"]);
+	}
+
+	#[test]
+	fn optional_keys_and_lengths(){
+		let (structure, _values) = analyze(&[
+			r#"{"items": [{"sku": "A", "qty": 1}, {"sku": "B"}]}"#,
+			r#"{"items": []}"#,
+			r#"{"items": [{"sku": "C"}]}"#,
+		]);
+
+		assert_eq!(structure.documents, 3);
+		assert_eq!(structure.presence("items[]", "sku"), 1.0);
+		assert_eq!(structure.presence("items[]", "qty"), 1.0 / 3.0);
+		assert_eq!(structure.nodes["items"].lengths.values().cloned().collect::<Vec<u32>>(), vec![1, 1, 1]);
+	}
+
+	#[test]
+	fn merge_structures(){
+		let (mut structure, _values) = analyze(&[r#"{"id": 1}"#]);
+		let (other, _values) = analyze(&[r#"{"id": null, "note": "x"}"#]);
+
+		structure.merge(&other);
+
+		assert_eq!(structure.documents, 2);
+		assert_eq!(structure.nodes["id"].kinds.len(), 2);
+		assert_eq!(structure.nodes[""].keys, vec!["id", "note"]);
+		assert_eq!(structure.presence("", "note"), 0.5);
+	}
+
+	#[test]
+	fn generate_structure(){
+		let (structure, _values) = analyze(&[
+			r#"{"id": 1, "items": [{"sku": "A", "qty": 2}], "paid": true, "note": null}"#,
+			r#"{"id": 2, "items": [{"sku": "B", "qty": 1}, {"sku": "C", "qty": 3}], "paid": false, "note": null}"#,
+		]);
+		let mut rng = rand::thread_rng();
+		let mut text = |path: &str| Ok(match path {
+			"id" | "items[].qty" => Some("5".to_string()),
+			_ => Some("X".to_string()),
+		});
+
+		for _i in 0..10 {
+			let document = Value::from(structure.generate(&mut rng, true, &mut text).unwrap());
+			assert_eq!(document["id"], 5);
+			assert!(document["paid"].is_boolean());
+			assert_eq!(document["note"], Value::Null);
+			assert!(!document["items"].as_array().unwrap().is_empty());
+			assert_eq!(document["items"][0]["sku"], "X");
+
+			let document = Value::from(structure.generate(&mut rng, false, &mut text).unwrap());
+			assert_eq!(document["id"], "5");
+			assert!(document["paid"] == "true" || document["paid"] == "false");
+			assert_eq!(document["note"], "");
+		}
+	}
+
+	#[test]
+	fn ordered_values(){
+		let data = r#"{"zip": "75001", "name": {"last": "Smith", "first": "John"}, "age": 7.5, "tags": [true, null], "zip": "75002"}"#;
+		let document: JsonValue = serde_json::from_str(data).unwrap();
+
+		assert_eq!(document.to_string(), r#"{"zip":"75002","name":{"last":"Smith","first":"John"},"age":7.5,"tags":[true,null]}"#);
+		assert_eq!(Value::from(document), serde_json::from_str::<Value>(data).unwrap());
+		assert!(serde_json::from_str::<JsonValue>("{\"a\": }").is_err());
+	}
+
+	#[test]
+	fn infer_values(){
+		assert_eq!(JsonKind::infer(String::new(), true), JsonValue::Null);
+		assert_eq!(Value::from(JsonKind::infer("42".to_string(), true)), 42);
+		assert_eq!(Value::from(JsonKind::infer("42".to_string(), false)), "42");
+		assert_eq!(Value::from(JsonKind::infer("false".to_string(), false)), false);
+		assert_eq!(Value::from(JsonKind::Number.value_of("1,234".to_string())), "1,234");
+	}
+}
@@ -32,7 +32,7 @@
 //!     data_profile.pre_generate();
 //!
 //!     // generate some data
-//!    	println!("The generated name is {:?}", data_profile.generate());
+//!    	println!("The generated name is {:?}", data_profile.generate().unwrap());
 //! }
 //! ```
 //!
@@ -58,10 +58,10 @@
 //!		assert_eq!(old_profile.save("./tests/samples/sample-00-profile").unwrap(), true);
 //!
 //!		// create a new profile from the archive json file
-//!		let mut new_profile = Profile::from_file("./tests/samples/sample-00-profile");
+//!		let mut new_profile = Profile::from_file("./tests/samples/sample-00-profile").unwrap();
 //!
 //!		// generate some data. NOTE that the pre-generate() was already called prior to saving
-//!     println!("The generated name is {:?}", new_profile.generate());
+//!     println!("The generated name is {:?}", new_profile.generate().unwrap());
 //! }
 //! ```
 //!
@@ -78,7 +78,7 @@
 //!     let mut dsp = DataSampleParser::new();
 //!     dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
 //!
-//!     println!("My new name is {} {}", dsp.generate_record()[0], dsp.generate_record()[1]);
+//!     println!("My new name is {} {}", dsp.generate_record().unwrap()[0], dsp.generate_record().unwrap()[1]);
 //!     // My new name is Abbon Aady
 //! }
 //! ```
@@ -104,9 +104,9 @@
 //! use test_data_generation::data_sample_parser::DataSampleParser;
 //!
 //! fn main() {
-//!     let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-01-dsp"));
+//!     let mut dsp = DataSampleParser::from_file(&String::from("./tests/samples/sample-01-dsp")).unwrap();
 //!
-//! 	println!("Sample data is {:?}", dsp.generate_record()[0]);
+//! 	println!("Sample data is {:?}", dsp.generate_record().unwrap()[0]);
 //! }
 //! ```
 //!
@@ -124,6 +124,21 @@
 //!    	dsp.generate_csv(100, &String::from("./tests/samples/generated-01.csv")).unwrap();
 //! }
 //! ```
+//!
+//! ### Errors
+//!
+//! All the functions that can fail return a `Result` with the crate's `Error` type, so that loading a bad archive file
+//! or generating data from an empty profile doesn't abort the process.
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::Profile;
+//!
+//! fn main() {
+//!     assert!(Profile::from_file("./tests/samples/bad-path").is_err());
+//! }
+//! ```
 #![crate_type= "lib"]
 #![crate_name = "test_data_generation"]
 
@@ -132,6 +147,8 @@ extern crate log;
 
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
+extern crate lazy_static;
 extern crate serde;
 extern crate serde_json;
 extern crate serde_yaml;
@@ -142,17 +159,204 @@ extern crate crossbeam;
 extern crate csv;
 extern crate levenshtein;
 
-use crate::engine::{Fact, PatternDefinition};
-use std::collections::BTreeMap;
+use crate::engine::{Fact, FactCounts, FactIndex, MarkovChain, PatternDefinition, SymbolClass};
+use crate::generalization::GeneralizedPatterns;
+use crate::numeric::NumericModel;
+use crate::semantic::{SemanticModel, SemanticType, SemanticValues};
+use crate::shared::{hash_str, AliasTable};
+pub use crate::constraints::Constraints;
+pub use crate::csv_dialect::CsvDialect;
+pub use crate::error::Error;
+pub use crate::json_structure::{JsonFormat, JsonValue};
+pub use crate::sql::{SqlFormat, SqlStatement};
+pub use crate::summary::ProfileSummary;
+pub use crate::tokens::TokenModel;
+pub use crate::validator::RegexForm;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::ops::AddAssign;
 use std::fs::File;
-use std::io;
 use std::io::Write;
 use std::io::prelude::*;
 
 type PatternMap = BTreeMap<String, u32>;
 type SizeMap = BTreeMap<u32, u32>;
 type SizeRankMap  = BTreeMap<u32, f64>;
+/// The constraints of a generated value, with the patterns and the filter of the chars that satisfy them
+type Constrained<'a> = (&'a Constraints, &'a AliasTable<String>, &'a dyn Fn(&char) -> bool);
+
+#[derive(Clone, Debug)]
+/// Represents the settings of the novel-value mode, which ensures that the generated data never equals any of the sample data that was analyzed
+pub struct NovelMode {
+	/// If true, each generated value is also unique within the batch of generated data
+	pub unique: bool,
+	/// The number of times a value that is not novel (or not unique) is regenerated before an error is returned
+	pub retries: u32,
+}
+
+impl NovelMode {
+	/// Constructs a new NovelMode
+	///
+	/// # Arguments
+	///
+	/// * `unique: bool` - If true, each generated value is also unique within the batch of generated data.</br>
+	/// * `retries: u32` - The number of times a value is regenerated before an error is returned.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::NovelMode;
+	///
+	/// fn main() {
+	/// 	let mode = NovelMode::new(true, 100);
+	///
+	///		assert!(mode.unique);
+	/// }
+	/// ```
+	pub fn new(unique: bool, retries: u32) -> NovelMode {
+		NovelMode {
+			unique,
+			retries,
+		}
+	}
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(from = "BTreeSet<u64>", into = "BTreeSet<u64>")]
+/// Represents the hashes of the analyzed entities, which are used by the novel-value mode to recognize the sample data.
+/// Since short sample data (e.g.: names or zip codes) could be recovered from their hashes by trying all the possible values,
+/// the hashes are only archived with the Profile if they are explicitly archived (see _Profile::set_archive_entity_hashes()_).
+/// Hashes that are incomplete (because the limit was reached) are never archived, since they can't ensure the generated data is novel.
+pub struct EntityHashes {
+	/// If true, the hashes are archived with the Profile
+	pub archive: bool,
+	/// The maximum number of hashes that are kept (None for no limit), so that the memory stays bounded when the entities are streamed
+	pub limit: Option<usize>,
+	/// The hashes of the entities
+	hashes: BTreeSet<u64>,
+	/// If true, some of the analyzed entities have no hash (e.g.: the limit was reached)
+	truncated: bool,
+}
+
+impl From<BTreeSet<u64>> for EntityHashes {
+	fn from(hashes: BTreeSet<u64>) -> Self {
+		// the hashes of an archive have been archived explicitly
+		EntityHashes {
+			archive: true,
+			limit: None,
+			hashes,
+			truncated: false,
+		}
+	}
+}
+
+impl From<EntityHashes> for BTreeSet<u64> {
+	fn from(entity_hashes: EntityHashes) -> Self {
+		entity_hashes.hashes
+	}
+}
+
+impl EntityHashes {
+	/// Constructs a new EntityHashes, which isn't archived
+	pub fn new() -> EntityHashes {
+		EntityHashes {
+			archive: false,
+			limit: None,
+			hashes: BTreeSet::new(),
+			truncated: false,
+		}
+	}
+
+	/// This function adds the hash of an entity, unless the limit of hashes has been reached
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The analyzed entity.</br>
+	pub fn insert(&mut self, entity: &str) {
+		let hash = hash_str(entity);
+
+		if !self.is_full() {
+			self.hashes.insert(hash);
+		} else if !self.hashes.contains(&hash) {
+			self.truncated = true;
+		}
+	}
+
+	/// This function returns true if the hash of the entity has been added
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The entity, (e.g.: a generated value).</br>
+	pub fn contains(&self, entity: &str) -> bool {
+		self.hashes.contains(&hash_str(entity))
+	}
+
+	/// This function adds the hashes of other EntityHashes
+	///
+	/// # Arguments
+	///
+	/// * `other: &EntityHashes` - The hashes to add.</br>
+	pub fn merge(&mut self, other: &EntityHashes) {
+		self.truncated |= other.truncated;
+
+		for hash in other.hashes.iter() {
+			if !self.is_full() {
+				self.hashes.insert(*hash);
+			} else if !self.hashes.contains(hash) {
+				self.truncated = true;
+				break;
+			}
+		}
+	}
+
+	/// This function returns the number of hashes
+	pub fn len(&self) -> usize {
+		self.hashes.len()
+	}
+
+	/// This function returns true if there are no hashes
+	pub fn is_empty(&self) -> bool {
+		self.hashes.is_empty()
+	}
+
+	/// This function returns true if none of the analyzed entities is missing a hash (e.g.: because the limit was reached)
+	pub fn is_complete(&self) -> bool {
+		!self.truncated
+	}
+
+	/// This function returns true if the limit of hashes has been reached
+	fn is_full(&self) -> bool {
+		self.limit.map(|l| self.hashes.len() >= l).unwrap_or(false)
+	}
+
+	/// This function returns true if the hashes aren't archived
+	fn is_private(&self) -> bool {
+		!self.archive || self.truncated
+	}
+}
+
+/// Represents an (infinite) iterator of the test data that is generated by a Profile.
+/// The iterator ends if the Profile fails to generate data (e.g.: it hasn't analyzed any data).
+pub struct ProfileIter<'a> {
+	profile: &'a mut Profile,
+}
+
+impl<'a> Iterator for ProfileIter<'a> {
+	type Item = String;
+
+	fn next(&mut self) -> Option<String> {
+		match self.profile.generate() {
+			Ok(generated) => Some(generated),
+			Err(e) => {
+				error!("The profile stopped generating data. Error Message: {}", e);
+				None
+			},
+		}
+	}
+}
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 /// Represents a Profile for sample data that has been analyzed and can be used to generate realistic data
@@ -179,10 +383,66 @@ pub struct Profile {
 	pub size_total: u32,
 	/// A list of pattern sizes (lengths) with a running total of their percent chance of occurrence, in increasing order
 	pub size_ranks: Vec<(u32, f64)>,
-	/// The number of processors used to distribute the work load (multi-thread) while finding Facts to generate data
+	/// The number of processors used to distribute the work load (multi-thread).
+	/// NOTE: The Facts are no longer distributed across the processors since they are indexed when generating data
 	pub processors: u8,
-	/// A list of processors (which are lists of Facts) that store all the Facts in the profile
-	pub facts: Vec<Vec<Fact>>,
+	/// The distinct Facts in the profile with their count of occurrence
+	/// (archives of earlier versions that list the Facts per processor can still be loaded)
+	pub facts: FactCounts,
+	/// The Markov chain (n-gram model) of the chars that is used to condition each generated char on the chars generated before it
+	#[serde(default)]
+	pub markov: MarkovChain,
+	/// The hashes of the sample data entities that have been analyzed, which are used to generate novel values (see _set_novel_mode()_).
+	/// The hashes are only archived if they are explicitly archived (see _set_archive_entity_hashes()_)
+	#[serde(default, skip_serializing_if = "EntityHashes::is_private")]
+	pub entity_hashes: EntityHashes,
+	/// The user-defined symbol classes that the PatternDefinition used to analyze the data is built with (see _new_with_symbol_classes()_)
+	#[serde(default)]
+	pub symbol_classes: Vec<SymbolClass>,
+	/// The model of the tokens of the analyzed entities, which is only used in the token-aware mode (see _new_with_token_mode()_)
+	#[serde(default)]
+	pub tokens: Option<TokenModel>,
+	/// If true, the patterns are generalized into run-length forms when generating data, so that new lengths can be generated (see _set_pattern_generalization()_)
+	#[serde(default)]
+	pub generalize_patterns: bool,
+	/// If true, valid values of the semantic type of the analyzed entities are generated instead of the chars of a pattern (see _set_semantic_generation()_)
+	#[serde(default)]
+	pub semantic_generation: bool,
+	/// The semantic types and formats of the analyzed entities, which are used to generate valid values of the semantic type (e.g.: dates or email addresses)
+	/// when the confidence of the type is at least the threshold of the model (see _semantic_type()_)
+	#[serde(default)]
+	pub semantic: SemanticModel,
+	/// If true, numbers are generated from the histogram of the analyzed numbers instead of the chars of a pattern (see _set_numeric_generation()_)
+	#[serde(default)]
+	pub numeric_generation: bool,
+	/// The range, sign, precision, histogram and formats of the analyzed numbers, which are used to generate numbers
+	/// when most of the analyzed entities are numbers (see _is_numeric()_)
+	#[serde(default)]
+	pub numeric: NumericModel,
+	/// The PatternDefinition that is used to analyze the data (not archived).
+	/// It is built from the symbol classes when data is first analyzed
+	#[serde(skip)]
+	definition: Option<PatternDefinition>,
+	/// The random number generator used when generating data (not archived).
+	/// It is seeded from entropy unless a seed has been set using _set_seed()_
+	#[serde(skip)]
+	rng: Option<StdRng>,
+	/// The index of the Facts that is used to select the generated chars (not archived).
+	/// It is built by _pre_generate()_, or when data is first generated
+	#[serde(skip)]
+	index: Option<FactIndex>,
+	/// The alias table of the patterns that is used to select the pattern of the generated data (not archived)
+	#[serde(skip)]
+	pattern_table: Option<AliasTable<String>>,
+	/// The generalized patterns that are used to select the pattern of the generated data when the patterns are generalized (not archived)
+	#[serde(skip)]
+	generalized: Option<GeneralizedPatterns>,
+	/// The novel-value mode that is used when generating data (not archived)
+	#[serde(skip)]
+	novel: Option<NovelMode>,
+	/// The hashes of the values that have been generated in the novel-value mode (not archived)
+	#[serde(skip)]
+	generated: HashSet<u64>,
 }
 
 impl Profile {
@@ -212,7 +472,23 @@ impl Profile {
 			size_total: 0,
 			size_ranks: Vec::new(),
 			processors: 4,
-			facts: Profile::new_facts(4),
+			facts: FactCounts::new(),
+			markov: MarkovChain::default(),
+			rng: None,
+			entity_hashes: EntityHashes::new(),
+			symbol_classes: Vec::new(),
+			tokens: None,
+			generalize_patterns: false,
+			semantic_generation: false,
+			semantic: SemanticModel::new(),
+			numeric_generation: false,
+			numeric: NumericModel::new(),
+			definition: None,
+			index: None,
+			pattern_table: None,
+			generalized: None,
+			novel: None,
+			generated: HashSet::new(),
 		}
 	}
 
@@ -232,29 +508,16 @@ impl Profile {
 	pub fn new_with_id(id: String) -> Profile {
 		Profile {
 			id: Some(id),
-			patterns: PatternMap::new(),
-			pattern_total: 0,
-			pattern_keys: Vec::new(),
-			pattern_vals: Vec::new(),
-			pattern_percentages: Vec::new(),
-			pattern_ranks: Vec::new(),
-			sizes: SizeMap::new(),
-			size_total: 0,
-			size_ranks: Vec::new(),
-			processors: 4,
-			facts: Profile::new_facts(4),
+			..Profile::new()
 		}
 	}
 
 	/// Constructs a new Profile with a specified number of processors to analyze the data.
-	/// Each processor shares the load of generating the data based on the Facts it has been assigned to manage.
 	///
 	/// # Arguments
 	///
-	/// * `p: u8` - A number that sets the number of processors to start up to manage the Facts.</br>
-	///         Increasing the number of processors will speed up the generator be distributing the workload.
-	///         The recommended number of processors is 1 per 10K data points (e.g.: profiling 20K names should be handled by 2 processors)</br>
-	///         NOTE: The default number of processors is 4.
+	/// * `p: u8` - A number that sets the number of processors.</br>
+	///   NOTE: The default number of processors is 4. The Facts are no longer distributed across the processors since they are indexed when generating data.
 	///
 	/// #Example
 	///
@@ -270,18 +533,102 @@ impl Profile {
 	/// ```
 	pub fn new_with_processors(p: u8) -> Profile {
 		Profile {
-			id: None,
-			patterns: PatternMap::new(),
-			pattern_total: 0,
-			pattern_keys: Vec::new(),
-			pattern_vals: Vec::new(),
-			pattern_percentages: Vec::new(),
-			pattern_ranks: Vec::new(),
-			sizes: SizeMap::new(),
-			size_total: 0,
-			size_ranks: Vec::new(),
 			processors: p,
-			facts: Profile::new_facts(p),
+			..Profile::new()
+		}
+	}
+
+	/// Constructs a new Profile that uses a Markov chain of the specified order to generate the chars.
+	/// The higher the order, the more the generated data will resemble the sample data that was analyzed.
+	///
+	/// # Arguments
+	///
+	/// * `order: u8` - The highest number of preceding chars that each generated char is conditioned on (e.g.: 1 = bigrams, 2 = trigrams).</br>
+	///   An order of 0 disables the Markov chain, so that the chars are only selected based on their position in the pattern.</br>
+	///   NOTE: The default order is 2.
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let profile = Profile::new_with_markov_order(3);
+	///
+	///		assert_eq!(profile.markov.order, 3);
+	/// }
+	/// ```
+	pub fn new_with_markov_order(order: u8) -> Profile {
+		Profile {
+			markov: MarkovChain::new(order),
+			..Profile::new()
+		}
+	}
+
+	/// Constructs a new Profile that analyzes the data using user-defined symbol classes in addition to the built-in classes.
+	/// The symbol classes are archived with the Profile, so that a restored Profile analyzes data consistently.
+	///
+	/// # Arguments
+	///
+	/// * `classes: &[SymbolClass]` - The user-defined symbol classes.</br>
+	///   NOTE: A Parse Error is returned if a PatternDefinition can't be built from the classes.
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::SymbolClass;
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	///		let classes = vec![SymbolClass::from_chars("HexDigit", 'h', "0123456789abcdef", 1)];
+	/// 	let mut profile = Profile::new_with_symbol_classes(&classes).unwrap();
+	///		profile.analyze("0x1f");
+	///
+	///		assert_eq!(profile.patterns.get("hch"), None);
+	///		assert_eq!(profile.patterns.get("hchh"), Some(&1));
+	/// }
+	/// ```
+	pub fn new_with_symbol_classes(classes: &[SymbolClass]) -> Result<Profile, Error> {
+		Ok(Profile {
+			symbol_classes: classes.to_vec(),
+			definition: Some(PatternDefinition::new_with_classes(classes)?),
+			..Profile::new()
+		})
+	}
+
+	/// Constructs a new Profile in the token-aware mode. In the token-aware mode, the entities are also split into tokens on white spaces and punctuation,
+	/// the tokens in the same position (slot) of the entities with the same layout are analyzed together, and each token of the generated data is generated independently,
+	/// (see the `tokens` module).
+	/// NOTE: Data that is generated using _generate_from_pattern()_ is still generated char by char.
+	///
+	/// # Arguments
+	///
+	/// * `real_token_ratio: f64` - The probability (0.0 to 1.0) that a generated token is a whole token of the sample data,
+	///   instead of a token that is generated from the patterns of its slot.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new_with_token_mode(1.0);
+	///		profile.analyze("123 Main Street");
+	///		profile.pre_generate();
+	///
+	///		assert_eq!(profile.generate().unwrap(), "123 Main Street");
+	/// }
+	/// ```
+	pub fn new_with_token_mode(real_token_ratio: f64) -> Profile {
+		Profile {
+			tokens: Some(TokenModel::new(real_token_ratio)),
+			..Profile::new()
 		}
 	}
 
@@ -299,19 +646,19 @@ impl Profile {
 	/// use test_data_generation::Profile;
 	///
 	/// fn main() {
-	///		let mut profile = Profile::from_file("./tests/samples/sample-00-profile");
+	///		let mut profile = Profile::from_file("./tests/samples/sample-00-profile").unwrap();
     ///
     ///     profile.pre_generate();
     ///
-    ///     println!("The generated name is {:?}", profile.generate());
+    ///     println!("The generated name is {:?}", profile.generate().unwrap());
 	/// }
     /// ```
-	pub fn from_file(path: &'static str) -> Profile {
+	pub fn from_file(path: &'static str) -> Result<Profile, Error> {
 		// open the archive file
 		let mut file = match File::open(format!("{}.json",&path)) {
-			Err(_e) => {
+			Err(e) => {
 				error!("Could not open file {:?}", &path.to_string());
-				panic!("Could not open file {:?}", &path.to_string());
+				return Err(Error::Io(e));
 			},
 			Ok(f) => {
 				info!("Successfully opened file {:?}", &path.to_string());
@@ -324,7 +671,7 @@ impl Profile {
 		match file.read_to_string(&mut serialized) {
 			Err(e) => {
 				error!("Could not read file {:?} because of {:?}", &path.to_string(), e.to_string());
-				panic!("Could not read file {:?} because of {:?}", &path.to_string(), e.to_string());
+				return Err(Error::Io(e));
 			},
 			Ok(s) => {
 				info!("Successfully read file {:?}", &path.to_string());
@@ -332,7 +679,6 @@ impl Profile {
 			},
 		};
 
-        //serde_json::from_str(&serialized).unwrap()
         Self::from_serialized(&serialized)
 	}
 
@@ -348,15 +694,18 @@ impl Profile {
 	///
 	/// fn main() {
 	///		let serialized = "{\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}";
-    ///		let mut profile = Profile::from_serialized(&serialized);
+    ///		let mut profile = Profile::from_serialized(&serialized).unwrap();
     ///
     ///     profile.pre_generate();
     ///
-    ///     println!("The generated name is {:?}", profile.generate());
+    ///     println!("The generated name is {:?}", profile.generate().unwrap());
 	/// }
     /// ```
-	pub fn from_serialized(serialized: &str) -> Profile {
-		serde_json::from_str(&serialized).unwrap()
+	pub fn from_serialized(serialized: &str) -> Result<Profile, Error> {
+		serde_json::from_str(serialized).map_err(|e| {
+			error!("Could not parse the serialized Profile because of {:?}", e.to_string());
+			Error::from(e)
+		})
 	}
 
 	/// This function converts an data point (&str) to a pattern and adds it to the profile
@@ -383,13 +732,44 @@ impl Profile {
 	/// }
 	/// ```
 	pub fn analyze(&mut self, entity: &str) {
-		let rslt = PatternDefinition::new().analyze(entity);
+		let rslt = match self.pattern_definition() {
+			Ok(pttrn_def) => pttrn_def.analyze(entity),
+			Err(e) => {
+				error!("Warning: Couldn't analyze the entity {}! Error Message: {}", entity, e);
+				return;
+			},
+		};
 		let _t = self.apply_facts(rslt.0, rslt.1).map_err(|e| {
 			error!("Warning: Couldn't apply the pattern and facts for the entity {}!", entity);
     		e.to_string()
 		});
 	}
 
+	/// This function returns the PatternDefinition that the Profile uses to analyze data, which is built from the symbol classes of the Profile
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::engine::SymbolClass;
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	///		let classes = vec![SymbolClass::from_regex("Currency", '¤', r"\p{Sc}", 1)];
+	/// 	let mut profile = Profile::new_with_symbol_classes(&classes).unwrap();
+	///
+	///		assert_eq!(profile.pattern_definition().unwrap().symbolize_char('$'), '¤');
+	/// }
+	/// ```
+	pub fn pattern_definition(&mut self) -> Result<&mut PatternDefinition, Error> {
+		if self.definition.is_none() {
+			self.definition = Some(PatternDefinition::new_with_classes(&self.symbol_classes)?);
+		}
+
+		Ok(self.definition.as_mut().unwrap())
+	}
+
 	/// This function applies the pattern and list of Facts  to the profile
 	///
 	/// # Arguments
@@ -412,32 +792,125 @@ impl Profile {
 	///		assert_eq!(profile.apply_facts(results.0, results.1).unwrap(), 1);
 	/// }
 	/// ```
-	pub fn apply_facts(&mut self, pattern: String, facts: Vec<Fact>) -> Result<i32, String>{
-		// balance the storing of facts across all the vectors that can be processed in parallel
-		let mut i = 0;
-		for f in facts.into_iter() {
-			if i == self.processors {
-				i = 0;
-			}
+	pub fn apply_facts(&mut self, pattern: String, facts: Vec<Fact>) -> Result<i32, Error>{
+		// learn the transitions between the chars
+		self.markov.learn(&facts);
+		self.index = None;
+
+		// remember the entity, so that it can be excluded from the generated data
+		let entity = facts.iter().map(|f| f.key).collect::<String>();
+		self.entity_hashes.insert(&entity);
+
+		// detect the semantic type of the entity
+		self.semantic.analyze(&entity);
+		self.numeric.analyze(&entity);
+
+		// in the token-aware mode, analyze the tokens of the entity
+		if let Some(model) = self.tokens.as_mut() {
+			model.analyze(&entity, &self.symbol_classes)?;
+		}
 
-			self.facts[i as usize].push(f);
-			i = i + 1;
+		// count the distinct facts
+		for f in facts.into_iter() {
+			self.facts.add(f);
 		}
 
-		// store the pattern
+		// store the pattern, keeping the pattern keys and values (which are sorted like the patterns) in step
 		AddAssign::add_assign(self.patterns.entry(pattern.to_string()).or_insert(0), 1);
 
+		match self.pattern_keys.binary_search(&pattern) {
+			Ok(i) => self.pattern_vals[i] += 1,
+			Err(i) => {
+				self.pattern_keys.insert(i, pattern.clone());
+				self.pattern_vals.insert(i, 1);
+			},
+		}
+
 		// store the total number of patterns generated so far
-		self.pattern_total = self.patterns.values().sum::<u32>();
+		self.pattern_total += 1;
 
 		// analyze sizes
-		AddAssign::add_assign(self.sizes.entry(pattern.len() as u32).or_insert(0), 1);
-		self.size_total = self.sizes.values().sum::<u32>();
+		AddAssign::add_assign(self.sizes.entry(pattern.chars().count() as u32).or_insert(0), 1);
+		self.size_total += 1;
+
+		Ok(1)
+	}
 
+	/// This function rebuilds the lists of the symbolic patterns and their counts (i.e.: pattern_keys and pattern_vals) from the patterns,
+	/// which is needed when the patterns are changed as a whole (e.g.: merged or reset) rather than by _apply_facts()_
+	pub(crate) fn index_patterns(&mut self) {
 		self.pattern_keys = self.patterns.keys().cloned().collect();
 		self.pattern_vals = self.patterns.values().cloned().collect();
+	}
 
-		Ok(1)
+	/// This function merges (unions) the analyzed data of another Profile into this Profile,
+	/// so that profiles that have been built separately (e.g.: on several machines or threads) can be combined.
+	/// The pattern and size counts are summed, the Facts and Markov chain transitions are added,
+	/// and the totals and ranks are re-derived so the Profile is ready to generate data.
+	///
+	/// # Arguments
+	///
+	/// * `other: &Profile` - The Profile to merge into this Profile.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile =  Profile::new();
+	///		profile.analyze("Smith, John");
+	///		profile.analyze("O'Brian, Henny");
+	///
+	/// 	let mut other =  Profile::new();
+	///		other.analyze("Dale, Danny");
+	///		other.analyze("Rickets, Ronney");
+	///
+	///		profile.merge(&other);
+	///
+	///		assert_eq!(profile.pattern_total, 4);
+	///		println!("The generated name is {:?}", profile.generate().unwrap());
+	/// }
+	/// ```
+	pub fn merge(&mut self, other: &Profile) {
+		info!("Merging the profile {:?} into the profile {:?} ...", other.id, self.id);
+
+		if self.symbol_classes != other.symbol_classes {
+			warn!("The profile {:?} was built with other symbol classes than the profile {:?}, so their patterns may not be consistent.", other.id, self.id);
+		}
+
+		self.facts.merge(&other.facts);
+		self.entity_hashes.merge(&other.entity_hashes);
+
+		// the hashes of the other profile weren't archived, so its entities aren't recognized by the novel-value mode
+		if other.pattern_total > 0 && other.entity_hashes.is_empty() {
+			self.entity_hashes.truncated = true;
+		}
+
+		if let Some(other_model) = other.tokens.as_ref() {
+			self.tokens.get_or_insert_with(|| TokenModel::new(other_model.real_token_ratio)).merge(other_model);
+		}
+		self.markov.merge(&other.markov);
+		self.semantic.merge(&other.semantic);
+		self.numeric.merge(&other.numeric);
+
+		for (pattern, cnt) in other.patterns.iter() {
+			AddAssign::add_assign(self.patterns.entry(pattern.to_string()).or_insert(0), *cnt);
+		}
+
+		for (size, cnt) in other.sizes.iter() {
+			AddAssign::add_assign(self.sizes.entry(*size).or_insert(0), *cnt);
+		}
+
+		// re-derive the totals and lists
+		self.pattern_total = self.patterns.values().sum::<u32>();
+		self.size_total = self.sizes.values().sum::<u32>();
+		self.index_patterns();
+
+		// re-derive the ranks
+		self.pre_generate();
 	}
 
 	/// This function calculates the patterns to use by the chance they will occur (as cumulative percentage) in decreasing order
@@ -472,7 +945,7 @@ impl Profile {
 		debug!("calucating the cumulative percentage of occurences for data point patterns...");
 
 		// calculate the percentage by patterns
-		// -> {"CcvccpSCvcc": 14.285714285714285, "CvccvccpSCvccvc": 14.285714285714285, "CvccvccpSCvccvv": 28.57142857142857, "CvcvcccpSCcvcv": 14.285714285714285, "CvcvpSCvccc": 14.285714285714285, "V~CcvvcpSCvccc": 14.285714285714285}
+		// -> {"CcvccpSCvcc": 14.285714285714285, "CvccvccpSCvccvc": 14.285714285714285, "CvccvccpSCvccvv": 28.57142857142857, "CvcvcccpSCcvcv": 14.285714285714285, "CvcvpSCvccc": 14.285714285714285, "V@CcvvcpSCvccc": 14.285714285714285}
 		let n = self.patterns.len();
 
 		// see issue: https://github.com/dsietz/test-data-generation/issues/88
@@ -483,15 +956,17 @@ impl Profile {
 		}
 
 		// sort the ranks by percentages in decreasing order
-		// -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 14.285714285714285), ("CvccvccpSCvccvc", 14.285714285714285), ("CvcvcccpSCcvcv", 14.285714285714285), ("CvcvpSCvccc", 14.285714285714285), ("V~CcvvcpSCvccc", 14.285714285714285)]
+		// -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 14.285714285714285), ("CvccvccpSCvccvc", 14.285714285714285), ("CvcvcccpSCcvcv", 14.285714285714285), ("CvcvpSCvccc", 14.285714285714285), ("V@CcvvcpSCvccc", 14.285714285714285)]
 		self.pattern_percentages.sort_by(|&(_, a), &(_, b)| b.partial_cmp(&a).unwrap());
 
 		// calculate the cumulative sum of the pattern rankings
-		// -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 42.857142857142854), ("CvccvccpSCvccvc", 57.14285714285714), ("CvcvcccpSCcvcv", 71.42857142857142), ("CvcvpSCvccc", 85.7142857142857), ("V~CcvvcpSCvccc", 99.99999999999997)]
+		// -> [("CvccvccpSCvccvv", 28.57142857142857), ("CcvccpSCvcc", 42.857142857142854), ("CvccvccpSCvccvc", 57.14285714285714), ("CvcvcccpSCcvcv", 71.42857142857142), ("CvcvpSCvccc", 85.7142857142857), ("V@CcvvcpSCvccc", 99.99999999999997)]
 		let mut rank: f64 = 0.00;
 
 		// see issue: https://github.com/dsietz/test-data-generation/issues/88
 		self.pattern_ranks.clear();
+		self.pattern_table = None;
+		self.generalized = None;
 
 		for pttrn in self.pattern_percentages.iter() {
 			let tmp = pttrn.1 + rank;
@@ -547,7 +1022,11 @@ impl Profile {
 		}).collect::<Vec<(_,_)>>();
 	}
 
-	/// This function generates realistic test data based on the sampel data that was analyzed.
+	/// This function returns a summary (report) of what the Profile has learned from the sample data that was analyzed.
+	///
+	/// # Arguments
+	///
+	/// * `top_n: usize` - The number of most frequent patterns to report.</br>
 	///
 	/// # Example
 	///
@@ -558,204 +1037,821 @@ impl Profile {
 	///
 	/// fn main() {
     /// 	let mut profile =  Profile::new();
+    ///		profile.analyze("Smith, John");
+    ///		profile.analyze("O'Brian, Henny");
+    ///		profile.analyze("Dale, Danny");
     ///
-    ///		profile.analyze("One");
-    ///		profile.analyze("Two");
-    ///		profile.analyze("Three");
-    ///		profile.analyze("Four");
-    ///		profile.analyze("Five");
-    ///
-    ///     profile.pre_generate();
+    ///		let summary = profile.summary(2);
     ///
-    ///		print!("The test data {:?} was generated.", profile.generate());
+    ///		assert_eq!(summary.distinct_patterns, 3);
+    ///		println!("{}", summary);
     /// }
 	/// ```
-	pub fn generate(&mut self) -> String{
-		// 1. get a random number
-	 	let s: f64 = random_percentage!();
-
-	 	// 2. find the first pattern that falls within the percentage chance of occurring
-	 	// NOTE: The following 2 lines has been commented out because this doesn't need to
-	 	//       happen since the patterns are already ranks by percent chance of occurring
-	 	//       and therefore sizes (lengths) as well since the patterns include the full
-	 	//       length of the entitiy analyzed.
-		//let size = self.size_ranks.iter().find(|&&x|&x.1 >= &s).unwrap().0;
-		//let pattern = self.pattern_ranks.iter().find(|x|&x.1 >= &s && x.0.len() == size as usize).unwrap().clone();
-		let pattern = self.pattern_ranks.iter().find(|x|&x.1 >= &s).unwrap().clone();
-
-		// lastly, generate the test data using facts that adhere to the pattern
-		let generated = self.generate_from_pattern(pattern.0);
-
-		generated
+	pub fn summary(&self, top_n: usize) -> ProfileSummary {
+		ProfileSummary::new(self, top_n)
 	}
 
-	/// This function generates realistic test data based on the sample data that was analyzed.
+	/// This function returns a regular expression that matches the data the Profile has analyzed, so that it can be used to validate data.
+	/// Each pattern placeholder is replaced by the set of chars that have been analyzed for the placeholder (see the `validator` module).
 	///
 	/// # Arguments
 	///
-	/// * `pattern: String` - The pattern to reference when generating the test data.</br>
+	/// * `form: RegexForm` - The form of the regular expression, which is either an alternation of the exact patterns,
+	///   or an alternation of the generalized (run-length) patterns.</br>
+	///
+	/// #Errors
+	/// If the Profile hasn't analyzed any data, an Error::EmptyProfile is returned.
 	///
 	/// # Example
 	///
 	/// ```rust
 	/// extern crate test_data_generation;
 	///
-	/// use test_data_generation::Profile;
+	/// use test_data_generation::{Profile, RegexForm};
 	///
 	/// fn main() {
-    /// 	let mut profile =  Profile::new();
-    ///
-    ///		profile.analyze("01/13/2017");
-    ///		profile.analyze("11/24/2017");
-    ///		profile.analyze("08/05/2017");
-    ///
-    ///     profile.pre_generate();
-    ///
-    ///  	let generated = profile.generate_from_pattern("##p##p####".to_string());
-    ///
-    ///     assert_eq!(generated.len(), 10);
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("XY-12");
+	///		profile.analyze("ZW-345");
+	///
+	///		assert_eq!(profile.to_regex(RegexForm::Exact).unwrap(), "^(?:[W-Z]{2}\\x{2D}[1-5]{2}|[W-Z]{2}\\x{2D}[1-5]{3})$");
+	///		assert_eq!(profile.to_regex(RegexForm::Generalized).unwrap(), "^(?:[W-Z]{2}\\x{2D}[1-5]{2,3})$");
+	/// }
+	/// ```
+	pub fn to_regex(&self, form: RegexForm) -> Result<String, Error> {
+		validator::profile_regex(self, form)
+	}
+
+	/// This function generates realistic test data based on the sampel data that was analyzed.
+	/// By default, the data is generated from the patterns of the analyzed data, (see _set_semantic_generation()_ and _set_numeric_generation()_
+	/// to generate valid values of the semantic type or numbers from the histogram of the analyzed numbers instead).
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+    /// 	let mut profile =  Profile::new();
+    ///
+    ///		profile.analyze("One");
+    ///		profile.analyze("Two");
+    ///		profile.analyze("Three");
+    ///		profile.analyze("Four");
+    ///		profile.analyze("Five");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///		print!("The test data {:?} was generated.", profile.generate().unwrap());
+    /// }
+	/// ```
+	pub fn generate(&mut self) -> Result<String, Error> {
+		if self.pattern_ranks.is_empty() {
+			return Err(Error::EmptyProfile(format!("The profile {:?} has no pattern ranks. Analyze some data and call pre_generate() first.", self.id.clone().unwrap_or_default())));
+		}
+
+		// select the pattern by its chance of occurring
+		// NOTE: The sizes (lengths) don't need to be selected separately since the patterns
+		//       include the full length of the entitiy analyzed.
+		if self.pattern_table.is_none() {
+			self.pattern_table = Some(self.new_pattern_table());
+		}
+
+		if self.generalize_patterns && self.generalized.is_none() {
+			self.generalized = Some(self.generalize());
+		}
+
+		self.check_novel_mode()?;
+
+		for _ in 0..=self.novel_retries() {
+			let generated = self.generate_value(None)?;
+
+			if self.is_novel(&generated) {
+				return Ok(generated);
+			}
+		}
+
+		Err(self.no_novel_value(None))
+	}
+
+	/// This function generates a value with the generator of the Profile, (i.e.: a value of the semantic type, a number, the tokens, or the chars of a pattern).
+	/// If constraints are provided, each generator is restricted to the values that can satisfy the length and char constraints,
+	/// the pattern is selected from the provided patterns (or the generalized patterns within the length constraints), and only the chars that satisfy the filter are generated.
+	fn generate_value(&mut self, constrained: Option<Constrained>) -> Result<String, Error> {
+		let generated_type = self.generated_semantic_type();
+		let rng = self.rng.get_or_insert_with(StdRng::from_entropy);
+
+		// when the data has a semantic type (e.g.: dates), valid values of the type are generated
+		if let Some(semantic_type) = generated_type {
+			return match constrained {
+				Some((constraints, _, filter)) => self.semantic.generate_within(semantic_type, &constraints.lengths(), filter, rng),
+				None => self.semantic.generate(semantic_type, rng),
+			};
+		}
+
+		// when the data are numbers, the numbers are generated from their histogram in one of their formats
+		if self.numeric_generation && self.numeric.is_numeric() {
+			return match constrained {
+				Some((constraints, _, filter)) => self.numeric.generate_within(&constraints.lengths(), filter, rng),
+				None => self.numeric.generate(rng),
+			};
+		}
+
+		// in the token-aware mode, each token is generated independently
+		if let Some(model) = self.tokens.as_mut() {
+			return match constrained {
+				Some((constraints, _, filter)) => model.generate_with(rng, constraints, filter),
+				None => model.generate(rng),
+			};
+		}
+
+		let sampled = match (constrained, self.generalized.as_ref()) {
+			// when the patterns are generalized, the number of repetitions of each run of the pattern is also selected
+			(Some((constraints, _, _)), Some(generalized)) => {
+				let classes = |s: &char| constraints.char_classes.as_ref().map(|c| c.contains(s)).unwrap_or(true);
+				match generalized.sample_within(rng, &constraints.lengths(), &classes) {
+					Some(p) => Some(p),
+					None => return Err(Error::Unsatisfiable("None of the generalized patterns satisfies the constraints".to_string())),
+				}
+			},
+			(Some((_, patterns, _)), None) => patterns.sample(rng).cloned(),
+			(None, Some(generalized)) => generalized.sample(rng),
+			(None, None) => self.pattern_table.as_ref().unwrap().sample(rng).cloned(),
+		};
+		let pattern = match sampled {
+			Some(p) => p,
+			None => self.pattern_ranks[self.pattern_ranks.len()-1].0.clone(),
+		};
+
+		// lastly, generate the test data using facts that adhere to the pattern
+		self.build_from_pattern(&pattern, constrained.map(|c| c.2))
+	}
+
+	/// This function generates realistic test data, that satisfies the constraints, based on the sample data that was analyzed.
+	/// The value is generated in the same way as _generate()_, (i.e.: a value of the semantic type, a number, the tokens, or the chars of a pattern),
+	/// but each generator is restricted to the values that can satisfy the length and char (forbidden chars and character classes) constraints:
+	/// only the analyzed patterns, the semantic formats, the token layouts and tokens, and the run lengths of the generalized patterns that fit the constraints are selected,
+	/// the digits of the numbers are limited to the maximum length, and only the chars that satisfy the constraints are generated.
+	/// A value that still doesn't satisfy the constraints (e.g.: doesn't match the regular expression) is regenerated up to the number of retries of the constraints.
+	///
+	/// # Arguments
+	///
+	/// * `constraints: &Constraints` - The constraints that the generated data must satisfy.</br>
+	///
+	/// #Errors
+	/// If the Profile has no patterns that satisfy the constraints, or no value satisfied the constraints within the number of retries,
+	/// an Error::Unsatisfiable is returned.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::{Constraints, Profile};
+	///
+	/// fn main() {
+    /// 	let mut profile =  Profile::new();
+    ///
+    ///		profile.analyze("One");
+    ///		profile.analyze("Two");
+    ///		profile.analyze("Three");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///		let generated = profile.generate_with(&Constraints::new().with_min_length(4)).unwrap();
+    ///		assert_eq!(generated.len(), 5);
+    ///
+    ///		assert!(profile.generate_with(&Constraints::new().with_max_length(2)).is_err());
+    /// }
+	/// ```
+	pub fn generate_with(&mut self, constraints: &Constraints) -> Result<String, Error> {
+		if self.pattern_ranks.is_empty() {
+			return Err(Error::EmptyProfile(format!("The profile {:?} has no pattern ranks. Analyze some data and call pre_generate() first.", self.id.clone().unwrap_or_default())));
+		}
+
+		if self.generalize_patterns && self.generalized.is_none() {
+			self.generalized = Some(self.generalize());
+		}
+
+		// restrict the patterns to those that satisfy the constraints
+		let patterns = AliasTable::new(self.pattern_ranks.iter()
+			.filter(|(p, _)| constraints.allows_pattern(p))
+			.map(|(p, _)| (p.clone(), *self.patterns.get(p).unwrap_or(&0) as u64))
+			.collect());
+
+		// the semantic, numeric, token and generalized generators don't select one of the analyzed patterns,
+		// so they are restricted by the constraints themselves
+		let from_patterns = self.generated_semantic_type().is_none() && !(self.numeric_generation && self.numeric.is_numeric()) && self.tokens.is_none() && self.generalized.is_none();
+
+		self.check_novel_mode()?;
+
+		if from_patterns && patterns.is_empty() {
+			let msg = format!("The profile {:?} has no patterns that satisfy the constraints.", self.id.clone().unwrap_or_default());
+			error!("{}", msg);
+			return Err(Error::Unsatisfiable(msg));
+		}
+
+		// the chars must not be forbidden, and their symbols must be one of the character classes
+		let definition = match constraints.char_classes {
+			Some(_) => Some(self.pattern_definition()?.clone()),
+			None => None,
+		};
+		let filter = |c: &char| {
+			constraints.allows_char(*c) &&
+			constraints.char_classes.iter().zip(definition.iter()).all(|(classes, def)| classes.contains(&def.symbolize_char(*c)))
+		};
+
+		for _ in 0..=constraints.retries {
+			// a pattern may not have any facts that satisfy the constraints for one of its chars
+			if let Ok(generated) = self.generate_value(Some((constraints, &patterns, &filter))) {
+				let pattern_def = self.pattern_definition()?;
+				let pattern = generated.chars().map(|c| pattern_def.symbolize_char(c)).collect::<String>();
+
+				if constraints.allows_pattern(&pattern) && constraints.allows_value(&generated) && self.is_novel(&generated) {
+					return Ok(generated);
+				}
+			}
+		}
+
+		let msg = format!("The profile {:?} couldn't generate a value that satisfies the constraints after {} retries.", self.id.clone().unwrap_or_default(), constraints.retries);
+		error!("{}", msg);
+		Err(Error::Unsatisfiable(msg))
+	}
+
+	/// This function generates a list of realistic test data based on the sample data that was analyzed.
+	///
+	/// # Arguments
+	///
+	/// * `n: usize` - The number of values to generate.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+    /// 	let mut profile =  Profile::new();
+    ///
+    ///		profile.analyze("One");
+    ///		profile.analyze("Two");
+    ///		profile.analyze("Three");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///		assert_eq!(profile.generate_n(10).unwrap().len(), 10);
+    /// }
+	/// ```
+	pub fn generate_n(&mut self, n: usize) -> Result<Vec<String>, Error> {
+		(0..n).map(|_| self.generate()).collect()
+	}
+
+	/// This function returns an (infinite) iterator of realistic test data based on the sample data that was analyzed.
+	/// The iterator ends if the Profile fails to generate data (e.g.: it hasn't analyzed any data, or no novel value could be generated).
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+    /// 	let mut profile =  Profile::new();
+    ///
+    ///		profile.analyze("One");
+    ///		profile.analyze("Two");
+    ///		profile.analyze("Three");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///		let generated = profile.iter().take(5).collect::<Vec<String>>();
+    ///		assert_eq!(generated.len(), 5);
+    /// }
+	/// ```
+	pub fn iter(&mut self) -> ProfileIter<'_> {
+		ProfileIter {
+			profile: self,
+		}
+	}
+
+	/// This function generates realistic test data based on the sample data that was analyzed.
+	///
+	/// # Arguments
+	///
+	/// * `pattern: String` - The pattern to reference when generating the test data.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+    /// 	let mut profile =  Profile::new();
+    ///
+    ///		profile.analyze("01/13/2017");
+    ///		profile.analyze("11/24/2017");
+    ///		profile.analyze("08/05/2017");
+    ///
+    ///     profile.pre_generate();
+    ///
+    ///  	let generated = profile.generate_from_pattern("##p##p####".to_string()).unwrap();
+    ///
+    ///     assert_eq!(generated.len(), 10);
     /// }
 	/// ```
-	pub fn generate_from_pattern(&self, pattern: String) -> String {
+	pub fn generate_from_pattern(&mut self, pattern: String) -> Result<String, Error> {
+		self.check_novel_mode()?;
+
+		for _ in 0..=self.novel_retries() {
+			let generated = self.build_from_pattern(&pattern, None)?;
+
+			if self.is_novel(&generated) {
+				return Ok(generated);
+			}
+		}
+
+		Err(self.no_novel_value(Some(&pattern)))
+	}
+
+	/// This function generates a value from the pattern using the Facts (and Markov chain) of the Profile.
+	/// If a filter is provided, only the chars that satisfy the filter are selected.
+	fn build_from_pattern(&mut self, pattern: &str, filter: Option<&dyn Fn(&char) -> bool>) -> Result<String, Error> {
+		if self.index.is_none() {
+			self.index = Some(FactIndex::new(&self.facts, &self.markov));
+		}
+
+		let index = self.index.as_ref().unwrap();
+		let rng = self.rng.get_or_insert_with(StdRng::from_entropy);
 		let pattern_chars = pattern.chars().collect::<Vec<char>>();
-		let mut generated = String::new();
-		let prev_char = ' ';
+		let mut generated = Vec::with_capacity(pattern_chars.len());
 
 		// iterate through the chars in the pattern string
 		for (idx, ch) in pattern_chars.iter().enumerate() {
-			match crossbeam::scope(|scope| {
-				let c = ch;
-				let starts = if idx == 0 { 1 } else { 0 };
-			 	let ends = if idx == pattern_chars.len()-1 { 1 } else { 0 };
-			 	let mut fact_options = vec![];
-			 	let prior_char = prev_char;
-
-			 	// iterate through the processors (vec) that hold the lists (vec) of facts
-				for v in &self.facts {
-					let selected_facts = scope.spawn(move |_| {
-						let mut facts = vec![];
-
-						// iterate through the list of facts
-						for value in v {
-							if value.starts_with == starts &&
-							   value.ends_with == ends &&
-							   value.pattern_placeholder == *c &&
-							   value.index_offset == idx as u32 {
-									facts.push(value.key.clone());
-
-									// if the value.key's prior char matches the prior generated char, then weight the value.key
-									// to increase the chance of it being used when generated
-									if value.prior_key.unwrap_or(' ') == prior_char {
-										facts.push(value.key.clone());
-										facts.push(value.key.clone());
-									}
-
-									// if the value.key's index_offset matches the current index, then weight the value.key
-									// to increase the chance of it being used when generated
-									if value.index_offset == idx as u32 {
-										facts.push(value.key.clone());
-										facts.push(value.key.clone());
-									}
-							}
-						}
-
-						facts
-					});
-
-					//append the selected_facts to the fact_options 
-					//fact_options.extend_from_slice(&selected_facts.join());
-					match selected_facts.join() {
-						Ok(sf) => fact_options.extend_from_slice(&sf),
-						Err(err) => {
-							error!("{:?}", err);
-							panic!("{:?}", err);
-						}
-					}
-				}
+			// condition the char on the chars that have already been generated,
+			// otherwise use the facts that match the placeholder and position of the char
+			let prior = generated.last().cloned();
+			let mut picked = match filter {
+				Some(f) => index.pick_transition_where(*ch, &generated, rng, f).or_else(|| index.pick_fact_where(*ch, idx, pattern_chars.len(), prior, rng, f)),
+				None => index.pick_transition(*ch, &generated, rng).or_else(|| index.pick_fact(*ch, idx, pattern_chars.len(), prior, rng)),
+			};
+
+			// a generalized pattern may have a length that hasn't been analyzed, so use any fact of the placeholder
+			if picked.is_none() && self.generalize_patterns {
+				picked = match filter {
+					Some(f) => index.pick_any_where(*ch, idx, pattern_chars.len(), rng, f),
+					None => index.pick_any(*ch, idx, pattern_chars.len(), rng),
+				};
+			}
 
-				//select a fact to use as the generated char
-				let rnd_start = 0;
-				let rnd_end = fact_options.len()-1;
-
-				if rnd_start >= rnd_end {
-					//generated.push(fact_options[0 as usize]);					
-					fact_options[0 as usize]
-				}else{
-					let x: u32 = random_between!(rnd_start, rnd_end);
-					//prev_char = fact_options[x as usize];
-					//generated.push(prev_char);
-					fact_options[x as usize]
-				}
-			}) {
-				Ok(c) => generated.push(c),
-				Err(err) => {
-					error!("{:?}", err);
-					panic!("{:?}", err);
+			let c = match picked {
+				Some(c) => c,
+				None => {
+					return Err(Error::EmptyProfile(format!("There are no facts for the symbol {:?} at index {} of the pattern {:?}", ch, idx, pattern)));
 				},
-			}
+			};
+
+			generated.push(c);
 		}
 
-		generated
+		Ok(generated.into_iter().collect())
 	}
 
 	/// This function learns by measuring how realistic the test data it generates to the sample data that was provided.
 	///
 	/// # Arguments
 	///
-	/// * `control_list: Vec<String>` - The list of strings to compare against. This would be the real data from the data sample.</br>
+	/// * `control_list: Vec<String>` - The list of strings to compare against. This would be the real data from the data sample.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profil =  Profile::new();
+	/// 	let sample_data = vec!("Smith, John".to_string(),"Doe, John".to_string(),"Dale, Danny".to_string(),"Rickets, Ronney".to_string());
+	///
+	/// 	for sample in sample_data.iter().clone() {
+	/// 		profil.analyze(&sample);
+	/// 	}
+	///
+	/// 	// in order to learn the profile must be prepared with pre_genrate()
+	///		// so it can generate data to learn from
+	///		profil.pre_generate();
+	///
+	/// 	let learning = profil.learn_from_entity(sample_data).unwrap();
+	///
+	/// 	assert_eq!(learning, true);
+    /// }
+	/// ```
+	pub fn learn_from_entity(&mut self, control_list: Vec<String>) -> Result<bool, Error> {
+		for _n in 0..10 {
+			let experiment = self.generate()?;
+			let mut percent_similarity: Vec<f64> = Vec::new();
+
+			for control in control_list.iter().clone() {
+				debug!("Comparing {} with {} ...", &control, &experiment);
+				percent_similarity.push(self.realistic_test(&control, &experiment));
+			}
+
+			let percent = percent_similarity.iter().sum::<f64>() as f64 / percent_similarity.len() as f64;
+			debug!("Percent similarity is {} ...", &percent);
+
+			if percent >= 80 as f64 {
+				self.analyze(&experiment);
+			}
+		}
+
+		Ok(true)
+	}
+
+	/// This function calculates the levenshtein distance between 2 strings.
+	/// See: https://crates.io/crates/levenshtein
+	///
+	/// # Arguments
+	///
+	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
+	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the distance.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	///		let mut profile =  Profile::new();
+	///
+	///     assert_eq!(profile.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()), 3 as usize);
+	/// }
+	///
+	pub fn levenshtein_distance(&mut self, control: &String, experiment: &String) -> usize {
+		// https://docs.rs/levenshtein/1.0.3/levenshtein/fn.levenshtein.html
+		levenshtein_distance!(control, experiment)
+	}
+
+	/// This function calculates the percent difference between 2 strings.
+	///
+	/// # Arguments
+	///
+	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
+	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the percent difference.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	///		let mut profile =  Profile::new();
+	///
+	///     assert_eq!(profile.realistic_test(&"kitten".to_string(), &"sitting".to_string()), 76.92307692307692 as f64);
+	/// }
+	///
+	pub fn realistic_test(&mut self, control: &String, experiment: &String) -> f64 {
+		realistic_test!(control, experiment)
+	}
+
+	/// This function seeds the random number generator used when generating data, so that the same seed and Profile
+	/// will always generate the same sequence of test data. This is useful to reproduce a data set (e.g.: when a test fails).
+	///
+	/// # Arguments
+	///
+	/// * `seed: u64` - The number used to seed the random number generator.</br>
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile =  Profile::new();
+	///		profile.analyze("Smith, John");
+	///		profile.analyze("O'Brian, Henny");
+	///		profile.analyze("Dale, Danny");
+	///		profile.analyze("Rickets, Ronney");
+	///		profile.pre_generate();
+	///
+	///		let mut copy = profile.clone();
+	///
+	///		profile.set_seed(1234);
+	///		copy.set_seed(1234);
+	///
+	///		assert_eq!(profile.generate().unwrap(), copy.generate().unwrap());
+	/// }
+	/// ```
+	pub fn set_seed(&mut self, seed: u64) {
+		self.rng = Some(StdRng::seed_from_u64(seed));
+
+		if let Some(model) = self.tokens.as_mut() {
+			model.clear_seeds();
+		}
+	}
+
+	/// This function turns the generalization of the patterns on or off. When the patterns are generalized, patterns that have the same sequence of runs
+	/// (e.g.: `Cvcc` and `Cvvccc`) are combined, and the number of repetitions of each run is selected by its chance of occurring,
+	/// so that data of new-but-plausible lengths can be generated (see the `generalization` module).
+	/// When it is off (the default), only the exact patterns of the analyzed data are generated.
+	///
+	/// # Arguments
+	///
+	/// * `generalize: bool` - If true, the patterns are generalized.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("Abc");
+	///		profile.analyze("Abbbbc");
+	///		profile.set_pattern_generalization(true);
+	///		profile.pre_generate();
+	///
+	///		let generated = profile.generate().unwrap();
+	///		assert!(generated.len() >= 3 && generated.len() <= 6);
+	/// }
+	/// ```
+	pub fn set_pattern_generalization(&mut self, generalize: bool) {
+		self.generalize_patterns = generalize;
+		self.generalized = None;
+	}
+
+	/// This function returns the semantic type of most of the analyzed entities (e.g.: email addresses, dates or UUIDs) with its confidence,
+	/// which is the fraction of the analyzed entities that have the type (None if no entity has a semantic type).
+	/// If the semantic generation is on (see _set_semantic_generation()_) and the confidence is at least the threshold (see _set_semantic_threshold()_),
+	/// _generate()_ generates valid values of the type in the formats of the analyzed entities instead of generating them char by char.
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	/// use test_data_generation::semantic::SemanticType;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("(555) 123-4567");
+	///		profile.analyze("(555) 987-6543");
+	///		profile.pre_generate();
+	///
+	///		assert_eq!(profile.semantic_type(), Some((SemanticType::Phone, 1.0)));
+	///		assert_eq!(profile.generate().unwrap().len(), 14);
+	/// }
+	/// ```
+	pub fn semantic_type(&self) -> Option<(SemanticType, f64)> {
+		self.semantic.semantic_type()
+	}
+
+	/// This function sets the minimum confidence of the semantic type that is required to generate valid values of the type when the semantic generation is on
+	/// (the default is 0.9). A threshold above 1.0 turns the type-aware generation off, so the data is always generated char by char.
+	///
+	/// # Arguments
+	///
+	/// * `threshold: f64` - The minimum confidence, (e.g.: 0.8).</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("01/04/2017");
+	///		profile.set_semantic_threshold(1.1);
+	///		profile.pre_generate();
+	///
+	///		assert_eq!(profile.semantic.generated_type(), None);
+	///		assert_eq!(profile.generate().unwrap().len(), 10);
+	/// }
+	/// ```
+	pub fn set_semantic_threshold(&mut self, threshold: f64) {
+		self.semantic.threshold = threshold;
+	}
+
+	/// This function sets if valid values of the semantic type of the analyzed entities (e.g.: dates, email addresses or phone numbers) are generated
+	/// in the formats of the analyzed entities, when the confidence of the type is at least the threshold (see _semantic_type()_).
+	/// When it is off (the default), the data is generated char by char from the patterns of the analyzed data.
+	///
+	/// # Arguments
+	///
+	/// * `semantic: bool` - If true, the values of the semantic type are generated.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	/// use test_data_generation::semantic::SemanticModel;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("01/04/2017");
+	///		profile.analyze("12/21/2018");
+	///		profile.set_semantic_generation(true);
+	///		profile.pre_generate();
+	///
+	///		let date = profile.generate().unwrap();
+	///		assert!(SemanticModel::classify(&date).is_some());
+	/// }
+	/// ```
+	pub fn set_semantic_generation(&mut self, semantic: bool) {
+		self.semantic_generation = semantic;
+	}
+
+	/// This function returns the semantic type of which valid values are generated (None if the data is generated from the patterns)
+	pub(crate) fn generated_semantic_type(&self) -> Option<SemanticType> {
+		match self.semantic_generation {
+			true => self.semantic.generated_type(),
+			false => None,
+		}
+	}
+
+	/// This function returns true if most of the analyzed entities are numbers, (i.e.: the fraction of the numbers is at least the threshold,
+	/// see _set_numeric_threshold()_). If the numeric generation is on (see _set_numeric_generation()_), the numbers of a numeric Profile are generated
+	/// from the histogram of the analyzed numbers, within their range, in one of their formats (e.g.: with leading zeros, thousands separators or decimal places)
+	/// instead of digit by digit. Semantic types (see _semantic_type()_) take precedence over numbers.
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("18");
+	///		profile.analyze("42");
+	///		profile.analyze("65");
+	///		profile.set_numeric_generation(true);
+	///		profile.pre_generate();
+	///
+	///		assert!(profile.is_numeric());
+	///
+	///		let age = profile.generate().unwrap().parse::<u32>().unwrap();
+	///		assert!(age >= 18 && age <= 65);
+	/// }
+	/// ```
+	pub fn is_numeric(&self) -> bool {
+		self.numeric.is_numeric()
+	}
+
+	/// This function sets the minimum fraction of the analyzed entities that must be numbers to generate numbers when the numeric generation is on
+	/// (the default is 0.9). A threshold above 1.0 turns the numeric generation off, so the numbers are always generated digit by digit.
+	///
+	/// # Arguments
+	///
+	/// * `threshold: f64` - The minimum fraction, (e.g.: 0.8).</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("12");
+	///		profile.analyze("n/a");
+	///		profile.set_numeric_threshold(0.5);
+	///
+	///		assert!(profile.is_numeric());
+	/// }
+	/// ```
+	pub fn set_numeric_threshold(&mut self, threshold: f64) {
+		self.numeric.threshold = threshold;
+	}
+
+	/// This function sets if the numbers of a numeric Profile (see _is_numeric()_) are generated from the histogram of the analyzed numbers,
+	/// within their range and in one of their formats. When it is off (the default), the numbers are generated digit by digit from the patterns of the analyzed data.
+	///
+	/// # Arguments
+	///
+	/// * `numeric: bool` - If true, the numbers are generated from the histogram.</br>
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("1500");
+	///		profile.analyze("2500");
+	///		profile.set_numeric_generation(true);
+	///		profile.pre_generate();
+	///
+	///		let number = profile.generate().unwrap().parse::<u32>().unwrap();
+	///		assert!(number >= 1500 && number <= 2500);
+	/// }
+	/// ```
+	pub fn set_numeric_generation(&mut self, numeric: bool) {
+		self.numeric_generation = numeric;
+	}
+
+	/// This function returns the generalized (run-length) forms of the patterns of the Profile, (e.g.: `C v{1,2} c{2,5}`)
+	///
+	/// #Example
+	///
+	/// ```rust
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	/// 	let mut profile = Profile::new();
+	///		profile.analyze("Abc");
+	///		profile.analyze("Abbbbc");
+	///
+	///		assert_eq!(profile.generalize().to_string(), "V c{2,5}");
+	/// }
+	/// ```
+	pub fn generalize(&self) -> GeneralizedPatterns {
+		GeneralizedPatterns::new(&self.patterns)
+	}
+
+	/// This function sets the novel-value mode of the Profile. In the novel-value mode, a generated value never equals any of the
+	/// sample data entities that were analyzed (which are remembered as hashes), and optionally never equals a value that was
+	/// generated before. Values that don't satisfy the mode are regenerated, and an error is returned when the retries are used up.
+	/// Setting the mode starts a new batch of generated data.
+	///
+	/// NOTE: The sample data can only be recognized if the Profile holds the hashes of all the analyzed entities. This isn't the case for a Profile
+	/// that is restored from an archive without its hashes (see _set_archive_entity_hashes()_), or that analyzed more entities than the limit of hashes
+	/// (see _set_entity_hash_limit()_). Such a Profile can't be set to (or generate data in) the novel-value mode.
+	///
+	/// # Arguments
+	///
+	/// * `mode: Option<NovelMode>` - The novel-value mode to use, or None to turn it off.</br>
+	///
+	/// #Errors
+	/// If the Profile has analyzed entities without holding all of their hashes, an Error::Config is returned and the mode isn't changed.
 	///
-	/// # Example
+	/// #Example
 	///
 	/// ```rust
 	/// extern crate test_data_generation;
 	///
-	/// use test_data_generation::Profile;
+	/// use test_data_generation::{NovelMode, Profile};
 	///
 	/// fn main() {
-	/// 	let mut profil =  Profile::new();
-	/// 	let sample_data = vec!("Smith, John".to_string(),"Doe, John".to_string(),"Dale, Danny".to_string(),"Rickets, Ronney".to_string());
-	///
-	/// 	for sample in sample_data.iter().clone() {
-	/// 		profil.analyze(&sample);
-	/// 	}
-	///
-	/// 	// in order to learn the profile must be prepared with pre_genrate()
-	///		// so it can generate data to learn from
-	///		profil.pre_generate();
+	/// 	let mut profile =  Profile::new();
+	///		profile.analyze("Smith, John");
+	///		profile.analyze("O'Brian, Henny");
+	///		profile.analyze("Dale, Danny");
+	///		profile.analyze("Rickets, Ronney");
+	///		profile.pre_generate();
 	///
-	/// 	let learning = profil.learn_from_entity(sample_data).unwrap();
+	///		profile.set_novel_mode(Some(NovelMode::new(false, 1000))).unwrap();
 	///
-	/// 	assert_eq!(learning, true);
-    /// }
+	///		let generated = profile.generate().unwrap();
+	///		assert!(!["Smith, John", "O'Brian, Henny", "Dale, Danny", "Rickets, Ronney"].contains(&generated.as_str()));
+	/// }
 	/// ```
-	pub fn learn_from_entity(&mut self, control_list: Vec<String>) -> Result<bool, String> {
-		for _n in 0..10 {
-			let experiment = self.generate();
-			let mut percent_similarity: Vec<f64> = Vec::new();
-
-			for control in control_list.iter().clone() {
-				debug!("Comparing {} with {} ...", &control, &experiment);
-				percent_similarity.push(self.realistic_test(&control, &experiment));
-			}
-
-			let percent = percent_similarity.iter().sum::<f64>() as f64 / percent_similarity.len() as f64;
-			debug!("Percent similarity is {} ...", &percent);
-
-			if percent >= 80 as f64 {
-				self.analyze(&experiment);
-			}
+	pub fn set_novel_mode(&mut self, mode: Option<NovelMode>) -> Result<(), Error> {
+		if mode.is_some() {
+			self.check_entity_hashes()?;
 		}
 
-		Ok(true)
+		self.novel = mode;
+		self.generated.clear();
+		Ok(())
 	}
 
-	/// This function calculates the levenshtein distance between 2 strings.
-	/// See: https://crates.io/crates/levenshtein
+	/// This function sets if the hashes of the analyzed entities are archived with the Profile, so that the novel-value mode
+	/// also recognizes the sample data after the Profile is restored from the archive. By default, the hashes aren't archived,
+	/// since short sample data (e.g.: names, zip codes or phone numbers) could be recovered from the archived hashes by trying all the possible values.
+	/// The parts of the entities that are reused when generating values of the semantic types (i.e.: the domains of the email addresses,
+	/// the schemes, hosts and ports of the URLs and the years of the dates, see _SemanticModel::values_) are only archived with the same setting.
+	/// NOTE: The whole tokens in the token-aware mode (see _TokenSlot::vocabulary_) are always archived as they are,
+	/// so don't share the archive of a Profile of sensitive sample data that is analyzed in the token-aware mode.
 	///
 	/// # Arguments
 	///
-	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
-	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the distance.</br>
+	/// * `archive: bool` - If true, the hashes are archived.</br>
 	///
 	/// #Example
 	///
@@ -765,22 +1861,27 @@ impl Profile {
 	/// use test_data_generation::Profile;
 	///
 	/// fn main() {
-	///		let mut profile =  Profile::new();
+	/// 	let mut profile =  Profile::new();
+	///		profile.analyze("Smith, John");
+	///		assert!(!profile.serialize().contains("entity_hashes"));
 	///
-	///     assert_eq!(profile.levenshtein_distance(&"kitten".to_string(), &"sitting".to_string()), 3 as usize);
+	///		profile.set_archive_entity_hashes(true);
+	///		assert!(profile.serialize().contains("entity_hashes"));
 	/// }
-	///
-	pub fn levenshtein_distance(&mut self, control: &String, experiment: &String) -> usize {
-		// https://docs.rs/levenshtein/1.0.3/levenshtein/fn.levenshtein.html
-		levenshtein_distance!(control, experiment)
+	/// ```
+	pub fn set_archive_entity_hashes(&mut self, archive: bool) {
+		self.entity_hashes.archive = archive;
+		self.semantic.values.archive = archive;
 	}
 
-	/// This function calculates the percent difference between 2 strings.
+	/// This function sets the maximum number of hashes of the analyzed entities that are kept for the novel-value mode.
+	/// Since a hash is kept for each distinct entity, the limit bounds the memory of the Profile when the entities are streamed
+	/// (see _Engine::profile_entities_from_iter()_). The entities that are analyzed after the limit is reached aren't recognized by the novel-value mode,
+	/// and a limit of 0 turns off the hashing of the entities. The limit isn't archived with the Profile.
 	///
 	/// # Arguments
 	///
-	/// * `control: &String` - The string to compare against. This would be the real data from the data sample.</br>
-	/// * `experiment: &String` - The string to compare. This would be the generated data for which you want to find the percent difference.</br>
+	/// * `limit: Option<usize>` - The maximum number of hashes, (None for no limit).</br>
 	///
 	/// #Example
 	///
@@ -790,33 +1891,76 @@ impl Profile {
 	/// use test_data_generation::Profile;
 	///
 	/// fn main() {
-	///		let mut profile =  Profile::new();
+	/// 	let mut profile =  Profile::new();
+	///		profile.set_entity_hash_limit(Some(1));
+	///		profile.analyze("Smith, John");
+	///		profile.analyze("Dale, Danny");
 	///
-	///     assert_eq!(profile.realistic_test(&"kitten".to_string(), &"sitting".to_string()), 76.92307692307692 as f64);
+	///		assert_eq!(profile.entity_hashes.len(), 1);
 	/// }
-	///
-	pub fn realistic_test(&mut self, control: &String, experiment: &String) -> f64 {
-		realistic_test!(control, experiment)
+	/// ```
+	pub fn set_entity_hash_limit(&mut self, limit: Option<usize>) {
+		self.entity_hashes.limit = limit;
 	}
 
-	/// This function is called from within the implementated structure and returns a list processors (Vec) with empty lists (Vec) for their Facts.
-	/// Each processor shares the load of generating the data based on the Facts it has been assigned to manage.
-	///
-	/// # Arguments
-	///
-	/// * `p: u8` - A number that sets the number of processors to start up to manage the Facts.</br>
-	///         Increasing the number of processors will speed up the generator be ditributing the workload.
-	///         The recommended number of processors is 1 per 10K data points (e.g.: profiling 20K names should be handled by 2 processors)</br>
-	///         NOTE: The default number of processors is 4.
-	///
-	fn new_facts(p: u8) -> Vec<Vec<Fact>> {
-		let mut vec_main = Vec::new();
+	/// This function returns an Error::Config if the Profile has analyzed entities without holding all of their hashes,
+	/// so that the novel-value mode can't recognize all of the sample data
+	pub(crate) fn check_entity_hashes(&self) -> Result<(), Error> {
+		if self.pattern_total == 0 || (!self.entity_hashes.is_empty() && self.entity_hashes.is_complete()) {
+			return Ok(());
+		}
+
+		let msg = if self.entity_hashes.is_empty() {
+			format!("The profile {:?} holds no hashes of the analyzed entities (they aren't archived unless set_archive_entity_hashes() is used), so it can't generate novel values.", self.id.clone().unwrap_or_default())
+		} else {
+			format!("The profile {:?} holds the hashes of only some of the analyzed entities (the limit of hashes was reached), so it can't generate novel values.", self.id.clone().unwrap_or_default())
+		};
+		error!("{}", msg);
+		Err(Error::Config(msg))
+	}
+
+	/// This function returns an error if the Profile is in the novel-value mode, but can't recognize all of the sample data
+	fn check_novel_mode(&self) -> Result<(), Error> {
+		match self.novel {
+			Some(_) => self.check_entity_hashes(),
+			None => Ok(()),
+		}
+	}
 
-		for _ in 0..p {
-			vec_main.push(Vec::new());
+	/// This function returns the number of times a value may be regenerated in the novel-value mode
+	fn novel_retries(&self) -> u32 {
+		self.novel.as_ref().map(|m| m.retries).unwrap_or(0)
+	}
+
+	/// This function returns true if the generated value satisfies the novel-value mode (or the mode is turned off)
+	fn is_novel(&mut self, generated: &str) -> bool {
+		let unique = match &self.novel {
+			Some(mode) => mode.unique,
+			None => return true,
+		};
+		if self.entity_hashes.contains(generated) {
+			return false;
 		}
 
-		vec_main
+		!unique || self.generated.insert(hash_str(generated))
+	}
+
+	/// This function returns the error for when no value that satisfies the novel-value mode could be generated
+	fn no_novel_value(&self, pattern: Option<&str>) -> Error {
+		let retries = self.novel_retries();
+		let msg = match pattern {
+			Some(p) => format!("The profile {:?} couldn't generate a novel value for the pattern {:?} after {} retries.", self.id.clone().unwrap_or_default(), p, retries),
+			None => format!("The profile {:?} couldn't generate a novel value after {} retries.", self.id.clone().unwrap_or_default(), retries),
+		};
+		error!("{}", msg);
+		Error::NoNovelValue(msg)
+	}
+
+	/// This function returns an alias table of the ranked patterns, weighted by their count of occurrence
+	fn new_pattern_table(&self) -> AliasTable<String> {
+		AliasTable::new(self.pattern_ranks.iter().map(|(p, _)| {
+			(p.clone(), *self.patterns.get(p).unwrap_or(&0) as u64)
+		}).collect())
 	}
 
 	/// This function prepares the size a pattern accumulated percentages order by percentage increasing
@@ -848,6 +1992,16 @@ impl Profile {
 		self.cum_sizemap();
 		self.cum_patternmap();
 		info!("Profile: preparing generator...");
+		self.pattern_table = Some(self.new_pattern_table());
+		self.index = Some(FactIndex::new(&self.facts, &self.markov));
+
+		if self.generalize_patterns {
+			self.generalized = Some(self.generalize());
+		}
+
+		if let Some(model) = self.tokens.as_mut() {
+			model.pre_generate();
+		}
 	}
 
 	/// This function resets the patterns that the Profile has analyzed.
@@ -888,6 +2042,15 @@ impl Profile {
 	pub fn reset_analyze(&mut self) {
 		info!("Resetting the profile ...");
 		self.patterns = PatternMap::new();
+		self.pattern_total = 0;
+		self.index_patterns();
+		self.pattern_table = None;
+		self.generalized = None;
+		self.semantic = SemanticModel { threshold: self.semantic.threshold, values: SemanticValues { archive: self.semantic.values.archive, ..SemanticValues::new() }, ..SemanticModel::new() };
+		self.numeric = NumericModel { threshold: self.numeric.threshold, ..NumericModel::new() };
+		if let Some(model) = self.tokens.as_mut() {
+			*model = TokenModel::new(model.real_token_ratio);
+		}
 		info!("Profile: patterns have been reset ...");
 	}
 
@@ -922,14 +2085,14 @@ impl Profile {
     ///     assert_eq!(profile.save("./tests/samples/sample-00-profile").unwrap(), true);
 	/// }
 	///
-	pub fn save(&mut self, path: &'static str) -> Result<bool, io::Error>  {
-		let dsp_json = serde_json::to_string(&self).unwrap();
+	pub fn save(&mut self, path: &'static str) -> Result<bool, Error>  {
+		let dsp_json = serde_json::to_string(&self)?;
 
 		// Create the archive file
 		let mut file = match File::create(format!("{}.json",&path)) {
 			Err(e) => {
 				error!("Could not create file {:?}", &path.to_string());
-				return Err(e);
+				return Err(Error::Io(e));
 			},
 			Ok(f) => {
 				info!("Successfully exported to {:?}", &path.to_string());
@@ -941,7 +2104,7 @@ impl Profile {
     	match file.write_all(dsp_json.as_bytes()) {
         	Err(e) => {
             	error!("Could not write to file {}", &path.to_string());
-            	return Err(e);
+            	return Err(Error::Io(e));
         	},
         	Ok(_) => {
         		info!("Successfully exported to {}", &path.to_string());
@@ -968,7 +2131,7 @@ impl Profile {
 	///		data_profile.analyze("OK");
 	///
     ///     println!("{}", data_profile.serialize());
-    ///     // {"patterns":{"VC":1},"pattern_total":1,"pattern_keys":["VC"],"pattern_vals":[1],"pattern_percentages":[],"pattern_ranks":[],"sizes":{"2":1},"size_total":1,"size_ranks":[],"processors":4,"facts":[[{"key":"O","prior_key":null,"next_key":"K","pattern_placeholder":"V","starts_with":1,"ends_with":0,"index_offset":0}],[{"key":"K","prior_key":"O","next_key":null,"pattern_placeholder":"C","starts_with":0,"ends_with":1,"index_offset":1}],[],[]]}
+    ///     // {"patterns":{"VC":1},"pattern_total":1,"pattern_keys":["VC"],"pattern_vals":[1],"pattern_percentages":[],"pattern_ranks":[],"sizes":{"2":1},"size_total":1,"size_ranks":[],"processors":4,"facts":[{"key":"K","prior_key":"O","next_key":null,"pattern_placeholder":"C","starts_with":0,"ends_with":1,"index_offset":1,"count":1},{"key":"O","prior_key":null,"next_key":"K","pattern_placeholder":"V","starts_with":1,"ends_with":0,"index_offset":0,"count":1}],"markov":{"order":2,"transitions":{"CO":{"K":1}}}}
 	/// }
 	///
 	pub fn serialize(&mut self) ->String {
@@ -982,6 +2145,17 @@ pub mod shared;
 pub mod data_sample_parser;
 pub mod configs;
 pub mod engine;
+pub mod constraints;
+pub mod csv_dialect;
+pub mod error;
+pub mod summary;
+pub mod generalization;
+pub mod validator;
+pub mod tokens;
+pub mod semantic;
+pub mod numeric;
+pub mod json_structure;
+pub mod sql;
 
 // Unit Tests
 #[cfg(test)]
@@ -996,6 +2170,57 @@ mod tests {
 		assert_eq!(profile.apply_facts(results.0, results.1).unwrap(),1);
 	}
 
+	#[test]
+	fn apply_facts_totals(){
+		let mut profile =  Profile::new();
+		profile.analyze("Smith, John");
+		profile.analyze("Dale, Danny");
+		profile.analyze("OK");
+		profile.analyze("Smith, John");
+
+		assert_eq!(profile.pattern_total, 4);
+		assert_eq!(profile.size_total, 4);
+		assert_eq!(profile.pattern_keys, vec!["CcvccpSCvcc".to_string(), "CvcvpSCvccc".to_string(), "VC".to_string()]);
+		assert_eq!(profile.pattern_vals, vec![2, 1, 1]);
+
+		profile.reset_analyze();
+		assert!(profile.pattern_keys.is_empty());
+		profile.analyze("OK");
+		assert_eq!(profile.pattern_total, 1);
+		assert_eq!(profile.pattern_keys, vec!["VC".to_string()]);
+	}
+
+	#[test]
+	fn merge_profiles(){
+		let mut profile =  Profile::new();
+		profile.analyze("Smith, John");
+		profile.analyze("Dale, Danny");
+
+		let mut other =  Profile::new_with_processors(3);
+		other.analyze("Dale, Danny");
+		other.analyze("O'Brian, Henny");
+
+		profile.merge(&other);
+
+		assert_eq!(profile.pattern_total, 4);
+		assert_eq!(profile.size_total, 4);
+		assert_eq!(profile.patterns.get("CvcvpSCvccc"), Some(&2));
+		assert_eq!(profile.pattern_keys.len(), profile.pattern_vals.len());
+		assert_eq!(profile.facts.total(), 11 + 11 + 11 + 14);
+		assert_eq!(profile.pattern_ranks.last().unwrap().1.round(), 100.0);
+		assert!(profile.generate().is_ok());
+	}
+
+	#[test]
+	fn merge_empty_profile(){
+		let mut profile =  Profile::new();
+		profile.analyze("OK");
+		profile.merge(&Profile::new());
+
+		assert_eq!(profile.pattern_total, 1);
+		assert_eq!(profile.generate().unwrap(), "OK");
+	}
+
 	#[test]
     fn levenshtein_test(){
     	let mut profil =  Profile::new();
@@ -1044,37 +2269,514 @@ mod tests {
 
 	#[test]
     fn new_profile_from_file(){
-		let mut profile = Profile::from_file("./tests/samples/sample-00-profile");
+		let mut profile = Profile::from_file("./tests/samples/sample-00-profile").unwrap();
     	profile.pre_generate();
 
-    	assert!(profile.generate().len() > 0);
+    	assert!(profile.generate().unwrap().len() > 0);
     }
 
     #[test]
-    #[should_panic]
     fn new_profile_from_file_bad_data(){
-		let mut profile = Profile::from_file("./tests/samples/not-readable");
+		match Profile::from_file("./tests/samples/not-readable") {
+			Err(Error::Parse(_)) => {},
+			_ => panic!("expected a parse error"),
+		}
+    }
+
+    #[test]
+    fn new_profile_from_file_bad_path(){
+		match Profile::from_file("./tests/samples/bad-path") {
+			Err(Error::Io(_)) => {},
+			_ => panic!("expected an I/O error"),
+		}
+    }
+
+    #[test]
+    fn new_profile_from_serialized_bad_data(){
+		assert!(Profile::from_serialized("{\"patterns\":").is_err());
+    }
+
+    #[test]
+    // ensure an empty or unprepared Profile returns an error instead of panicking
+    fn profile_generate_empty(){
+		let mut profile = Profile::new();
+		assert!(profile.generate().is_err());
+
+		profile.analyze("OK");
+		assert!(profile.generate().is_err());
+
+		profile.pre_generate();
+		assert_eq!(profile.generate().unwrap(), "OK");
+    }
+
+    #[test]
+    // ensure Profile returns an error when there are no facts for the pattern
+    fn profile_generate_from_pattern_unknown(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.pre_generate();
+
+		assert!(profile.generate_from_pattern("##".to_string()).is_err());
+    }
+
+    #[test]
+    fn profile_generate_novel(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.pre_generate();
+
+		profile.set_novel_mode(Some(NovelMode::new(false, 10))).unwrap();
+
+		match profile.generate() {
+			Err(Error::NoNovelValue(_)) => {},
+			_ => panic!("expected no novel value"),
+		}
+
+		profile.set_novel_mode(None).unwrap();
+		assert_eq!(profile.generate().unwrap(), "OK");
+    }
+
+    #[test]
+    fn profile_novel_mode_without_entity_hashes(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.pre_generate();
+
+		// the hashes aren't archived, so the restored profile can't recognize the sample data
+		let mut restored = Profile::from_serialized(&profile.serialize()).unwrap();
+		match restored.set_novel_mode(Some(NovelMode::new(false, 10))) {
+			Err(Error::Config(_)) => {},
+			_ => panic!("expected a configuration error"),
+		}
+		assert_eq!(restored.generate().unwrap(), "OK");
+
+		// the entities analyzed after the limit was reached have no hash
+		let mut profile = Profile::new();
+		profile.set_entity_hash_limit(Some(1));
+		profile.analyze("OK");
+		profile.set_novel_mode(Some(NovelMode::new(false, 10))).unwrap();
+		profile.analyze("NO");
+		profile.pre_generate();
+		assert!(!profile.entity_hashes.is_complete());
+		assert!(!profile.serialize().contains("entity_hashes"));
+
+		match profile.generate() {
+			Err(Error::Config(_)) => {},
+			_ => panic!("expected a configuration error"),
+		}
+    }
+
+    #[test]
+    fn profile_entity_hashes_not_archived(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+
+		let restored = Profile::from_serialized(&profile.serialize()).unwrap();
+		assert!(restored.entity_hashes.is_empty());
+		assert!(!profile.serialize().contains("entity_hashes"));
+
+		profile.set_archive_entity_hashes(true);
+		let restored = Profile::from_serialized(&profile.serialize()).unwrap();
+		assert_eq!(restored.entity_hashes, profile.entity_hashes);
+		assert!(restored.entity_hashes.contains("OK"));
+    }
+
+    #[test]
+    fn profile_semantic_values_not_archived(){
+		let mut profile = Profile::new();
+		profile.analyze("john.smith@example.com");
+		assert!(!profile.serialize().contains("example.com"));
+
+		let restored = Profile::from_serialized(&profile.serialize()).unwrap();
+		assert_eq!(restored.semantic.values.get(&semantic::SemanticType::Email), None);
+
+		profile.set_archive_entity_hashes(true);
+		let restored = Profile::from_serialized(&profile.serialize()).unwrap();
+		assert_eq!(restored.semantic.values, profile.semantic.values);
+		assert_eq!(restored.semantic.values.get(&semantic::SemanticType::Email).unwrap()["example.com"], 1);
+    }
+
+    #[test]
+    fn profile_entity_hash_limit(){
+		let mut profile = Profile::new();
+		profile.set_entity_hash_limit(Some(0));
+		profile.analyze("OK");
+		assert!(profile.entity_hashes.is_empty());
+
+		let mut other = Profile::new();
+		other.analyze("OK");
+		other.analyze("NO");
+		profile.set_entity_hash_limit(Some(1));
+		profile.merge(&other);
+		assert_eq!(profile.entity_hashes.len(), 1);
+    }
+
+    #[test]
+    fn profile_generate_novel_unique(){
+		let mut profile = Profile::new_with_markov_order(0);
+		profile.analyze("13");
+		profile.analyze("21");
+		profile.set_numeric_generation(true);
+		profile.pre_generate();
+
+		// the hashes of the analyzed entities are archived
+		profile.set_archive_entity_hashes(true);
+		let mut profile = Profile::from_serialized(&profile.serialize()).unwrap();
+		profile.set_novel_mode(Some(NovelMode::new(true, 1000))).unwrap();
+
+		// the numbers are generated from the histogram, where only 14 is a novel integer
+		assert_eq!(profile.generate().unwrap(), "14");
+		assert!(profile.generate().is_err());
+
+		// the chars of the pattern are generated char by char
+		let mut generated = vec![profile.generate_from_pattern("##".to_string()).unwrap(), profile.generate_from_pattern("##".to_string()).unwrap()];
+		generated.sort();
+
+		assert_eq!(generated, vec!["11", "23"]);
+		assert!(profile.generate_from_pattern("##".to_string()).is_err());
+    }
+
+    #[test]
+    fn profile_generate_with_constraints(){
+		let mut profile = Profile::new_with_markov_order(0);
+		profile.analyze("ab1");
+		profile.analyze("cd2");
+		profile.analyze("xyz99");
+		profile.pre_generate();
+
+		let constraints = Constraints::new().with_max_length(3).with_forbidden_chars(&['a', 'c', '1']);
+		for _ in 0..20 {
+			let generated = profile.generate_with(&constraints).unwrap();
+			assert_eq!(generated.chars().count(), 3);
+			assert!(!generated.contains('a') && !generated.contains('c') && !generated.contains('1'));
+		}
+
+		let constraints = Constraints::new().with_regex("^[a-z]+$").unwrap().with_char_classes(&['c', 'v']);
+		assert!(profile.generate_with(&constraints).is_err());
+
+		let constraints = Constraints::new().with_regex("^x").unwrap().with_min_length(4);
+		assert_eq!(profile.generate_with(&constraints).unwrap().len(), 5);
+    }
+
+    #[test]
+    // ensure the constrained data is generated by the semantic, numeric and generalized generators of the Profile
+    fn profile_generate_with_constraints_typed(){
+		let mut numbers = Profile::new();
+		numbers.set_numeric_generation(true);
+		for entity in ["7", "25", "120", "999"].iter() {
+			numbers.analyze(entity);
+		}
+		numbers.pre_generate();
+		let constraints = Constraints::new().with_max_length(2).with_forbidden_values(&["7", "25"]);
+		for _ in 0..20 {
+			let number = numbers.generate_with(&constraints).unwrap().parse::<u32>().unwrap();
+			assert!((8..=99).contains(&number) && number != 25);
+		}
+
+		let mut dates = Profile::new();
+		dates.set_semantic_generation(true);
+		dates.analyze("01/04/2017");
+		dates.analyze("12/21/2018");
+		dates.pre_generate();
+		let constraints = Constraints::new().with_regex("^1[0-2]/").unwrap();
+		for _ in 0..20 {
+			let date = dates.generate_with(&constraints).unwrap();
+			assert_eq!(semantic::SemanticModel::classify(&date).map(|c| c.0), Some(semantic::SemanticType::Date));
+			assert!(date.starts_with('1'));
+		}
+
+		let mut generalized = Profile::new_with_markov_order(0);
+		generalized.set_pattern_generalization(true);
+		for entity in ["Ab1", "Abbb12", "Ab123"].iter() {
+			generalized.analyze(entity);
+		}
+		generalized.pre_generate();
+		let constraints = Constraints::new().with_min_length(6);
+		assert!(generalized.generate_with(&constraints).unwrap().len() >= 6);
+    }
+
+    #[test]
+    // ensure the semantic, numeric, token and generalized generators are restricted by the constraints, so that no value has to be regenerated
+    fn profile_generate_with_constraints_restricted(){
+		let constraints = Constraints::new().with_max_length(2).with_retries(0);
+		let mut numbers = Profile::new();
+		numbers.set_numeric_generation(true);
+		for entity in ["7", "120", "4500", "99999"].iter() {
+			numbers.analyze(entity);
+		}
+		numbers.pre_generate();
+		for _ in 0..50 {
+			let number = numbers.generate_with(&constraints).unwrap().parse::<u32>().unwrap();
+			assert!((7..=99).contains(&number));
+		}
+
+		let constraints = Constraints::new().with_max_length(12).with_forbidden_chars(&['0']).with_retries(0);
+		let mut phones = Profile::new();
+		phones.set_semantic_generation(true);
+		phones.analyze("(555) 123-4567");
+		phones.analyze("555-123-4567");
+		phones.pre_generate();
+		for _ in 0..50 {
+			let phone = phones.generate_with(&constraints).unwrap();
+			assert_eq!(phone.len(), 12);
+			assert!(!phone.contains('0'));
+		}
+
+		let constraints = Constraints::new().with_forbidden_chars(&[',']).with_max_length(8).with_retries(0);
+		let mut names = Profile::new_with_token_mode(0.5);
+		for entity in ["Smith, John", "Dale, Danny", "Madonna", "Cher"].iter() {
+			names.analyze(entity);
+		}
+		names.pre_generate();
+		for _ in 0..50 {
+			let name = names.generate_with(&constraints).unwrap();
+			assert!(!name.contains(',') && name.chars().count() <= 8);
+		}
+
+		let constraints = Constraints::new().with_max_length(4).with_char_classes(&['V', 'c']).with_retries(0);
+		let mut generalized = Profile::new_with_markov_order(0);
+		generalized.set_pattern_generalization(true);
+		for entity in ["Abbbbbb", "Abb", "123"].iter() {
+			generalized.analyze(entity);
+		}
+		generalized.pre_generate();
+		for _ in 0..50 {
+			let generated = generalized.generate_with(&constraints).unwrap();
+			assert!(["Ab", "Abb", "Abbb"].contains(&generated.as_str()));
+		}
+    }
+
+    #[test]
+    fn profile_generate_with_unsatisfiable(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.pre_generate();
+
+		match profile.generate_with(&Constraints::new().with_min_length(3)) {
+			Err(Error::Unsatisfiable(_)) => {},
+			_ => panic!("expected unsatisfiable constraints"),
+		}
+
+		match profile.generate_with(&Constraints::new().with_forbidden_values(&["OK"]).with_retries(5)) {
+			Err(Error::Unsatisfiable(_)) => {},
+			_ => panic!("expected unsatisfiable constraints"),
+		}
+    }
+
+    #[test]
+    fn profile_symbol_classes_archived(){
+    	let classes = vec![SymbolClass::from_chars("HexDigit", 'h', "0123456789abcdef", 1)];
+    	let mut profile = Profile::new_with_symbol_classes(&classes).unwrap();
+    	profile.analyze("0x1f");
+
+    	let mut restored = Profile::from_serialized(&profile.serialize()).unwrap();
+    	restored.analyze("0xa0");
+
+    	assert_eq!(restored.symbol_classes, classes);
+    	assert_eq!(restored.patterns.get("hchh"), Some(&2));
+    }
+
+    #[test]
+    fn profile_generate_token_mode(){
+    	let mut profile = Profile::new_with_token_mode(0.5);
+    	profile.set_seed(11);
+    	for entity in ["123 Main Street", "9 Elm Avenue", "4521 Oak Road"].iter() {
+    		profile.analyze(entity);
+    	}
     	profile.pre_generate();
 
-    	assert!(profile.generate().len() > 0);
+    	let generated = profile.generate_n(20).unwrap();
+    	for value in generated.iter() {
+    		let (tokens, _separators) = tokens::tokenize(value);
+
+    		assert_eq!(tokens.len(), 3);
+    		assert!(tokens[0].chars().all(|c| c.is_ascii_digit()));
+    		assert!(tokens[1].starts_with(|c: char| c.is_uppercase()));
+    	}
+
+    	// the token model is archived and the seeded data generation is reproducible
+    	let mut restored = Profile::from_serialized(&profile.serialize()).unwrap();
+    	restored.set_seed(11);
+    	profile.set_seed(11);
+    	assert_eq!(restored.generate_n(5).unwrap(), profile.generate_n(5).unwrap());
     }
 
     #[test]
-    #[should_panic(expected = "Could not open file \"./tests/samples/bad-path\"")]
-    fn new_profile_from_file_bad_path(){
-		let mut profile = Profile::from_file("./tests/samples/bad-path");
+    fn profile_reset_token_mode(){
+    	let mut profile = Profile::new_with_token_mode(1.0);
+    	profile.analyze("123 Main Street");
+    	profile.reset_analyze();
+    	profile.analyze("Smith, John");
+    	profile.pre_generate();
+
+    	let model = profile.tokens.as_ref().unwrap();
+    	assert_eq!(model.real_token_ratio, 1.0);
+    	assert_eq!(model.layouts.len(), 1);
+    	assert_eq!(profile.generate().unwrap(), "Smith, John");
+    }
+
+    #[test]
+    fn profile_generate_numeric(){
+    	let mut profile = Profile::new();
+    	profile.set_seed(3);
+    	for entity in ["0012", "0250", "0999", "0075"].iter() {
+    		profile.analyze(entity);
+    	}
+    	profile.set_numeric_generation(true);
+    	profile.pre_generate();
+
+    	let mut restored = Profile::from_serialized(&profile.serialize()).unwrap();
+    	assert!(restored.is_numeric());
+    	for value in restored.generate_n(50).unwrap().iter() {
+    		let number = value.parse::<u32>().unwrap();
+
+    		assert_eq!(value.len(), 4);
+    		assert!((12..=999).contains(&number), "{} is out of range", value);
+    	}
+    }
+
+    #[test]
+    // ensure the numbers are generated digit by digit from the patterns, unless the numeric generation is on
+    fn profile_generate_numeric_off_by_default(){
+    	let mut profile = Profile::new();
+    	profile.set_seed(3);
+    	profile.analyze("1500");
+    	profile.analyze("2500");
+    	profile.pre_generate();
+
+    	assert!(profile.is_numeric());
+    	for value in profile.generate_n(50).unwrap().iter() {
+    		assert_eq!(value.len(), 4);
+    		assert!(value.chars().all(|c| "0125".contains(c)), "{} isn't generated from the facts", value);
+    	}
+
+    	profile.set_numeric_generation(true);
+    	assert!(profile.generate_n(50).unwrap().iter().any(|v| v.chars().any(|c| !"0125".contains(c))));
+    }
+
+    #[test]
+    // ensure numbers that look like postal codes or phone numbers are generated in their range
+    fn profile_generate_numeric_postal_range(){
+    	let mut profile = Profile::new();
+    	profile.set_seed(11);
+    	for entity in (10250..=10500).step_by(10) {
+    		profile.analyze(&entity.to_string());
+    	}
+    	profile.analyze("12345.67");
+    	profile.set_numeric_generation(true);
+    	profile.pre_generate();
+
+    	assert_eq!(profile.semantic_type(), None);
+    	for value in profile.generate_n(100).unwrap().iter() {
+    		let number = value.parse::<f64>().unwrap();
+
+    		assert!((10250.0..=12345.67).contains(&number), "{} is out of range", value);
+    	}
+    }
+
+    #[test]
+    fn profile_generate_semantic_type(){
+    	let mut profile = Profile::new();
+    	profile.set_seed(7);
+    	for entity in ["02/28/2015", "12/31/2016", "07/04/2017", "n/a"].iter() {
+    		profile.analyze(entity);
+    	}
+    	profile.pre_generate();
+
+    	// the confidence is below the default threshold, so the data is generated char by char
+    	assert_eq!(profile.semantic_type(), Some((semantic::SemanticType::Date, 0.75)));
+    	assert_eq!(profile.semantic.generated_type(), None);
+
+    	profile.set_semantic_threshold(0.75);
+    	profile.set_semantic_generation(true);
+    	let mut restored = Profile::from_serialized(&profile.serialize()).unwrap();
+    	for value in restored.generate_n(50).unwrap().iter() {
+    		assert_eq!(semantic::SemanticModel::classify(value).map(|c| (c.0, c.1)), Some((semantic::SemanticType::Date, "MM/DD/YYYY".to_string())));
+    	}
+    }
+
+    #[test]
+    fn profile_generate_generalized_patterns(){
+    	let mut profile = Profile::new_with_markov_order(0);
+    	profile.set_seed(5);
+    	for entity in ["Ab1", "Abbb12", "Ab123"].iter() {
+    		profile.analyze(entity);
+    	}
     	profile.pre_generate();
 
-    	assert!(profile.generate().len() > 0);
+    	// the exact patterns are generated by default
+    	let exact = (0..50).map(|_| profile.generate().unwrap().len()).collect::<BTreeSet<usize>>();
+    	assert_eq!(exact.into_iter().collect::<Vec<usize>>(), vec![3, 5, 6]);
+
+    	profile.set_pattern_generalization(true);
+    	let generalized = (0..200).map(|_| profile.generate().unwrap()).collect::<Vec<String>>();
+    	let lengths = generalized.iter().map(|g| g.len()).collect::<BTreeSet<usize>>();
+
+    	assert_eq!(lengths.into_iter().collect::<Vec<usize>>(), vec![3, 4, 5, 6, 7]);
+    	assert!(generalized.iter().all(|g| g.starts_with('A') && g[1..].trim_start_matches('b').chars().all(|c| c.is_ascii_digit())));
+    }
+
+    #[test]
+    fn profile_generate_unicode(){
+		let mut profile = Profile::new();
+		profile.analyze("Zoë");
+		profile.analyze("Søren");
+		profile.analyze("東京");
+		profile.pre_generate();
+
+		// the multi-byte chars round-trip, and the sizes are counted in chars
+		for _ in 0..20 {
+			let generated = profile.generate().unwrap();
+			assert!(generated.chars().all(|c| "ZoëSøren東京".contains(c)));
+			assert!([3, 5, 2].contains(&generated.chars().count()));
+		}
+		assert_eq!(profile.sizes.keys().collect::<Vec<&u32>>(), vec![&2, &3, &5]);
+    }
+
+    #[test]
+    fn profile_generate_n(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.pre_generate();
+
+		assert_eq!(profile.generate_n(3).unwrap(), vec!["OK", "OK", "OK"]);
+		assert!(Profile::new().generate_n(1).is_err());
+    }
+
+    #[test]
+    fn profile_iter(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.analyze("42");
+		profile.pre_generate();
+
+		let generated = profile.iter().zip(1..=4).map(|(v, _)| v).collect::<Vec<String>>();
+		assert_eq!(generated.len(), 4);
+		assert!(generated.iter().all(|v| v == "OK" || v == "42"));
+
+		// the iterator ends when the profile can't generate data
+		assert_eq!(Profile::new().iter().count(), 0);
+    }
+
+    #[test]
+    fn profile_generate_after_analyze(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+		profile.pre_generate();
+
+		// the facts of data analyzed after pre_generate() are used as well
+		profile.analyze("42");
+
+		assert_eq!(profile.generate_from_pattern("##".to_string()).unwrap(), "42");
     }
 
 	#[test]
     fn new_profile_from_serialized(){
     	let serialized = "{\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}";
-    	let mut profile = Profile::from_serialized(&serialized);
+    	let mut profile = Profile::from_serialized(&serialized).unwrap();
     	profile.pre_generate();
 
-    	assert_eq!(profile.generate(), "OK");
+    	assert_eq!(profile.generate().unwrap(), "OK");
     }
 
     #[test]
@@ -1106,7 +2808,7 @@ mod tests {
     	profil.analyze("08/05/2017");
 
     	profil.pre_generate();
-    	let generated = profil.generate_from_pattern("##p##p####".to_string());
+    	let generated = profil.generate_from_pattern("##p##p####".to_string()).unwrap();
 
     	assert_eq!(10, generated.len());
     }
@@ -1120,11 +2822,26 @@ mod tests {
     	profil.analyze("Last");
 
     	profil.pre_generate();
-    	let generated = profil.generate_from_pattern("Cvcc".to_string());
+    	let generated = profil.generate_from_pattern("Cvcc".to_string()).unwrap();
 
     	assert_eq!(4, generated.len());
     }
 
+    #[test]
+    // ensure Profile conditions the generated chars on the chars that were generated before them
+    fn profile_generate_with_markov_chain(){
+    	let mut profil =  Profile::new_with_markov_order(1);
+    	profil.analyze("bado");
+    	profil.analyze("kine");
+
+    	profil.pre_generate();
+
+    	for _ in 0..50 {
+    		let generated = profil.generate().unwrap();
+    		assert!(generated == "bado" || generated == "kine");
+    	}
+    }
+
     #[test]
     // ensure Profile is generating correct test data
     fn profile_generate(){
@@ -1139,7 +2856,7 @@ mod tests {
 
     	profil.pre_generate();
 
-    	assert!(profil.generate().len() > 10);
+    	assert!(profil.generate().unwrap().len() > 10);
     }
 
     #[test]
@@ -1150,11 +2867,33 @@ mod tests {
     	profil.analyze("O'Brien");
 
     	profil.pre_generate();
-    	let generated = profil.generate();
+    	let generated = profil.generate().unwrap();
 
     	assert_eq!(generated, "O'Brien");
     }
 
+    #[test]
+    // ensure Profile generates the same sequence of test data when using the same seed
+    fn profile_generate_with_seed(){
+    	let mut profil =  Profile::new();
+    	profil.analyze("Smith, John");
+    	profil.analyze("O'Brian, Henny");
+    	profil.analyze("Dale, Danny");
+    	profil.analyze("Rickets, Ronney");
+    	profil.pre_generate();
+
+    	let serialized = profil.serialize();
+    	let mut first = Profile::from_serialized(&serialized).unwrap();
+    	let mut second = Profile::from_serialized(&serialized).unwrap();
+
+    	first.set_seed(42);
+    	second.set_seed(42);
+
+    	for _ in 0..20 {
+    		assert_eq!(first.generate().unwrap(), second.generate().unwrap());
+    	}
+    }
+
     #[test]
     // ensure Profile is providing the correct pattern ranks after analyzing the sample data
     fn profile_pregenerate_patterns(){
@@ -1214,6 +2953,6 @@ mod tests {
 		profil.analyze("OK");
 
     	let serialized = profil.serialize();
-    	assert_eq!(serialized, "{\"id\":null,\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[[{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0}],[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1}],[],[]]}");
+    	assert_eq!(serialized, "{\"id\":null,\"patterns\":{\"VC\":1},\"pattern_total\":1,\"pattern_keys\":[\"VC\"],\"pattern_vals\":[1],\"pattern_percentages\":[],\"pattern_ranks\":[],\"sizes\":{\"2\":1},\"size_total\":1,\"size_ranks\":[],\"processors\":4,\"facts\":[{\"key\":\"K\",\"prior_key\":\"O\",\"next_key\":null,\"pattern_placeholder\":\"C\",\"starts_with\":0,\"ends_with\":1,\"index_offset\":1,\"count\":1},{\"key\":\"O\",\"prior_key\":null,\"next_key\":\"K\",\"pattern_placeholder\":\"V\",\"starts_with\":1,\"ends_with\":0,\"index_offset\":0,\"count\":1}],\"markov\":{\"order\":2,\"transitions\":{\"CO\":{\"K\":1}}},\"symbol_classes\":[],\"tokens\":null,\"generalize_patterns\":false,\"semantic_generation\":false,\"semantic\":{\"threshold\":0.9,\"total\":1,\"counts\":{},\"formats\":{}},\"numeric_generation\":false,\"numeric\":{\"threshold\":0.9,\"total\":1,\"count\":0,\"min\":null,\"max\":null,\"negatives\":0,\"decimals\":0,\"bin_width\":0.0009765625,\"histogram\":{},\"formats\":{}}}");
     }
 }
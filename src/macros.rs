@@ -28,17 +28,34 @@ macro_rules! levenshtein_distance {
 /// This macro generates a random number between 0 and 100.
 /// Returns a f64.
 ///
+/// # Arguments
+///
+/// * `rng: &mut R` - (optional) The random number generator to draw from (e.g.: a seeded `StdRng`). The thread's generator is used if omitted.</br>
+///
 /// # Example
 ///
 /// ```rust
 /// # #[macro_use] extern crate test_data_generation; extern crate rand;
+/// # use rand::SeedableRng;
 /// # fn main() {
 /// 	let rnd: f64 = random_percentage!();
 ///     println!("Your random number is {}", rnd);
+///
+///     // reproducible random numbers using a seeded generator
+///     let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// 	let seeded: f64 = random_percentage!(rng);
+///     assert!(seeded >= 0 as f64 && seeded < 100 as f64);
 /// # }
 /// ```
 #[macro_export]
 macro_rules! random_percentage {
+    ( $rng:expr ) => {
+        {
+              use rand::Rng;
+
+              $rng.gen_range::<f64, f64, f64>(0 as f64, 100 as f64)
+        }
+    };
     ( $( $x:expr ),* ) => {
         {
     	      use rand::{thread_rng, Rng};
@@ -58,18 +75,32 @@ macro_rules! random_percentage {
 ///
 /// * `a: u32` - The lowest number of the range to use for the random number.</br>
 /// * `b: u32` - The highest number of the range to use for the random number.</br>
+/// * `rng: &mut R` - (optional) The random number generator to draw from (e.g.: a seeded `StdRng`). The thread's generator is used if omitted.</br>
 ///
 /// # Example
 ///
 /// ```rust
 /// # #[macro_use] extern crate test_data_generation; extern crate rand;
+/// # use rand::SeedableRng;
 /// # fn main() {
 /// 	let rnd: u32 = random_between!(0, 100);
 ///     println!("Your random number is {}", rnd);
+///
+///     // reproducible random numbers using a seeded generator
+///     let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// 	let seeded: u32 = random_between!(0, 100, rng);
+///     assert!(seeded < 100);
 /// # }
 /// ```
 #[macro_export]
 macro_rules! random_between {
+    ($a:expr, $b:expr, $rng:expr) => {
+        {
+            use rand::Rng;
+
+            $rng.gen_range::<u32, u32, u32>($a as u32, $b as u32)
+        }
+    };
     ($a:expr, $b:expr) => {
         {
     	    use rand::{thread_rng, Rng};
@@ -0,0 +1,802 @@
+//! The `numeric` module provides the modeling of numeric data (e.g.: amounts, ages and quantities).
+//!
+//! While a Profile analyzes the entities, the `NumericModel` parses the numbers and learns their range (min/max), sign, precision,
+//! a histogram of their values and their formats (e.g.: `#,##0.00` or `000`).
+//! If most of the entities are numbers (see _NumericModel::is_numeric()_) and the numeric generation of the Profile is on (see _Profile::set_numeric_generation()_),
+//! the Profile generates numbers from the histogram in one of the observed formats,
+//! instead of generating them digit by digit, so the generated numbers keep the range and distribution of the sample data.
+//! Numbers whose integer part is beyond MAX_EXACT_INTEGER (2^53) can't be represented exactly by a f64, so they aren't parsed as numbers
+//! and are generated from the patterns instead (e.g.: 20 digit identifiers keep all their digits).
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::Profile;
+//!
+//! fn main() {
+//!		let mut profile = Profile::new();
+//!		profile.analyze("1,250.00");
+//!		profile.analyze("3,499.95");
+//!		profile.analyze("12.50");
+//!		profile.set_numeric_generation(true);
+//!		profile.pre_generate();
+//!
+//!		assert!(profile.is_numeric());
+//!		println!("The generated amount is {}", profile.generate().unwrap());
+//! }
+//! ```
+
+use rand::Rng;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::shared::AliasTable;
+use crate::Error;
+
+/// The default minimum fraction of the entities that must be numbers to generate numbers
+pub const DEFAULT_NUMERIC_THRESHOLD: f64 = 0.9;
+
+/// The highest number of bins of the histogram, (the width of the bins is doubled until the range of the numbers fits in these bins)
+pub const MAX_HISTOGRAM_BINS: usize = 32;
+
+/// The highest index of a bin of the histogram, so that the index of a bin is exact in a f64 and never overflows
+const MAX_BIN_INDEX: f64 = 4_503_599_627_370_496.0;
+
+/// The highest integer part of a number that is parsed as a number (2^53), since the integers beyond it aren't exact in a f64
+pub const MAX_EXACT_INTEGER: u64 = 9_007_199_254_740_992;
+
+/// The highest number of digits of the integer part of a f64
+const MAX_DIGITS: usize = 309;
+
+/// The width of the bins of the histogram of a new NumericModel
+const INITIAL_BIN_WIDTH: f64 = 1.0 / 1024.0;
+
+lazy_static! {
+	static ref NUMBER: Regex = Regex::new(r"^([+-])?(\d{1,3}(?:,\d{3})+|\d+)(?:\.(\d+))?$").unwrap();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Represents the format of a number
+pub struct NumberFormat {
+	/// If true, positive numbers have a `+` sign
+	pub plus: bool,
+	/// If true, the thousands of the integer part are separated by `,`
+	pub grouping: bool,
+	/// The number of digits that the integer part is padded to with leading zeros, (e.g.: 3 for "007")
+	pub width: usize,
+	/// The number of decimal places
+	pub decimals: usize,
+}
+
+impl NumberFormat {
+	/// This function parses a format that is written as a number pattern, (e.g.: `+#,##0.00` or `000`)
+	///
+	/// # Arguments
+	///
+	/// * `pattern: &str` - The number pattern.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumberFormat;
+	///
+	/// fn main() {
+	///		let format = NumberFormat::from_pattern("#,##0.00");
+	///
+	///		assert_eq!(format, NumberFormat { plus: false, grouping: true, width: 1, decimals: 2 });
+	///		assert_eq!(format.to_string(), "#,##0.00");
+	/// }
+	/// ```
+	pub fn from_pattern(pattern: &str) -> NumberFormat {
+		let mut parts = pattern.splitn(2, '.');
+		let integer = parts.next().unwrap_or_default();
+		let grouping = integer.contains(',');
+
+		NumberFormat {
+			plus: integer.starts_with('+'),
+			grouping,
+			width: if grouping { 1 } else { integer.matches('0').count().max(1) },
+			decimals: parts.next().map(|d| d.len()).unwrap_or(0),
+		}
+	}
+
+	/// This function formats a number, which is rounded to the decimal places of the format
+	///
+	/// # Arguments
+	///
+	/// * `value: f64` - The number to format.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumberFormat;
+	///
+	/// fn main() {
+	///		assert_eq!(NumberFormat::from_pattern("#,##0.00").format(-1234567.891), "-1,234,567.89");
+	///		assert_eq!(NumberFormat::from_pattern("+000").format(7.0), "+007");
+	/// }
+	/// ```
+	pub fn format(&self, value: f64) -> String {
+		let digits = format!("{:.*}", self.decimals, value.abs());
+		let mut parts = digits.splitn(2, '.');
+		let mut integer = parts.next().unwrap_or_default().to_string();
+		let fraction = parts.next();
+
+		if integer.len() < self.width {
+			integer = format!("{}{}", "0".repeat(self.width - integer.len()), integer);
+		}
+
+		if self.grouping {
+			let len = integer.len();
+			integer = integer.chars().enumerate().fold(String::new(), |mut grouped, (idx, c)| {
+				if idx > 0 && (len - idx) % 3 == 0 {
+					grouped.push(',');
+				}
+				grouped.push(c);
+				grouped
+			});
+		}
+
+		// a number that is rounded to zero has no sign
+		let zero = digits.chars().all(|c| c == '0' || c == '.');
+		let sign = match (value < 0.0 && !zero, self.plus) {
+			(true, _) => "-",
+			(false, true) => "+",
+			(false, false) => "",
+		};
+
+		match fraction {
+			Some(f) => format!("{}{}.{}", sign, integer, f),
+			None => format!("{}{}", sign, integer),
+		}
+	}
+
+	/// This function returns the length (number of chars) of a formatted number, whose integer part has the number of digits
+	///
+	/// # Arguments
+	///
+	/// * `digits: usize` - The number of digits of the integer part (without the leading zeros of the format).</br>
+	/// * `negative: bool` - If true, the number is negative.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumberFormat;
+	///
+	/// fn main() {
+	///		assert_eq!(NumberFormat::from_pattern("#,##0.00").length(7, true), "-1,234,567.89".len());
+	///		assert_eq!(NumberFormat::from_pattern("+000").length(1, false), "+007".len());
+	/// }
+	/// ```
+	pub fn length(&self, digits: usize, negative: bool) -> usize {
+		let sign = if negative || self.plus { 1 } else { 0 };
+		let integer = digits.max(self.width).max(1);
+		let separators = if self.grouping { (integer - 1) / 3 } else { 0 };
+		let fraction = if self.decimals > 0 { self.decimals + 1 } else { 0 };
+
+		sign + integer + separators + fraction
+	}
+
+	/// This function returns the lowest and highest absolute value of the numbers whose formatted length is in the range of lengths
+	/// (None if no number has such a length)
+	///
+	/// # Arguments
+	///
+	/// * `negative: bool` - If true, the numbers are negative.</br>
+	/// * `lengths: &RangeInclusive<usize>` - The range of the lengths (number of chars) of the formatted numbers.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumberFormat;
+	///
+	/// fn main() {
+	///		assert_eq!(NumberFormat::from_pattern("0.0").magnitudes(false, &(0..=4)), Some((0.0, 99.9)));
+	///		assert_eq!(NumberFormat::from_pattern("0.0").magnitudes(true, &(5..=5)), Some((10.0, 99.9)));
+	///		assert_eq!(NumberFormat::from_pattern("0.0").magnitudes(false, &(0..=2)), None);
+	/// }
+	/// ```
+	pub fn magnitudes(&self, negative: bool, lengths: &RangeInclusive<usize>) -> Option<(f64, f64)> {
+		let step = 10f64.powi(-(self.decimals as i32));
+		let digits = (1..=MAX_DIGITS).filter(|d| lengths.contains(&self.length(*d, negative))).collect::<Vec<usize>>();
+		let (first, last) = (*digits.first()?, *digits.last()?);
+
+		// the length grows with the number of digits, so the numbers of digits that fit the lengths are consecutive
+		let low = if first == 1 { 0.0 } else { 10f64.powi(first as i32 - 1) };
+		let high = if last == MAX_DIGITS { f64::MAX } else { 10f64.powi(last as i32) - step };
+
+		Some((low, high))
+	}
+
+	/// This function returns the regular expression of the numbers in the format, (see _format()_)
+	///
+	/// # Arguments
+	///
+	/// * `negative: bool` - If true, the numbers can be negative.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumberFormat;
+	///
+	/// fn main() {
+	///		assert_eq!(NumberFormat::from_pattern("#,##0.00").to_regex(true), "-?[0-9]{1,3}(?:,[0-9]{3})*\\.[0-9]{2}");
+	///		assert_eq!(NumberFormat::from_pattern("+000").to_regex(false), "\\+[0-9]{3,}");
+	/// }
+	/// ```
+	pub fn to_regex(&self, negative: bool) -> String {
+		let sign = match (negative, self.plus) {
+			(true, true) => "[-+]",
+			(true, false) => "-?",
+			(false, true) => "\\+",
+			(false, false) => "",
+		};
+		let integer = match (self.grouping, self.width) {
+			(true, _) => "[0-9]{1,3}(?:,[0-9]{3})*".to_string(),
+			(false, 0) | (false, 1) => "[0-9]+".to_string(),
+			(false, width) => format!("[0-9]{{{},}}", width),
+		};
+
+		match self.decimals {
+			0 => format!("{}{}", sign, integer),
+			d => format!("{}{}\\.[0-9]{{{}}}", sign, integer, d),
+		}
+	}
+}
+
+impl fmt::Display for NumberFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let sign = if self.plus { "+" } else { "" };
+		let integer = if self.grouping { "#,##0".to_string() } else { "0".repeat(self.width.max(1)) };
+
+		match self.decimals {
+			0 => write!(f, "{}{}", sign, integer),
+			d => write!(f, "{}{}.{}", sign, integer, "0".repeat(d)),
+		}
+	}
+}
+
+/// This function returns the default minimum fraction of the numbers
+fn default_threshold() -> f64 {
+	DEFAULT_NUMERIC_THRESHOLD
+}
+
+/// This function returns the width of the bins of the histogram of a new NumericModel
+fn default_bin_width() -> f64 {
+	INITIAL_BIN_WIDTH
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents the numbers that a Profile has analyzed
+pub struct NumericModel {
+	/// The minimum fraction of the entities that must be numbers to generate numbers (a threshold above 1.0 turns the numeric generation off)
+	#[serde(default = "default_threshold")]
+	pub threshold: f64,
+	/// The number of entities that have been analyzed
+	pub total: u32,
+	/// The number of entities that are numbers
+	pub count: u32,
+	/// The lowest number
+	pub min: Option<f64>,
+	/// The highest number
+	pub max: Option<f64>,
+	/// The number of negative numbers
+	pub negatives: u32,
+	/// The highest number of decimal places (precision)
+	pub decimals: usize,
+	/// The width of the bins of the histogram
+	#[serde(default = "default_bin_width")]
+	pub bin_width: f64,
+	/// The histogram of the numbers, (i.e.: the count of the numbers in each bin, where the bin `k` is [k * bin_width, (k + 1) * bin_width))
+	pub histogram: BTreeMap<i64, u32>,
+	/// The formats of the numbers with their count of occurrence, (e.g.: `#,##0.00` or `000`)
+	pub formats: BTreeMap<String, u32>,
+}
+
+impl Default for NumericModel {
+	fn default() -> Self {
+		NumericModel::new()
+	}
+}
+
+impl NumericModel {
+	/// Constructs a new NumericModel
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumericModel;
+	///
+	/// fn main() {
+	///		let model = NumericModel::new();
+	///
+	///		assert!(!model.is_numeric());
+	/// }
+	/// ```
+	pub fn new() -> NumericModel {
+		NumericModel {
+			threshold: DEFAULT_NUMERIC_THRESHOLD,
+			total: 0,
+			count: 0,
+			min: None,
+			max: None,
+			negatives: 0,
+			decimals: 0,
+			bin_width: INITIAL_BIN_WIDTH,
+			histogram: BTreeMap::new(),
+			formats: BTreeMap::new(),
+		}
+	}
+
+	/// This function parses an entity, and returns the number with its format (None if the entity isn't a number).
+	/// The thousands separator is `,` and the decimal separator is `.`.
+	/// NOTE: An entity whose integer part is beyond MAX_EXACT_INTEGER isn't a number, since its digits would be lost in a f64.
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The textual str of the value to parse.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumericModel;
+	///
+	/// fn main() {
+	///		let (value, format) = NumericModel::parse("-1,234.50").unwrap();
+	///
+	///		assert_eq!(value, -1234.5);
+	///		assert_eq!(format.to_string(), "#,##0.00");
+	///		assert_eq!(NumericModel::parse("0042").unwrap().1.to_string(), "0000");
+	///		assert!(NumericModel::parse("12a").is_none());
+	///		assert!(NumericModel::parse("12345678901234567890").is_none());
+	/// }
+	/// ```
+	pub fn parse(entity: &str) -> Option<(f64, NumberFormat)> {
+		let c = NUMBER.captures(entity)?;
+		let integer = c.get(2)?.as_str();
+		let fraction = c.get(3).map(|f| f.as_str()).unwrap_or("");
+
+		// the integer part must be exact in a f64, (a too long integer part doesn't even parse as a u64)
+		match integer.replace(',', "").parse::<u64>() {
+			Ok(i) if i <= MAX_EXACT_INTEGER => {},
+			_ => return None,
+		}
+
+		let value = format!("{}{}.{}", c.get(1).map(|s| s.as_str()).unwrap_or(""), integer.replace(',', ""), if fraction.is_empty() { "0" } else { fraction }).parse::<f64>().ok().filter(|v| v.is_finite())?;
+
+		let format = NumberFormat {
+			plus: c.get(1).map(|s| s.as_str() == "+").unwrap_or(false),
+			grouping: integer.contains(','),
+			width: if integer.len() > 1 && integer.starts_with('0') { integer.len() } else { 1 },
+			decimals: fraction.len(),
+		};
+
+		Some((value, format))
+	}
+
+	/// This function parses an entity and, if it is a number, learns its value and format
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The textual str of the value to analyze.</br>
+	pub fn analyze(&mut self, entity: &str) {
+		self.total += 1;
+
+		let (value, format) = match NumericModel::parse(entity) {
+			Some(parsed) => parsed,
+			None => return,
+		};
+
+		self.count += 1;
+		self.min = Some(self.min.map_or(value, |m| m.min(value)));
+		self.max = Some(self.max.map_or(value, |m| m.max(value)));
+		self.decimals = self.decimals.max(format.decimals);
+		if value < 0.0 {
+			self.negatives += 1;
+		}
+		*self.formats.entry(format.to_string()).or_insert(0) += 1;
+
+		// the bins are widened for the new range before the number is added, so that its bin is within MAX_BIN_INDEX
+		self.limit_bins();
+		*self.histogram.entry(self.bin_of(value)).or_insert(0) += 1;
+		self.limit_bins();
+	}
+
+	/// This function returns the bin of the histogram of a number
+	fn bin_of(&self, value: f64) -> i64 {
+		(value / self.bin_width).floor() as i64
+	}
+
+	/// This function returns the lowest width of the bins for the analyzed numbers, so that the range of the numbers fits in MAX_HISTOGRAM_BINS bins,
+	/// a bin spans at least one step of the precision, (e.g.: 1 for integers) and the index of a bin stays within MAX_BIN_INDEX
+	fn range_bin_width(&self) -> f64 {
+		let (min, max) = match (self.min, self.max) {
+			(Some(min), Some(max)) => (min, max),
+			_ => return self.bin_width,
+		};
+
+		((max - min) / MAX_HISTOGRAM_BINS as f64)
+			.max(10f64.powi(-(self.decimals as i32)))
+			.max(min.abs().max(max.abs()) / MAX_BIN_INDEX)
+	}
+
+	/// This function doubles the width of the bins until the width is at least the width, (the bins are combined in pairs)
+	fn widen_bins(&mut self, bin_width: f64) {
+		while self.bin_width < bin_width {
+			let mut histogram = BTreeMap::new();
+			for (bin, count) in self.histogram.iter() {
+				*histogram.entry(bin.div_euclid(2)).or_insert(0) += *count;
+			}
+
+			self.histogram = histogram;
+			self.bin_width *= 2.0;
+		}
+	}
+
+	/// This function doubles the width of the bins until they fit the range of the analyzed numbers and there are at most MAX_HISTOGRAM_BINS bins
+	fn limit_bins(&mut self) {
+		let bin_width = self.range_bin_width();
+		self.widen_bins(bin_width);
+
+		while self.histogram.len() > MAX_HISTOGRAM_BINS {
+			let bin_width = self.bin_width * 2.0;
+			self.widen_bins(bin_width);
+		}
+	}
+
+	/// This function merges (unions) the numbers of another NumericModel into this NumericModel
+	///
+	/// # Arguments
+	///
+	/// * `other: &NumericModel` - The NumericModel to merge into this NumericModel.</br>
+	pub fn merge(&mut self, other: &NumericModel) {
+		self.total += other.total;
+		self.count += other.count;
+		self.negatives += other.negatives;
+		self.decimals = self.decimals.max(other.decimals);
+		self.min = match (self.min, other.min) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		};
+		self.max = match (self.max, other.max) {
+			(Some(a), Some(b)) => Some(a.max(b)),
+			(a, b) => a.or(b),
+		};
+
+		for (format, count) in other.formats.iter() {
+			*self.formats.entry(format.clone()).or_insert(0) += *count;
+		}
+
+		// both histograms are brought to the wider bins before they are added
+		let mut histogram = other.clone();
+		histogram.widen_bins(self.bin_width);
+		self.widen_bins(histogram.bin_width);
+		for (bin, count) in histogram.histogram.iter() {
+			*self.histogram.entry(*bin).or_insert(0) += *count;
+		}
+		self.limit_bins();
+	}
+
+	/// This function returns the fraction of the analyzed entities that are numbers
+	pub fn confidence(&self) -> f64 {
+		if self.total == 0 {
+			return 0.0;
+		}
+
+		self.count as f64 / self.total as f64
+	}
+
+	/// This function returns true if the fraction of the analyzed entities that are numbers is at least the threshold, so that numbers are generated
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::numeric::NumericModel;
+	///
+	/// fn main() {
+	///		let mut model = NumericModel::new();
+	///		model.analyze("42");
+	///		model.analyze("7");
+	///
+	///		assert!(model.is_numeric());
+	///
+	///		model.analyze("unknown");
+	///
+	///		assert!(!model.is_numeric());
+	/// }
+	/// ```
+	pub fn is_numeric(&self) -> bool {
+		self.count > 0 && self.confidence() >= self.threshold
+	}
+
+	/// This function returns the regular expressions of the formats of the analyzed numbers in decreasing order of occurrence,
+	/// which match the numbers that are generated (see _generate()_)
+	pub fn regex_alternatives(&self) -> Vec<String> {
+		let negative = self.min.map(|m| m < 0.0).unwrap_or(false);
+		let mut formats = self.formats.iter().collect::<Vec<(&String, &u32)>>();
+		formats.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+		formats.iter().map(|(f, _c)| NumberFormat::from_pattern(f).to_regex(negative)).collect()
+	}
+
+	/// This function generates a number from the histogram, within the range of the analyzed numbers,
+	/// in one of the analyzed formats selected by its chance of occurring
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use rand::thread_rng;
+	/// use test_data_generation::numeric::NumericModel;
+	///
+	/// fn main() {
+	///		let mut model = NumericModel::new();
+	///		model.analyze("007");
+	///		model.analyze("025");
+	///
+	///		let generated = model.generate(&mut thread_rng()).unwrap();
+	///		let value = generated.parse::<u32>().unwrap();
+	///
+	///		assert_eq!(generated.len(), 3);
+	///		assert!(value >= 7 && value <= 25);
+	/// }
+	/// ```
+	pub fn generate<R: Rng>(&self, rng: &mut R) -> Result<String, Error> {
+		let (min, max) = match (self.min, self.max) {
+			(Some(min), Some(max)) => (min, max),
+			_ => return Err(Error::EmptyProfile("There are no numbers to generate numbers from".to_string())),
+		};
+
+		let format = AliasTable::new(self.formats.iter().map(|(f, c)| (f.clone(), *c as u64)).collect()).sample(rng).map(|f| NumberFormat::from_pattern(f)).unwrap();
+		let bin = *AliasTable::new(self.histogram.iter().map(|(b, c)| (*b, *c as u64)).collect()).sample(rng).unwrap();
+
+		// select a number in the bin, within the range of the analyzed numbers
+		let low = (bin as f64 * self.bin_width).max(min);
+		let high = ((bin as f64 + 1.0) * self.bin_width).min(max);
+		let mut value = if high > low { rng.gen_range(low, high) } else { low };
+
+		// round the number to the decimal places of the format, so that the formatted number stays in the range
+		let step = 10f64.powi(-(format.decimals as i32));
+		let (lowest, highest) = ((min / step).ceil() * step, (max / step).floor() * step);
+		value = (value / step).round() * step;
+		if lowest <= highest {
+			value = value.clamp(lowest, highest);
+		}
+
+		Ok(format.format(value))
+	}
+
+	/// This function generates a number, like _generate()_, but only in the formats whose signs and separators satisfy the filter,
+	/// and with the number of digits limited, so that the length of the formatted number is in the range of lengths.
+	/// NOTE: The digits aren't filtered, so the generated number still has to be checked against the filter.
+	///
+	/// # Arguments
+	///
+	/// * `lengths: &RangeInclusive<usize>` - The range of the lengths (number of chars) of the formatted number.</br>
+	/// * `filter: &dyn Fn(&char) -> bool` - The filter of the chars of the formatted number.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// #Errors
+	/// If no number in the range of the analyzed numbers can be formatted within the range of lengths, an Error::Unsatisfiable is returned.
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use rand::thread_rng;
+	/// use test_data_generation::numeric::NumericModel;
+	///
+	/// fn main() {
+	///		let mut model = NumericModel::new();
+	///		model.analyze("7");
+	///		model.analyze("2500");
+	///
+	///		let generated = model.generate_within(&(0..=2), &|_c: &char| true, &mut thread_rng()).unwrap();
+	///		let value = generated.parse::<u32>().unwrap();
+	///
+	///		assert!(value >= 7 && value <= 99);
+	/// }
+	/// ```
+	pub fn generate_within<R: Rng>(&self, lengths: &RangeInclusive<usize>, filter: &dyn Fn(&char) -> bool, rng: &mut R) -> Result<String, Error> {
+		let (min, max) = match (self.min, self.max) {
+			(Some(min), Some(max)) => (min, max),
+			_ => return Err(Error::EmptyProfile("There are no numbers to generate numbers from".to_string())),
+		};
+
+		// the formats with the intervals of the numbers (rounded to the decimal places) that are in the range of the analyzed numbers
+		// and whose formatted length is in the range of lengths
+		let mut candidates = Vec::new();
+		for (pattern, count) in self.formats.iter() {
+			let format = NumberFormat::from_pattern(pattern);
+			if (format.plus && !filter(&'+')) || (format.grouping && !filter(&',')) || (format.decimals > 0 && !filter(&'.')) {
+				continue;
+			}
+
+			let step = 10f64.powi(-(format.decimals as i32));
+			let mut intervals = Vec::new();
+			if let Some((low, high)) = format.magnitudes(false, lengths) {
+				intervals.push((low.max(min), high.min(max)));
+			}
+			if filter(&'-') {
+				if let Some((low, high)) = format.magnitudes(true, lengths) {
+					intervals.push(((-high).max(min), (-low.max(step)).min(max)));
+				}
+			}
+
+			let intervals = intervals.into_iter()
+				.map(|(low, high)| ((low / step).ceil() * step, (high / step).floor() * step))
+				.filter(|(low, high)| low <= high)
+				.collect::<Vec<(f64, f64)>>();
+			if !intervals.is_empty() {
+				candidates.push((format, intervals, *count as u64));
+			}
+		}
+
+		let (format, intervals) = match AliasTable::new(candidates.iter().enumerate().map(|(idx, c)| (idx, c.2)).collect()).sample(rng) {
+			Some(idx) => (candidates[*idx].0, &candidates[*idx].1),
+			None => return Err(Error::Unsatisfiable(format!("None of the analyzed numbers can be formatted with a length in the range {:?}", lengths))),
+		};
+
+		// select a bin of the histogram that overlaps an interval, (or any of the intervals if the analyzed numbers aren't in the intervals)
+		let overlap = |bin: i64| {
+			let (low, high) = (bin as f64 * self.bin_width, (bin as f64 + 1.0) * self.bin_width);
+			intervals.iter().find(|(l, h)| *l < high && *h >= low).map(|(l, h)| (l.max(low), h.min(high)))
+		};
+		let bins = AliasTable::new(self.histogram.iter().filter(|(b, _c)| overlap(**b).is_some()).map(|(b, c)| (*b, *c as u64)).collect());
+		let (low, high) = match bins.sample(rng) {
+			Some(bin) => overlap(*bin).unwrap(),
+			None => intervals[rng.gen_range(0, intervals.len())],
+		};
+		let interval = intervals.iter().find(|(l, h)| *l <= high && *h >= low).cloned().unwrap_or((low, high));
+
+		// round the number to the decimal places of the format and keep it in the interval
+		let step = 10f64.powi(-(format.decimals as i32));
+		let value = if high > low { rng.gen_range(low, high) } else { low };
+		let value = ((value / step).round() * step).clamp(interval.0, interval.1);
+
+		Ok(format.format(value))
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn parse_formats(){
+		assert_eq!(NumericModel::parse("+12").unwrap(), (12.0, NumberFormat { plus: true, grouping: false, width: 1, decimals: 0 }));
+		assert_eq!(NumericModel::parse("0.5").unwrap().1.to_string(), "0.0");
+		assert_eq!(NumericModel::parse("12,345,678").unwrap().0, 12345678.0);
+		assert!(NumericModel::parse("1,23").is_none());
+		assert!(NumericModel::parse("-").is_none());
+		assert!(NumericModel::parse("").is_none());
+	}
+
+	#[test]
+	fn format_numbers(){
+		assert_eq!(NumberFormat::from_pattern("0").format(-0.2), "0");
+		assert_eq!(NumberFormat::from_pattern("#,##0").format(999.0), "999");
+		assert_eq!(NumberFormat::from_pattern("#,##0").format(1000.0), "1,000");
+		assert_eq!(NumberFormat::from_pattern("00000.0").format(12.34), "00012.3");
+	}
+
+	#[test]
+	fn histogram_bins(){
+		let mut model = NumericModel::new();
+		for value in 0..1000 {
+			model.analyze(&value.to_string());
+		}
+
+		assert!(model.histogram.len() <= MAX_HISTOGRAM_BINS);
+		assert_eq!(model.histogram.values().sum::<u32>(), 1000);
+		assert_eq!((model.min, model.max), (Some(0.0), Some(999.0)));
+		assert_eq!(model.decimals, 0);
+	}
+
+	#[test]
+	fn generate_in_range(){
+		let mut model = NumericModel::new();
+		for value in ["-12.50", "-3.25", "0.10", "1,024.75", "99.99"].iter() {
+			model.analyze(value);
+		}
+		assert_eq!(model.negatives, 2);
+
+		let mut rng = StdRng::seed_from_u64(19);
+		for _ in 0..200 {
+			let generated = model.generate(&mut rng).unwrap();
+			let (value, format) = NumericModel::parse(&generated).unwrap();
+
+			assert!((-12.5..=1024.75).contains(&value), "{} is out of range", generated);
+			assert_eq!(format.decimals, 2);
+		}
+	}
+
+	#[test]
+	fn histogram_bin_width(){
+		let mut model = NumericModel::new();
+		for value in ["10", "20", "30", "40", "50"].iter() {
+			model.analyze(value);
+		}
+
+		// the bins span the range of the numbers instead of the single numbers
+		assert_eq!(model.bin_width, 2.0);
+		assert_eq!(model.histogram.len(), 5);
+
+		let mut rng = StdRng::seed_from_u64(19);
+		let generated = (0..100).map(|_| model.generate(&mut rng).unwrap()).collect::<Vec<String>>();
+		assert!(generated.iter().any(|g| !["10", "20", "30", "40", "50"].contains(&g.as_str())));
+	}
+
+	#[test]
+	fn generate_large_numbers(){
+		// the integers beyond 2^53 would lose digits in a f64, so they aren't numbers
+		assert_eq!(NumericModel::parse("9007199254740992").unwrap().0, 9007199254740992.0);
+		assert!(NumericModel::parse("9007199254740993").is_none());
+		assert!(NumericModel::parse("-12345678901234567890").is_none());
+		assert!(NumericModel::parse("12345678901234567890.5").is_none());
+		assert!(NumericModel::parse(&"9".repeat(400)).is_none());
+
+		let mut model = NumericModel::new();
+		model.analyze("12345678901234567890");
+		assert_eq!((model.total, model.count), (1, 0));
+
+		model.analyze("9007199254740992");
+		model.analyze("-9007199254740992");
+		let mut rng = StdRng::seed_from_u64(19);
+		for _ in 0..100 {
+			let value = NumericModel::parse(&model.generate(&mut rng).unwrap()).unwrap().0;
+			assert!(value.abs() <= 9007199254740992.0);
+		}
+	}
+
+	#[test]
+	fn merge_models(){
+		let mut model = NumericModel::new();
+		model.analyze("1");
+		model.analyze("2");
+		let mut other = NumericModel::new();
+		for value in 0..100 {
+			other.analyze(&(value * 1000).to_string());
+		}
+		other.analyze("n/a");
+
+		model.merge(&other);
+
+		assert_eq!((model.total, model.count), (103, 102));
+		assert_eq!(model.bin_width, other.bin_width);
+		assert_eq!(model.histogram.values().sum::<u32>(), 102);
+		assert_eq!(model.max, Some(99000.0));
+		assert!(model.is_numeric());
+	}
+}
@@ -0,0 +1,961 @@
+//! The `semantic` module provides the detection of the semantic type of the sample data (e.g.: email addresses, dates, UUIDs),
+//! and the type-aware generation of valid values in the formats of the sample data.
+//!
+//! While a Profile analyzes the entities, the `SemanticModel` classifies each entity and learns its format (e.g.: `(###) ###-####` or `MM/DD/YYYY`).
+//! The semantic type of the Profile is the type of most of the entities, and the confidence is the fraction of the entities that have the type.
+//! If the semantic generation of the Profile is on (see _Profile::set_semantic_generation()_) and the confidence is at least the threshold of the model,
+//! the Profile generates valid values of the type, instead of generating them char by char.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::Profile;
+//! use test_data_generation::semantic::SemanticType;
+//!
+//! fn main() {
+//!		let mut profile = Profile::new();
+//!		profile.analyze("2017-01-04");
+//!		profile.analyze("2016-12-31");
+//!		profile.analyze("2015-02-28");
+//!		profile.set_semantic_generation(true);
+//!		profile.pre_generate();
+//!
+//!		assert_eq!(profile.semantic_type(), Some((SemanticType::Date, 1.0)));
+//!		println!("The generated date is {}", profile.generate().unwrap());
+//! }
+//! ```
+
+use rand::Rng;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::RangeInclusive;
+
+use crate::numeric::NumericModel;
+use crate::shared::AliasTable;
+use crate::Error;
+
+/// The default minimum confidence of the semantic type that is required to generate values of the type
+pub const DEFAULT_SEMANTIC_THRESHOLD: f64 = 0.9;
+/// The maximum number of distinct formats and values that are kept for each semantic type, so that the model stays small when the entities are streamed
+/// (the counts of the kept formats and values are still updated)
+pub const MAX_SEMANTIC_VALUES: usize = 1024;
+
+lazy_static! {
+	static ref EMAIL: Regex = Regex::new(r"^([A-Za-z0-9._%+-]+)@([A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,})$").unwrap();
+	static ref URL: Regex = Regex::new(r"^((?:https?|ftp)://[A-Za-z0-9.-]+(?::\d+)?)((?:[/?#]\S*)?)$").unwrap();
+	static ref UUID: Regex = Regex::new(r"^\{?[0-9a-fA-F]{8}(-?)[0-9a-fA-F]{4}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{12}\}?$").unwrap();
+	static ref DATE_YMD: Regex = Regex::new(r"^(\d{4})([-/.])(\d{1,2})([-/.])(\d{1,2})$").unwrap();
+	static ref DATE_DMY: Regex = Regex::new(r"^(\d{1,2})([-/.])(\d{1,2})([-/.])(\d{4})$").unwrap();
+	static ref PHONE: Regex = Regex::new(r"^(\+\d{1,3}[ .-]?)?(\(\d{1,4}\)[ .-]?)?\d{2,4}([ .-]?\d{2,4}){1,4}$").unwrap();
+	static ref POSTAL_CODE: Regex = Regex::new(r"^(\d{5}(-\d{4})?|[A-Za-z]\d[A-Za-z] ?\d[A-Za-z]\d|[A-Za-z]{1,2}\d[A-Za-z\d]? ?\d[A-Za-z]{2}|\d{4} ?[A-Za-z]{2})$").unwrap();
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents the semantic types that can be detected
+pub enum SemanticType {
+	/// An email address, (e.g.: john.smith@example.com)
+	Email,
+	/// A phone number with formatting chars, (e.g.: (555) 123-4567 or +1 555.123.4567)
+	Phone,
+	/// A calendar date with a 4 digit year, (e.g.: 2017-01-04, 01/04/2017 or 4.1.2017)
+	Date,
+	/// A UUID, (e.g.: 123e4567-e89b-42d3-a456-426614174000)
+	Uuid,
+	/// An IPv4 address, (e.g.: 192.168.0.1)
+	Ipv4,
+	/// An IPv6 address, (e.g.: 2001:db8::ff00:42:8329)
+	Ipv6,
+	/// A http, https or ftp URL, (e.g.: https://example.com/path)
+	Url,
+	/// A postal code of the US, Canada, the UK or the Netherlands, (e.g.: 12345-6789 or K1A 0B1)
+	PostalCode,
+}
+
+impl fmt::Display for SemanticType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+/// This function returns true if the day exists in the month of the year
+fn is_valid_date(year: u32, month: u32, day: u32) -> bool {
+	(1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)
+}
+
+/// This function returns the number of days of the month of the year
+// u32::is_multiple_of() isn't available on the older toolchains that the crate supports
+#[allow(clippy::manual_is_multiple_of)]
+fn days_in_month(year: u32, month: u32) -> u32 {
+	match month {
+		2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// This function returns the format token of a number of the date, (e.g.: `MM` for "01" and `M` for "1")
+fn date_token(number: &str, token: &str) -> String {
+	if number.len() == 2 { token.repeat(2) } else { token.to_string() }
+}
+
+/// This function returns the format and year of a date, (e.g.: ("MM/DD/YYYY", 2017) for "01/04/2017")
+fn date_format(entity: &str) -> Option<(String, u32)> {
+	if let Some(c) = DATE_YMD.captures(entity) {
+		let (year, month, day) = (c[1].parse().ok()?, c[3].parse().ok()?, c[5].parse().ok()?);
+		if c[2] == c[4] && is_valid_date(year, month, day) {
+			return Some((format!("YYYY{}{}{}{}", &c[2], date_token(&c[3], "M"), &c[4], date_token(&c[5], "D")), year));
+		}
+	}
+
+	if let Some(c) = DATE_DMY.captures(entity) {
+		let (first, second, year): (u32, u32, u32) = (c[1].parse().ok()?, c[3].parse().ok()?, c[5].parse().ok()?);
+		if c[2] != c[4] {
+			return None;
+		}
+
+		// the day is first if it can't be a month, or if the separator is a dot (e.g.: 4.1.2017), otherwise the month is first (e.g.: 01/04/2017)
+		let day_first = first > 12 || (second <= 12 && &c[2] == ".");
+		let (day, month) = if day_first { (first, second) } else { (second, first) };
+
+		if is_valid_date(year, month, day) {
+			let (first_token, second_token) = if day_first { ("D", "M") } else { ("M", "D") };
+			return Some((format!("{}{}{}{}YYYY", date_token(&c[1], first_token), &c[2], date_token(&c[3], second_token), &c[4]), year));
+		}
+	}
+
+	None
+}
+
+/// This function returns the template of an entity, in which upper case letters are replaced by `A`, lower case letters by `a` and digits by `#`.
+/// The other chars are kept literally (`#` and `\` are escaped by `\`).
+fn template(entity: &str) -> String {
+	let mut tmpl = String::new();
+
+	for c in entity.chars() {
+		match c {
+			'A'..='Z' => tmpl.push('A'),
+			'a'..='z' => tmpl.push('a'),
+			'0'..='9' => tmpl.push('#'),
+			'#' | '\\' => {
+				tmpl.push('\\');
+				tmpl.push(c);
+			},
+			_ => tmpl.push(c),
+		}
+	}
+
+	tmpl
+}
+
+/// This function returns the template of a hexadecimal entity (e.g.: a UUID), in which the hex digits are replaced by `h` (or `H` if the entity is in upper case)
+fn hex_template(entity: &str) -> String {
+	let hex = if entity.chars().any(|c| c.is_ascii_uppercase()) { 'H' } else { 'h' };
+
+	entity.chars().map(|c| if c.is_ascii_hexdigit() { hex } else { c }).collect()
+}
+
+/// This function returns the template of a phone number, in which the first digit of the country code is `n` (1 to 9)
+/// and the first digits of the other groups of digits are `N` (2 to 9), so that the area codes and exchange codes don't start with 0 or 1
+fn phone_template(tmpl: &str) -> String {
+	let mut phone = String::new();
+	let mut prior = None;
+
+	for c in tmpl.chars() {
+		match (c, prior) {
+			('#', Some('#')) => phone.push('#'),
+			('#', Some('+')) => phone.push('n'),
+			('#', _) => phone.push('N'),
+			_ => phone.push(c),
+		}
+		prior = Some(c);
+	}
+
+	phone
+}
+
+/// This function returns the regular expression of a template, which matches the values that are expanded from the template (see _expand()_)
+fn template_regex(tmpl: &str) -> String {
+	let mut regex = String::new();
+	let mut chars = tmpl.chars();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'A' => regex.push_str("[A-Z]"),
+			'a' => regex.push_str("[a-z]"),
+			'#' => regex.push_str("[0-9]"),
+			'n' => regex.push_str("[1-9]"),
+			'N' => regex.push_str("[2-9]"),
+			'h' => regex.push_str("[0-9a-f]"),
+			'H' => regex.push_str("[0-9A-F]"),
+			'\\' => regex.extend(chars.next().map(|e| regex::escape(&e.to_string()))),
+			_ => regex.push_str(&regex::escape(&c.to_string())),
+		}
+	}
+
+	regex
+}
+
+/// This function returns the regular expression of a date format, which matches the valid dates in the format (see _generate_date()_)
+fn date_regex(format: &str) -> String {
+	let tokens = [
+		("YYYY", "[0-9]{4}"),
+		("MM", "(?:0[1-9]|1[0-2])"),
+		("DD", "(?:0[1-9]|[12][0-9]|3[01])"),
+		("M", "(?:[1-9]|1[0-2])"),
+		("D", "(?:[1-9]|[12][0-9]|3[01])"),
+	];
+	let mut regex = String::new();
+	let mut rest = format;
+
+	while let Some(c) = rest.chars().next() {
+		match tokens.iter().find(|(token, _)| rest.starts_with(token)) {
+			Some((token, token_regex)) => {
+				regex.push_str(token_regex);
+				rest = &rest[token.len()..];
+			},
+			None => {
+				regex.push_str(&regex::escape(&c.to_string()));
+				rest = &rest[c.len_utf8()..];
+			},
+		}
+	}
+
+	regex
+}
+
+/// This function returns the regular expression of an octet of an IPv4 address with the number of digits, (see _octet()_)
+fn octet_regex(digits: usize) -> &'static str {
+	match digits {
+		1 => "[0-9]",
+		2 => "[1-9][0-9]",
+		_ => "(?:1[0-9]{2}|2[0-4][0-9]|25[0-5])",
+	}
+}
+
+/// This function replaces the placeholders of a template with random chars, (see _template()_, _hex_template()_ and _phone_template()_)
+fn expand<R: Rng>(tmpl: &str, rng: &mut R) -> String {
+	const HEX: &[u8] = b"0123456789abcdef";
+	let mut expanded = String::new();
+	let mut chars = tmpl.chars();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'A' => expanded.push(rng.gen_range(b'A', b'Z' + 1) as char),
+			'a' => expanded.push(rng.gen_range(b'a', b'z' + 1) as char),
+			'#' => expanded.push(rng.gen_range(b'0', b'9' + 1) as char),
+			'n' => expanded.push(rng.gen_range(b'1', b'9' + 1) as char),
+			'N' => expanded.push(rng.gen_range(b'2', b'9' + 1) as char),
+			'h' => expanded.push(HEX[rng.gen_range(0, 16)] as char),
+			'H' => expanded.push((HEX[rng.gen_range(0, 16)] as char).to_ascii_uppercase()),
+			'\\' => expanded.extend(chars.next()),
+			_ => expanded.push(c),
+		}
+	}
+
+	expanded
+}
+
+/// This function returns the chars that a placeholder of a template is replaced with (None if the char isn't a placeholder)
+fn placeholder_chars(c: char) -> Option<&'static str> {
+	match c {
+		'A' => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+		'a' => Some("abcdefghijklmnopqrstuvwxyz"),
+		'#' => Some("0123456789"),
+		'n' => Some("123456789"),
+		'N' => Some("23456789"),
+		'h' => Some("0123456789abcdef"),
+		'H' => Some("0123456789ABCDEF"),
+		_ => None,
+	}
+}
+
+/// This function replaces the placeholders of a template with random chars that satisfy the filter, (None if a placeholder has no such chars)
+fn expand_where<R: Rng>(tmpl: &str, rng: &mut R, filter: &dyn Fn(&char) -> bool) -> Option<String> {
+	let mut expanded = String::new();
+	let mut chars = tmpl.chars();
+
+	while let Some(c) = chars.next() {
+		match (c, placeholder_chars(c)) {
+			('\\', _) => expanded.extend(chars.next()),
+			(_, Some(candidates)) => {
+				let allowed = candidates.chars().filter(|a| filter(a)).collect::<Vec<char>>();
+				if allowed.is_empty() {
+					return None;
+				}
+				expanded.push(allowed[rng.gen_range(0, allowed.len())]);
+			},
+			_ => expanded.push(c),
+		}
+	}
+
+	Some(expanded)
+}
+
+/// This function returns the chars of a template that aren't placeholders, (i.e.: the chars that are kept literally)
+fn template_literals(tmpl: &str) -> Vec<char> {
+	let mut literals = Vec::new();
+	let mut chars = tmpl.chars();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' => literals.extend(chars.next()),
+			_ if placeholder_chars(c).is_none() => literals.push(c),
+			_ => {},
+		}
+	}
+
+	literals
+}
+
+/// This function returns the lowest and highest length of the values that are generated in the format of the semantic type,
+/// (without the value of the part that is reused, e.g.: the domain of an email address), and the chars that are kept literally
+fn format_bounds(semantic_type: SemanticType, format: &str) -> (usize, usize, Vec<char>) {
+	match semantic_type {
+		SemanticType::Date => {
+			let mut bounds = (0, 0, Vec::new());
+			let mut rest = format;
+
+			while let Some(c) = rest.chars().next() {
+				let (token, min, max) = match c {
+					'Y' if rest.starts_with("YYYY") => ("YYYY", 4, 4),
+					'M' | 'D' if rest[1..].starts_with(c) => (&rest[..2], 2, 2),
+					'M' | 'D' => (&rest[..1], 1, 2),
+					_ => {
+						bounds.2.push(c);
+						(&rest[..c.len_utf8()], 1, 1)
+					},
+				};
+				bounds.0 += min;
+				bounds.1 += max;
+				rest = &rest[token.len()..];
+			}
+
+			bounds
+		},
+		_ => {
+			let len = template_len(format) + if semantic_type == SemanticType::Email { 1 } else { 0 };
+			let mut literals = template_literals(format);
+			if semantic_type == SemanticType::Email {
+				literals.push('@');
+			}
+
+			(len, len, literals)
+		},
+	}
+}
+
+/// This function returns the number of chars of the values that are expanded from a template
+fn template_len(tmpl: &str) -> usize {
+	let mut len = 0;
+	let mut chars = tmpl.chars();
+
+	while let Some(c) = chars.next() {
+		if c == '\\' {
+			chars.next();
+		}
+		len += 1;
+	}
+
+	len
+}
+
+/// This function returns a random number with the number of digits, that is a valid octet of an IPv4 address
+fn octet<R: Rng>(digits: usize, rng: &mut R) -> u32 {
+	match digits {
+		1 => rng.gen_range(0, 10),
+		2 => rng.gen_range(10, 100),
+		_ => rng.gen_range(100, 256),
+	}
+}
+
+/// This function samples a key of a map by its count of occurrence
+fn sample_key<R: Rng>(map: &BTreeMap<String, u32>, rng: &mut R) -> Option<String> {
+	AliasTable::new(map.iter().map(|(k, c)| (k.clone(), *c as u64)).collect()).sample(rng).cloned()
+}
+
+/// This function returns the default minimum confidence
+fn default_threshold() -> f64 {
+	DEFAULT_SEMANTIC_THRESHOLD
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents the semantic types and formats of the entities that a Profile has analyzed
+pub struct SemanticModel {
+	/// The minimum confidence of the semantic type that is required to generate values of the type (a threshold above 1.0 turns the type-aware generation off)
+	#[serde(default = "default_threshold")]
+	pub threshold: f64,
+	/// The number of entities that have been analyzed
+	pub total: u32,
+	/// The number of entities of each semantic type
+	pub counts: BTreeMap<SemanticType, u32>,
+	/// The formats of the entities of each semantic type with their count of occurrence, (e.g.: `(###) ###-####` or `MM/DD/YYYY`)
+	pub formats: BTreeMap<SemanticType, BTreeMap<String, u32>>,
+	/// The values of the parts of the entities of each semantic type that are reused when generating values with their count of occurrence,
+	/// (i.e.: the domains of the email addresses, the schemes, hosts and ports of the URLs and the years of the dates)
+	/// Only the first MAX_SEMANTIC_VALUES distinct formats and values of each semantic type are kept.
+	/// The values are only archived if they are explicitly archived (see _Profile::set_archive_entity_hashes()_)
+	#[serde(default, skip_serializing_if = "SemanticValues::is_private")]
+	pub values: SemanticValues,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(from = "BTreeMap<SemanticType, BTreeMap<String, u32>>", into = "BTreeMap<SemanticType, BTreeMap<String, u32>>")]
+/// Represents the values of the parts of the entities of each semantic type that are reused when generating values.
+/// Since the values are fragments of the sample data, they are only archived with the Profile if they are explicitly archived,
+/// like the hashes of the entities (see _Profile::set_archive_entity_hashes()_).
+pub struct SemanticValues {
+	/// If true, the values are archived with the Profile
+	pub archive: bool,
+	/// The values of each semantic type with their count of occurrence
+	pub counts: BTreeMap<SemanticType, BTreeMap<String, u32>>,
+}
+
+impl From<BTreeMap<SemanticType, BTreeMap<String, u32>>> for SemanticValues {
+	fn from(counts: BTreeMap<SemanticType, BTreeMap<String, u32>>) -> Self {
+		// the values of an archive have been archived explicitly
+		SemanticValues {
+			archive: true,
+			counts,
+		}
+	}
+}
+
+impl From<SemanticValues> for BTreeMap<SemanticType, BTreeMap<String, u32>> {
+	fn from(values: SemanticValues) -> Self {
+		values.counts
+	}
+}
+
+impl SemanticValues {
+	/// Constructs a new SemanticValues, which isn't archived
+	pub fn new() -> SemanticValues {
+		SemanticValues {
+			archive: false,
+			counts: BTreeMap::new(),
+		}
+	}
+
+	/// This function returns the values of the semantic type with their count of occurrence (None if the type has no values)
+	///
+	/// # Arguments
+	///
+	/// * `semantic_type: &SemanticType` - The semantic type of the values.</br>
+	pub fn get(&self, semantic_type: &SemanticType) -> Option<&BTreeMap<String, u32>> {
+		self.counts.get(semantic_type)
+	}
+
+	/// This function returns true if the values aren't archived
+	fn is_private(&self) -> bool {
+		!self.archive
+	}
+}
+
+/// This function adds the count of a format or value, unless the map already has MAX_SEMANTIC_VALUES other formats or values
+fn count_value(map: &mut BTreeMap<String, u32>, key: String, count: u32) {
+	if map.len() < MAX_SEMANTIC_VALUES || map.contains_key(&key) {
+		*map.entry(key).or_insert(0) += count;
+	}
+}
+
+impl Default for SemanticModel {
+	fn default() -> Self {
+		SemanticModel::new()
+	}
+}
+
+impl SemanticModel {
+	/// Constructs a new SemanticModel
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::semantic::{SemanticModel, DEFAULT_SEMANTIC_THRESHOLD};
+	///
+	/// fn main() {
+	///		let model = SemanticModel::new();
+	///
+	///		assert_eq!(model.threshold, DEFAULT_SEMANTIC_THRESHOLD);
+	///		assert_eq!(model.semantic_type(), None);
+	/// }
+	/// ```
+	pub fn new() -> SemanticModel {
+		SemanticModel {
+			threshold: DEFAULT_SEMANTIC_THRESHOLD,
+			total: 0,
+			counts: BTreeMap::new(),
+			formats: BTreeMap::new(),
+			values: SemanticValues::new(),
+		}
+	}
+
+	/// This function classifies an entity, and returns its semantic type with its format and the values of its parts (None if it has no semantic type)
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The textual str of the value to classify.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::semantic::{SemanticModel, SemanticType};
+	///
+	/// fn main() {
+	///		assert_eq!(SemanticModel::classify("john.smith@example.com"), Some((SemanticType::Email, "aaaa.aaaaa".to_string(), Some("example.com".to_string()))));
+	///		assert_eq!(SemanticModel::classify("(555) 123-4567"), Some((SemanticType::Phone, "(###) ###-####".to_string(), None)));
+	///		assert_eq!(SemanticModel::classify("01/04/2017"), Some((SemanticType::Date, "MM/DD/YYYY".to_string(), Some("2017".to_string()))));
+	///		assert_eq!(SemanticModel::classify("Smith, John"), None);
+	/// }
+	/// ```
+	pub fn classify(entity: &str) -> Option<(SemanticType, String, Option<String>)> {
+		if UUID.is_match(entity) {
+			return Some((SemanticType::Uuid, hex_template(entity), None));
+		}
+
+		if entity.parse::<Ipv4Addr>().is_ok() {
+			return Some((SemanticType::Ipv4, template(entity), None));
+		}
+
+		if entity.contains(':') && entity.parse::<Ipv6Addr>().is_ok() {
+			return Some((SemanticType::Ipv6, hex_template(entity), None));
+		}
+
+		if let Some(c) = EMAIL.captures(entity) {
+			return Some((SemanticType::Email, template(&c[1]), Some(c[2].to_string())));
+		}
+
+		if let Some(c) = URL.captures(entity) {
+			return Some((SemanticType::Url, template(&c[2]), Some(c[1].to_string())));
+		}
+
+		if let Some((format, year)) = date_format(entity) {
+			return Some((SemanticType::Date, format, Some(year.to_string())));
+		}
+
+		// numbers (e.g.: 10250 or 12345.67) are left to the NumericModel, so that they keep their range
+		if NumericModel::parse(entity).is_some() {
+			return None;
+		}
+
+		if POSTAL_CODE.is_match(entity) {
+			return Some((SemanticType::PostalCode, template(entity), None));
+		}
+
+		// a phone number has formatting chars, so that numbers aren't classified as phone numbers
+		let digits = entity.chars().filter(|c| c.is_ascii_digit()).count();
+		if PHONE.is_match(entity) && (7..=15).contains(&digits) && entity.chars().any(|c| !c.is_ascii_digit()) {
+			return Some((SemanticType::Phone, template(entity), None));
+		}
+
+		None
+	}
+
+	/// This function classifies an entity and learns its format
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The textual str of the value to analyze.</br>
+	pub fn analyze(&mut self, entity: &str) {
+		self.total += 1;
+
+		if let Some((semantic_type, format, value)) = SemanticModel::classify(entity) {
+			*self.counts.entry(semantic_type).or_insert(0) += 1;
+			count_value(self.formats.entry(semantic_type).or_default(), format, 1);
+
+			if let Some(v) = value {
+				count_value(self.values.counts.entry(semantic_type).or_default(), v, 1);
+			}
+		}
+	}
+
+	/// This function merges (unions) the counts, formats and values of another SemanticModel into this SemanticModel
+	///
+	/// # Arguments
+	///
+	/// * `other: &SemanticModel` - The SemanticModel to merge into this SemanticModel.</br>
+	pub fn merge(&mut self, other: &SemanticModel) {
+		self.total += other.total;
+
+		for (semantic_type, count) in other.counts.iter() {
+			*self.counts.entry(*semantic_type).or_insert(0) += *count;
+		}
+
+		for (target, source) in [(&mut self.formats, &other.formats), (&mut self.values.counts, &other.values.counts)].iter_mut() {
+			for (semantic_type, map) in source.iter() {
+				let entry = target.entry(*semantic_type).or_default();
+				for (key, count) in map.iter() {
+					count_value(entry, key.clone(), *count);
+				}
+			}
+		}
+	}
+
+	/// This function returns the semantic type of most of the entities with its confidence, which is the fraction of the entities that have the type
+	/// (None if no entity has a semantic type)
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::semantic::{SemanticModel, SemanticType};
+	///
+	/// fn main() {
+	///		let mut model = SemanticModel::new();
+	///		model.analyze("192.168.0.1");
+	///		model.analyze("10.0.0.254");
+	///		model.analyze("n/a");
+	///		model.analyze("172.16.4.2");
+	///
+	///		assert_eq!(model.semantic_type(), Some((SemanticType::Ipv4, 0.75)));
+	/// }
+	/// ```
+	pub fn semantic_type(&self) -> Option<(SemanticType, f64)> {
+		let (semantic_type, count) = self.counts.iter().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))?;
+
+		Some((*semantic_type, *count as f64 / self.total as f64))
+	}
+
+	/// This function returns the semantic type if its confidence is at least the threshold, so that values of the type are generated
+	pub fn generated_type(&self) -> Option<SemanticType> {
+		match self.semantic_type() {
+			Some((semantic_type, confidence)) if confidence >= self.threshold => Some(semantic_type),
+			_ => None,
+		}
+	}
+
+	/// This function generates a valid value of the semantic type in one of the formats of the analyzed entities, selected by its chance of occurring
+	///
+	/// # Arguments
+	///
+	/// * `semantic_type: SemanticType` - The semantic type of the value to generate.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use rand::thread_rng;
+	/// use test_data_generation::semantic::{SemanticModel, SemanticType};
+	///
+	/// fn main() {
+	///		let mut model = SemanticModel::new();
+	///		model.analyze("123e4567-e89b-42d3-a456-426614174000");
+	///
+	///		let uuid = model.generate(SemanticType::Uuid, &mut thread_rng()).unwrap();
+	///
+	///		assert_eq!(uuid.len(), 36);
+	///		assert_eq!(&uuid[14..15], "4");
+	/// }
+	/// ```
+	pub fn generate<R: Rng>(&self, semantic_type: SemanticType, rng: &mut R) -> Result<String, Error> {
+		let format = match self.formats.get(&semantic_type).and_then(|f| sample_key(f, rng)) {
+			Some(f) => f,
+			None => return Err(Error::EmptyProfile(format!("There are no formats for the semantic type {}", semantic_type))),
+		};
+		let value = self.values.get(&semantic_type).and_then(|v| sample_key(v, rng));
+
+		self.generate_in_format(semantic_type, &format, value, None, rng)
+			.ok_or_else(|| Error::Unsatisfiable(format!("The format {:?} of the semantic type {} can't be generated", format, semantic_type)))
+	}
+
+	/// This function generates a valid value of the semantic type, like _generate()_, but only in the formats (and with the reused values,
+	/// e.g.: the domains of email addresses) that generate values with a length in the range of lengths and with literal chars that satisfy the filter.
+	/// The random chars of the format are also selected from the chars that satisfy the filter.
+	/// NOTE: The digits of the dates and IPv4 addresses aren't filtered, so the generated value still has to be checked against the filter.
+	///
+	/// # Arguments
+	///
+	/// * `semantic_type: SemanticType` - The semantic type of the value to generate.</br>
+	/// * `lengths: &RangeInclusive<usize>` - The range of the lengths (number of chars) of the value.</br>
+	/// * `filter: &dyn Fn(&char) -> bool` - The filter of the chars of the value.</br>
+	/// * `rng: &mut R` - The random number generator to use.</br>
+	///
+	/// #Errors
+	/// If none of the formats satisfies the range of lengths and the filter, an Error::Unsatisfiable is returned.
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use rand::thread_rng;
+	/// use test_data_generation::semantic::{SemanticModel, SemanticType};
+	///
+	/// fn main() {
+	///		let mut model = SemanticModel::new();
+	///		model.analyze("(555) 123-4567");
+	///		model.analyze("555-123-4567");
+	///
+	///		let phone = model.generate_within(SemanticType::Phone, &(0..=12), &|c: &char| *c != '0', &mut thread_rng()).unwrap();
+	///
+	///		assert_eq!(phone.len(), 12);
+	///		assert!(!phone.contains('0'));
+	/// }
+	/// ```
+	pub fn generate_within<R: Rng>(&self, semantic_type: SemanticType, lengths: &RangeInclusive<usize>, filter: &dyn Fn(&char) -> bool, rng: &mut R) -> Result<String, Error> {
+		let unsatisfiable = || Error::Unsatisfiable(format!("None of the formats of the semantic type {} satisfies the constraints", semantic_type));
+		let reuses_value = semantic_type == SemanticType::Email || semantic_type == SemanticType::Url;
+
+		// the lengths of the reused values that only have allowed chars
+		let values = self.values.get(&semantic_type).map(|v| {
+			v.iter().filter(|(k, _c)| k.chars().all(|c| filter(&c))).map(|(k, c)| (k.clone(), *c)).collect::<BTreeMap<String, u32>>()
+		}).unwrap_or_default();
+		let fits = |value: &str, min: usize, max: usize| {
+			let len = value.chars().count();
+			max + len >= *lengths.start() && min + len <= *lengths.end()
+		};
+
+		// restrict the formats to those that can generate a value that satisfies the constraints
+		let formats = self.formats.get(&semantic_type).map(|f| {
+			f.iter().filter(|(format, _c)| {
+				let (min, max, literals) = format_bounds(semantic_type, format);
+				let fitting = if reuses_value { values.keys().any(|v| fits(v, min, max)) } else { fits("", min, max) };
+				literals.iter().all(filter) && fitting
+			}).map(|(k, c)| (k.clone(), *c)).collect::<BTreeMap<String, u32>>()
+		}).unwrap_or_default();
+		let format = sample_key(&formats, rng).ok_or_else(unsatisfiable)?;
+
+		let value = if reuses_value {
+			let (min, max, _literals) = format_bounds(semantic_type, &format);
+			let fitting = values.into_iter().filter(|(v, _c)| fits(v, min, max)).collect::<BTreeMap<String, u32>>();
+			Some(sample_key(&fitting, rng).ok_or_else(unsatisfiable)?)
+		} else {
+			self.values.get(&semantic_type).and_then(|v| sample_key(v, rng))
+		};
+
+		self.generate_in_format(semantic_type, &format, value, Some(filter), rng).ok_or_else(unsatisfiable)
+	}
+
+	/// This function generates a valid value of the semantic type in the format, with the random chars selected from the chars that satisfy the filter
+	/// (None if a random char has no such chars)
+	fn generate_in_format<R: Rng>(&self, semantic_type: SemanticType, format: &str, value: Option<String>, filter: Option<&dyn Fn(&char) -> bool>, rng: &mut R) -> Option<String> {
+		let fill = |tmpl: &str, rng: &mut R| match filter {
+			Some(f) => expand_where(tmpl, rng, f),
+			None => Some(expand(tmpl, rng)),
+		};
+
+		let generated = match semantic_type {
+			SemanticType::Email => format!("{}@{}", fill(format, rng)?, value.unwrap_or_default()),
+			SemanticType::Url => format!("{}{}", value.unwrap_or_default(), fill(format, rng)?),
+			SemanticType::Date => self.generate_date(format, rng),
+			SemanticType::Ipv4 => format.split('.').map(|o| octet(o.len(), rng).to_string()).collect::<Vec<String>>().join("."),
+			SemanticType::Uuid => {
+				let mut uuid = fill(format, rng)?.chars().collect::<Vec<char>>();
+				let offset = if uuid[0] == '{' { 1 } else { 0 };
+				let hyphens = uuid.contains(&'-');
+
+				// the generated UUIDs are random (version 4) UUIDs
+				let (version, variant) = if hyphens { (offset + 14, offset + 19) } else { (offset + 12, offset + 16) };
+				let upper = format.contains('H');
+				uuid[version] = '4';
+				uuid[variant] = ['8', '9', 'a', 'b'][rng.gen_range(0, 4)];
+				if upper {
+					uuid[variant] = uuid[variant].to_ascii_uppercase();
+				}
+
+				uuid.into_iter().collect()
+			},
+			SemanticType::Phone => fill(&phone_template(format), rng)?,
+			SemanticType::Ipv6 | SemanticType::PostalCode => fill(format, rng)?,
+		};
+
+		Some(generated)
+	}
+
+	/// This function returns the regular expressions of the formats of the semantic type in decreasing order of occurrence,
+	/// which match the values that are generated for the semantic type (see _generate()_)
+	///
+	/// # Arguments
+	///
+	/// * `semantic_type: SemanticType` - The semantic type of the generated values.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::semantic::{SemanticModel, SemanticType};
+	///
+	/// fn main() {
+	///		let mut model = SemanticModel::new();
+	///		model.analyze("(555) 123-4567");
+	///
+	///		assert_eq!(model.regex_alternatives(SemanticType::Phone), vec!["\\([2-9][0-9][0-9]\\) [2-9][0-9][0-9]\\-[2-9][0-9][0-9][0-9]"]);
+	/// }
+	/// ```
+	pub fn regex_alternatives(&self, semantic_type: SemanticType) -> Vec<String> {
+		let ranked = |map: Option<&BTreeMap<String, u32>>| {
+			let mut keys = map.map(|m| m.iter().collect::<Vec<(&String, &u32)>>()).unwrap_or_default();
+			keys.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+			keys.into_iter().map(|(k, _c)| k.clone()).collect::<Vec<String>>()
+		};
+		let values = ranked(self.values.get(&semantic_type)).iter().map(|v| regex::escape(v)).collect::<Vec<String>>().join("|");
+
+		ranked(self.formats.get(&semantic_type)).iter().map(|format| {
+			match semantic_type {
+				SemanticType::Email => format!("{}@(?:{})", template_regex(format), values),
+				SemanticType::Url => format!("(?:{}){}", values, template_regex(format)),
+				SemanticType::Date => date_regex(format),
+				SemanticType::Ipv4 => format.split('.').map(|o| octet_regex(o.len())).collect::<Vec<&str>>().join("\\."),
+				SemanticType::Phone => template_regex(&phone_template(format)),
+				SemanticType::Uuid | SemanticType::Ipv6 | SemanticType::PostalCode => template_regex(format),
+			}
+		}).collect()
+	}
+
+	/// This function generates a valid date in the format, with a year in the range of the years of the analyzed dates
+	fn generate_date<R: Rng>(&self, format: &str, rng: &mut R) -> String {
+		let years = self.values.get(&SemanticType::Date).map(|v| v.keys().filter_map(|y| y.parse::<u32>().ok()).collect::<Vec<u32>>()).unwrap_or_default();
+		let min = years.iter().min().cloned().unwrap_or(2000);
+		let max = years.iter().max().cloned().unwrap_or(min);
+
+		let year = rng.gen_range(min, max + 1);
+		let month = rng.gen_range(1, 13);
+		let day = rng.gen_range(1, days_in_month(year, month) + 1);
+
+		format.replace("YYYY", &format!("{:04}", year))
+			.replace("MM", &format!("{:02}", month))
+			.replace("DD", &format!("{:02}", day))
+			.replace('M', &month.to_string())
+			.replace('D', &day.to_string())
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn classify_types(){
+		assert_eq!(SemanticModel::classify("{123E4567-E89B-42D3-A456-426614174000}").unwrap().1, "{HHHHHHHH-HHHH-HHHH-HHHH-HHHHHHHHHHHH}");
+		assert_eq!(SemanticModel::classify("10.0.0.254").unwrap().0, SemanticType::Ipv4);
+		assert_eq!(SemanticModel::classify("999.0.0.1"), None);
+		assert_eq!(SemanticModel::classify("2001:db8::ff00:42:8329").unwrap(), (SemanticType::Ipv6, "hhhh:hhh::hhhh:hh:hhhh".to_string(), None));
+		assert_eq!(SemanticModel::classify("https://example.com:8080/a/b1?q=x").unwrap(), (SemanticType::Url, "/a/a#?a=a".to_string(), Some("https://example.com:8080".to_string())));
+		assert_eq!(SemanticModel::classify("2017-1-4").unwrap().1, "YYYY-M-D");
+		assert_eq!(SemanticModel::classify("31/12/2017").unwrap().1, "DD/MM/YYYY");
+		assert_eq!(SemanticModel::classify("4.1.2017").unwrap().1, "D.M.YYYY");
+		assert_eq!(SemanticModel::classify("02/30/2017"), None);
+		assert_eq!(SemanticModel::classify("+1 555.123.4567").unwrap().1, "+# ###.###.####");
+		assert_eq!(SemanticModel::classify("5551234567"), None);
+		assert_eq!(SemanticModel::classify("K1A 0B1").unwrap(), (SemanticType::PostalCode, "A#A #A#".to_string(), None));
+		assert_eq!(SemanticModel::classify("12345-6789").unwrap().0, SemanticType::PostalCode);
+		// numbers are left to the NumericModel
+		assert_eq!(SemanticModel::classify("10250"), None);
+		assert_eq!(SemanticModel::classify("12345.67"), None);
+	}
+
+	#[test]
+	fn generate_phone_area_codes(){
+		let mut rng = StdRng::seed_from_u64(13);
+		let mut model = SemanticModel::new();
+		model.analyze("+1 (555) 123-4567");
+		let regex = Regex::new(&format!("^{}$", model.regex_alternatives(SemanticType::Phone)[0])).unwrap();
+
+		for _ in 0..100 {
+			let phone = model.generate(SemanticType::Phone, &mut rng).unwrap();
+			let digits = phone.chars().filter(|c| c.is_ascii_digit()).collect::<Vec<char>>();
+
+			assert!(regex.is_match(&phone), "{} should match {}", phone, regex);
+			assert!(digits[0] != '0' && digits[1] >= '2' && digits[4] >= '2', "{} has an invalid area or exchange code", phone);
+		}
+	}
+
+	#[test]
+	fn generate_valid_values(){
+		let samples = [
+			("john.smith@example.com", EMAIL.clone()),
+			("https://example.com/items/42", URL.clone()),
+			("(555) 123-4567", PHONE.clone()),
+			("K1A 0B1", POSTAL_CODE.clone()),
+			("123e4567e89b42d3a456426614174000", UUID.clone()),
+		];
+		let mut rng = StdRng::seed_from_u64(17);
+
+		for (sample, regex) in samples.iter() {
+			let mut model = SemanticModel::new();
+			model.analyze(sample);
+			let semantic_type = model.generated_type().unwrap();
+
+			for _ in 0..20 {
+				let generated = model.generate(semantic_type, &mut rng).unwrap();
+				assert!(regex.is_match(&generated), "{} is not a valid {}", generated, semantic_type);
+				assert_eq!(SemanticModel::classify(&generated).map(|c| c.0), Some(semantic_type));
+			}
+		}
+	}
+
+	#[test]
+	fn generate_network_addresses(){
+		let mut rng = StdRng::seed_from_u64(3);
+		let mut model = SemanticModel::new();
+		model.analyze("192.168.0.1");
+		model.analyze("2001:0DB8:0000:0000:0000:FF00:0042:8329");
+		model.analyze("fe80::1");
+
+		for _ in 0..50 {
+			assert!(model.generate(SemanticType::Ipv4, &mut rng).unwrap().parse::<Ipv4Addr>().is_ok());
+			assert!(model.generate(SemanticType::Ipv6, &mut rng).unwrap().parse::<Ipv6Addr>().is_ok());
+		}
+	}
+
+	#[test]
+	fn generate_valid_dates(){
+		let mut rng = StdRng::seed_from_u64(5);
+		let mut model = SemanticModel::new();
+		model.analyze("02/29/2016");
+		model.analyze("12/31/2018");
+
+		for _ in 0..100 {
+			let date = model.generate(SemanticType::Date, &mut rng).unwrap();
+			let (format, year) = date_format(&date).unwrap();
+
+			assert_eq!(format, "MM/DD/YYYY");
+			assert!((2016..=2018).contains(&year));
+		}
+	}
+
+	#[test]
+	fn merge_models(){
+		let mut model = SemanticModel::new();
+		model.analyze("a@example.com");
+		let mut other = SemanticModel::new();
+		other.analyze("b@example.com");
+		other.analyze("unknown");
+
+		model.merge(&other);
+
+		assert_eq!(model.total, 3);
+		assert_eq!(model.values.counts[&SemanticType::Email]["example.com"], 2);
+		assert_eq!(model.semantic_type(), Some((SemanticType::Email, 2.0 / 3.0)));
+		assert_eq!(model.generated_type(), None);
+	}
+
+	#[test]
+	fn limit_values(){
+		let mut model = SemanticModel::new();
+		for i in 0..(MAX_SEMANTIC_VALUES + 10) {
+			model.analyze(&format!("a@host{}.com", i));
+		}
+		model.analyze("b@host0.com");
+
+		assert_eq!(model.counts[&SemanticType::Email], MAX_SEMANTIC_VALUES as u32 + 11);
+		assert_eq!(model.values.counts[&SemanticType::Email].len(), MAX_SEMANTIC_VALUES);
+		assert_eq!(model.values.counts[&SemanticType::Email]["host0.com"], 2);
+	}
+}
@@ -1,5 +1,8 @@
 use std::mem;
 use csv::Reader;
+use rand::Rng;
+use crate::csv_dialect::CsvDialect;
+use crate::Error;
 
 /// This function converts a String to a &'static str</br>
 ///
@@ -27,6 +30,212 @@ pub fn string_to_static_str(s: String) -> &'static str {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Represents an alias table (Walker's alias method) that selects an item, weighted by its count of occurrence, in constant time
+pub struct AliasTable<T> {
+    /// the items that can be selected
+    items: Vec<T>,
+    /// the probability of keeping the item in the column (instead of using its alias)
+    probabilities: Vec<f64>,
+    /// the index of the alias item for each column
+    aliases: Vec<usize>,
+    /// the weight of each item
+    weights: Vec<u64>,
+    /// the sum of the weights of all the items
+    total: u64,
+}
+
+impl<T> AliasTable<T> {
+    /// Constructs a new AliasTable from a list of items and their weights. Items with a weight of zero are never selected (unless all the weights are zero).
+    ///
+    /// # Arguments
+    ///
+    /// * `weighted: Vec<(T, u64)>` - The items with their weights (e.g.: the count of occurrences).</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::shared::AliasTable;
+    ///
+    /// fn main() {
+    ///     let table = AliasTable::new(vec![('a', 3), ('b', 1)]);
+    ///
+    ///     assert_eq!(table.len(), 2);
+    ///     assert_eq!(table.total(), 4);
+    /// }
+    /// ```
+    pub fn new(weighted: Vec<(T, u64)>) -> AliasTable<T> {
+        let total = weighted.iter().map(|(_, w)| w).sum::<u64>();
+        let n = weighted.len();
+        let mut items = Vec::with_capacity(n);
+        let mut weights = Vec::with_capacity(n);
+        let mut scaled = Vec::with_capacity(n);
+
+        for (item, weight) in weighted {
+            items.push(item);
+            weights.push(weight);
+            scaled.push(if total == 0 { 1.0 } else { weight as f64 * n as f64 / total as f64 });
+        }
+
+        let mut probabilities = vec![1.0; n];
+        let mut aliases = (0..n).collect::<Vec<usize>>();
+        let mut small = (0..n).filter(|i| scaled[*i] < 1.0).collect::<Vec<usize>>();
+        let mut large = (0..n).filter(|i| scaled[*i] >= 1.0).collect::<Vec<usize>>();
+
+        // pair each column that is under filled with an item that is over filled
+        while let (Some(s), Some(l)) = (small.pop(), large.pop()) {
+            probabilities[s] = scaled[s];
+            aliases[s] = l;
+            scaled[l] -= 1.0 - scaled[s];
+
+            if scaled[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+
+        AliasTable {
+            items,
+            probabilities,
+            aliases,
+            weights,
+            total,
+        }
+    }
+
+    /// This function returns the number of items in the table
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// This function returns true if there are no items in the table
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// This function returns the sum of the weights of all the items in the table
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// This function returns the sum of the weights of the items that satisfy the filter
+    ///
+    /// # Arguments
+    ///
+    /// * `filter: F` - The function that returns true for the items that can be selected.</br>
+    pub fn total_where<F: Fn(&T) -> bool>(&self, filter: F) -> u64 {
+        self.items.iter().zip(self.weights.iter()).filter(|(i, _)| filter(i)).map(|(_, w)| w).sum()
+    }
+
+    /// This function selects an item that satisfies the filter, weighted by its weight, using the random number generator.
+    /// Unlike _sample()_, this takes linear time since the items are filtered first.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: &mut R` - The random number generator to use.</br>
+    /// * `filter: F` - The function that returns true for the items that can be selected.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate rand;
+    ///
+    /// use test_data_generation::shared::AliasTable;
+    /// use rand::thread_rng;
+    ///
+    /// fn main() {
+    ///     let table = AliasTable::new(vec![('a', 5), ('b', 1)]);
+    ///
+    ///     assert_eq!(table.sample_where(&mut thread_rng(), |c| *c != 'a'), Some(&'b'));
+    ///     assert_eq!(table.sample_where(&mut thread_rng(), |c| *c == 'z'), None);
+    /// }
+    /// ```
+    pub fn sample_where<R: Rng, F: Fn(&T) -> bool>(&self, rng: &mut R, filter: F) -> Option<&T> {
+        let total = self.total_where(&filter);
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut x = rng.gen_range(0, total);
+
+        for (item, weight) in self.items.iter().zip(self.weights.iter()) {
+            if !filter(item) {
+                continue;
+            }
+            if x < *weight {
+                return Some(item);
+            }
+            x -= weight;
+        }
+
+        None
+    }
+
+    /// This function selects an item, weighted by its weight, using the random number generator
+    ///
+    /// # Arguments
+    ///
+    /// * `rng: &mut R` - The random number generator to use.</br>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    /// extern crate rand;
+    ///
+    /// use test_data_generation::shared::AliasTable;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// fn main() {
+    ///     let table = AliasTable::new(vec![('a', 1), ('b', 0)]);
+    ///     let mut rng = StdRng::seed_from_u64(7);
+    ///
+    ///     assert_eq!(table.sample(&mut rng), Some(&'a'));
+    /// }
+    /// ```
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<&T> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        let i = rng.gen_range(0, self.items.len());
+
+        if rng.gen::<f64>() < self.probabilities[i] {
+            Some(&self.items[i])
+        } else {
+            Some(&self.items[self.aliases[i]])
+        }
+    }
+}
+
+/// This function returns a (stable) 64-bit FNV-1a hash of a string, which is the same on every platform and for every build.</br>
+///
+/// # Arguments
+///
+/// * `s: &str` - The textual string to hash.</br>
+///
+/// # Example
+///
+/// ```rust
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::shared;
+///
+/// fn main() {
+///     assert_eq!(shared::hash_str("Hello World"), shared::hash_str("Hello World"));
+///     assert_ne!(shared::hash_str("Hello World"), shared::hash_str("Hello world"));
+/// }
+/// ```
+pub fn hash_str(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
 pub trait CsvManipulator {
     /// This function parses all the rows and splits the columns into separate Vectors
     /// 
@@ -58,7 +267,7 @@ pub trait CsvManipulator {
     ///     .double_quote(true)
     ///     .delimiter(b',')
     ///     .from_reader(data.as_bytes());///       
-    ///     let columns = CsvMngr::read_as_columns(rdr);
+    ///     let columns = CsvMngr::read_as_columns(rdr).unwrap();
     ///     let column0 = vec!("Aaron", "Aaron", "Abbey", "Abbie", "Abby");
     ///     let column1 = vec!("Aaberg", "Aaby", "Aadland", "Aagaard", "Aakre");
     ///     
@@ -66,11 +275,11 @@ pub trait CsvManipulator {
     ///     println!("lastname: {:?}", column1);
     /// }
     /// ```
-    fn read_as_columns(mut rdr: Reader<&[u8]>) -> Vec<Vec<String>> {
+    fn read_as_columns(mut rdr: Reader<&[u8]>) -> Result<Vec<Vec<String>>, Error> {
         let mut columns = Vec::new();
         
         for result in rdr.records() {
-            let record = result.expect("a CSV record");
+            let record = result?;
 
             if columns.len() < record.len() {
                 for _i in 0..record.len() {
@@ -78,13 +287,47 @@ pub trait CsvManipulator {
                 }
             }
 
-            for c in 0..record.len() {
-                let field = record.get(c).unwrap().to_string();
-                columns[c].push(field);
+            for (c, field) in record.iter().enumerate() {
+                columns[c].push(field.to_string());
             }
         }
 
-        columns
+        Ok(columns)
+    }
+
+    /// This function reads csv data of the dialect, and returns the headers (none if the dialect has no header row)
+    /// and the columns as separate Vectors
+    ///
+    /// # Arguments
+    /// * `data: &str` - The textual content of the csv data.</br>
+    /// * `dialect: &CsvDialect` - The dialect of the csv data.</br>
+    ///
+    /// ```rust
+    /// extern crate test_data_generation;
+    ///
+    /// use test_data_generation::CsvDialect;
+    /// use test_data_generation::shared::CsvManipulator;
+    ///
+    /// fn main() {
+    ///     struct CsvMngr {}
+    ///     impl CsvManipulator for CsvMngr {}
+    ///
+    ///     let dialect = CsvDialect::new().with_delimiter('\t');
+    ///     let (headers, columns) = CsvMngr::read_as_columns_with_dialect("firstname\tlastname\nAaron\tAaberg\nAbbey\tAadland", &dialect).unwrap();
+    ///
+    ///     assert_eq!(headers, vec!["firstname", "lastname"]);
+    ///     assert_eq!(columns[1], vec!["Aaberg", "Aadland"]);
+    /// }
+    /// ```
+    fn read_as_columns_with_dialect(data: &str, dialect: &CsvDialect) -> Result<(Vec<String>, Vec<Vec<String>>), Error> {
+        let mut rdr = dialect.reader_builder()?.from_reader(data.as_bytes());
+
+        let headers = match dialect.has_headers {
+            true => rdr.headers()?.iter().map(|h| h.to_string()).collect(),
+            false => Vec::new(),
+        };
+
+        Ok((headers, Self::read_as_columns(rdr)?))
     }
 }
 
@@ -96,6 +339,47 @@ mod tests {
     struct XTest {}
     impl CsvManipulator for XTest {}
 
+    #[test]
+    // ensure the alias table selects the items by their weight
+    fn test_alias_table_sample(){
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let table = AliasTable::new(vec![('a', 6), ('b', 3), ('c', 1), ('d', 0)]);
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = std::collections::BTreeMap::new();
+
+        for _ in 0..10000 {
+            *counts.entry(*table.sample(&mut rng).unwrap()).or_insert(0) += 1;
+        }
+
+        assert!(!counts.contains_key(&'d'));
+        assert!(counts[&'a'] > 5500 && counts[&'a'] < 6500);
+        assert!(counts[&'b'] > 2500 && counts[&'b'] < 3500);
+        assert!(counts[&'c'] > 700 && counts[&'c'] < 1300);
+    }
+
+    #[test]
+    fn test_alias_table_empty(){
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let table: AliasTable<char> = AliasTable::new(Vec::new());
+
+        assert!(table.is_empty());
+        assert_eq!(table.sample(&mut StdRng::seed_from_u64(1)), None);
+    }
+
+    #[test]
+    // ensure the columns are read using the dialect of the csv data
+    fn test_read_as_columns_with_dialect(){
+        let dialect = CsvDialect::new().with_delimiter('|').with_quote('\'').with_headers(false);
+        let (headers, columns) = XTest::read_as_columns_with_dialect("'Aaron'|'Aa|berg'\n'Abbey'|'Aadland'", &dialect).unwrap();
+
+        assert!(headers.is_empty());
+        assert_eq!(columns, vec![vec!["Aaron", "Abbey"], vec!["Aa|berg", "Aadland"]]);
+    }
+
     #[test]
     fn test_read_as_columns(){
         let mut data = String::from("");
@@ -113,7 +397,7 @@ mod tests {
         .delimiter(b',')
         .from_reader(data.as_bytes());
 
-        let columns = XTest::read_as_columns(rdr);
+        let columns = XTest::read_as_columns(rdr).unwrap();
         let column0 = vec!("Aaron", "Aaron", "Abbey", "Abbie", "Abby");
         let column1 = vec!("Aaberg", "Aaby", "Aadland", "Aagaard", "Aakre");
     	
@@ -121,6 +405,20 @@ mod tests {
         assert_eq!(columns[1], column1);
     }
 
+    #[test]
+    fn test_read_as_columns_bad_data(){
+        let mut data = String::from("");
+		data.push_str("\"firstname\",\"lastname\"\n");
+		data.push_str("\"Aaron\",\"Aaberg\"\n");
+		data.push_str("\"Aaron\"\n");
+
+        let rdr: Reader<&[u8]> = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(data.as_bytes());
+
+        assert!(XTest::read_as_columns(rdr).is_err());
+    }
+
     #[test]
     // ensure the conversion of String to &'static str
     fn test_to_static_str(){
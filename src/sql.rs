@@ -0,0 +1,787 @@
+//! The `sql` module provides the writing of generated test data as SQL, so that test databases (e.g.: PostgreSQL or SQLite) can be seeded.
+//!
+//! The `SqlFormat` defines the name of the table, if a `CREATE TABLE` statement is written, and if the records are written as batched `INSERT`
+//! statements or in the text format of the PostgreSQL `COPY ... FROM STDIN` statement (which isn't supported by SQLite).
+//! The type of each column is inferred from its Profile and the generated values (see _SqlColumn::infer()_): plain numbers are `INTEGER`, `BIGINT`
+//! or `NUMERIC(precision, scale)` columns, and the other values (including numbers with thousands separators or leading zeros) are `VARCHAR(max length)` columns.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::{SqlFormat, SqlStatement};
+//! use test_data_generation::data_sample_parser::DataSampleParser;
+//!
+//! fn main() {
+//!		let mut dsp = DataSampleParser::new();
+//!		dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap();
+//!
+//!		dsp.set_sql_format(SqlFormat::new().with_table("people").with_statement(SqlStatement::Copy));
+//!		dsp.generate_sql(100, &String::from("./tests/samples/generated-01.sql")).unwrap();
+//! }
+//! ```
+
+use std::fmt;
+use std::io::Write;
+
+use crate::numeric::{NumberFormat, NumericModel};
+use crate::{Error, Profile};
+
+/// The default name of the table
+pub const DEFAULT_SQL_TABLE: &str = "test_data";
+
+/// The default number of records of each `INSERT` statement
+pub const DEFAULT_SQL_BATCH_SIZE: usize = 100;
+
+/// This function returns the default name of the table
+fn default_table() -> String {
+	DEFAULT_SQL_TABLE.to_string()
+}
+
+/// This function returns the default number of records of each `INSERT` statement
+fn default_batch_size() -> usize {
+	DEFAULT_SQL_BATCH_SIZE
+}
+
+/// This function returns true
+fn default_true() -> bool {
+	true
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents the statements that write the records
+pub enum SqlStatement {
+	/// Batched `INSERT INTO ... VALUES` statements
+	Insert,
+	/// A PostgreSQL `COPY ... FROM STDIN` statement followed by the records in its text format
+	Copy,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents how the generated test data is written as SQL. By default, a `CREATE TABLE` statement for the `test_data` table
+/// is followed by `INSERT` statements of 100 records each.
+pub struct SqlFormat {
+	/// The name of the table, (which is quoted as a single identifier)
+	#[serde(default = "default_table")]
+	pub table: String,
+	/// The number of records of each `INSERT` statement
+	#[serde(default = "default_batch_size", rename = "batch-size")]
+	pub batch_size: usize,
+	/// The statements that write the records
+	#[serde(default = "default_statement")]
+	pub statement: SqlStatement,
+	/// If true, a `CREATE TABLE` statement is written before the records
+	#[serde(default = "default_true", rename = "create-table")]
+	pub create_table: bool,
+}
+
+/// This function returns the default statements that write the records
+fn default_statement() -> SqlStatement {
+	SqlStatement::Insert
+}
+
+impl Default for SqlFormat {
+	fn default() -> Self {
+		SqlFormat::new()
+	}
+}
+
+impl SqlFormat {
+	/// Constructs a new SqlFormat with the default settings
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::{SqlFormat, SqlStatement};
+	///
+	/// fn main() {
+	///		let format = SqlFormat::new();
+	///
+	///		assert_eq!(format.table, "test_data");
+	///		assert_eq!(format.statement, SqlStatement::Insert);
+	/// }
+	/// ```
+	pub fn new() -> SqlFormat {
+		SqlFormat {
+			table: default_table(),
+			batch_size: default_batch_size(),
+			statement: default_statement(),
+			create_table: true,
+		}
+	}
+
+	/// This function sets the name of the table
+	///
+	/// # Arguments
+	///
+	/// * `table: &str` - The name of the table, (e.g.: "customers").</br>
+	pub fn with_table(mut self, table: &str) -> Self {
+		self.table = table.to_string();
+		self
+	}
+
+	/// This function sets the number of records of each `INSERT` statement
+	///
+	/// # Arguments
+	///
+	/// * `batch_size: usize` - The number of records, which must be greater than 0.</br>
+	pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+		self.batch_size = batch_size;
+		self
+	}
+
+	/// This function sets the statements that write the records
+	///
+	/// # Arguments
+	///
+	/// * `statement: SqlStatement` - The statements, (e.g.: SqlStatement::Copy).</br>
+	pub fn with_statement(mut self, statement: SqlStatement) -> Self {
+		self.statement = statement;
+		self
+	}
+
+	/// This function sets if a `CREATE TABLE` statement is written before the records
+	///
+	/// # Arguments
+	///
+	/// * `create_table: bool` - If true, the `CREATE TABLE` statement is written.</br>
+	pub fn with_create_table(mut self, create_table: bool) -> Self {
+		self.create_table = create_table;
+		self
+	}
+
+	/// This function writes the records as SQL
+	///
+	/// #Errors
+	/// If the batch size is 0 or there are no columns, an Error::Config is returned.
+	///
+	/// # Arguments
+	///
+	/// * `wtr: &mut W` - The writer of the SQL, (e.g.: a file).</br>
+	/// * `columns: &[SqlColumn]` - The columns of the table.</br>
+	/// * `records: &[Vec<String>]` - The records, with a value for each column.</br>
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::SqlFormat;
+	/// use test_data_generation::sql::{SqlColumn, SqlType};
+	///
+	/// fn main() {
+	///		let columns = vec![SqlColumn::new("name", SqlType::Varchar(7)), SqlColumn::new("age", SqlType::Integer)];
+	///		let records = vec![vec!["O'Brian".to_string(), "42".to_string()]];
+	///		let mut sql = Vec::new();
+	///
+	///		SqlFormat::new().with_table("people").with_create_table(false).write(&mut sql, &columns, &records).unwrap();
+	///
+	///		assert_eq!(String::from_utf8(sql).unwrap(), "INSERT INTO \"people\" (\"name\", \"age\") VALUES\n('O''Brian', 42);\n");
+	/// }
+	/// ```
+	pub fn write<W: Write>(&self, wtr: &mut W, columns: &[SqlColumn], records: &[Vec<String>]) -> Result<(), Error> {
+		let mut writer = self.writer(wtr, columns)?;
+		writer.write_records(records)?;
+		writer.finish()
+	}
+
+	/// This function returns a SqlWriter, which writes the records in batches, so that the records don't need to be held in memory.
+	/// The `CREATE TABLE` statement (and the `COPY` statement) is written when the SqlWriter is created.
+	///
+	/// #Errors
+	/// If the batch size is 0 or there are no columns, an Error::Config is returned.
+	///
+	/// # Arguments
+	///
+	/// * `wtr: &'a mut W` - The writer of the SQL, (e.g.: a file).</br>
+	/// * `columns: &'a [SqlColumn]` - The columns of the table.</br>
+	///
+	/// #Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::SqlFormat;
+	/// use test_data_generation::sql::{SqlColumn, SqlType};
+	///
+	/// fn main() {
+	///		let columns = vec![SqlColumn::new("age", SqlType::Integer)];
+	///		let mut sql = Vec::new();
+	///
+	///		let format = SqlFormat::new().with_create_table(false);
+	///		let mut writer = format.writer(&mut sql, &columns).unwrap();
+	///		writer.write_records(&[vec!["42".to_string()]]).unwrap();
+	///		writer.write_records(&[vec!["7".to_string()]]).unwrap();
+	///		writer.finish().unwrap();
+	///
+	///		assert_eq!(String::from_utf8(sql).unwrap(), "INSERT INTO \"test_data\" (\"age\") VALUES\n(42);\nINSERT INTO \"test_data\" (\"age\") VALUES\n(7);\n");
+	/// }
+	/// ```
+	pub fn writer<'a, W: Write>(&'a self, wtr: &'a mut W, columns: &'a [SqlColumn]) -> Result<SqlWriter<'a, W>, Error> {
+		if self.batch_size == 0 {
+			return Err(Error::Config("The sql batch size must be greater than 0".to_string()));
+		}
+
+		if columns.is_empty() {
+			return Err(no_columns(&self.table));
+		}
+
+		if self.create_table {
+			writeln!(wtr, "{}", create_table(&self.table, columns)?)?;
+		}
+
+		if self.statement == SqlStatement::Copy {
+			write!(wtr, "{}", copy_statement(&self.table, columns))?;
+		}
+
+		Ok(SqlWriter {
+			format: self,
+			wtr,
+			columns,
+		})
+	}
+}
+
+/// Represents the writing of the records as SQL in batches (see _SqlFormat::writer()_)
+pub struct SqlWriter<'a, W: Write> {
+	format: &'a SqlFormat,
+	wtr: &'a mut W,
+	columns: &'a [SqlColumn],
+}
+
+impl<'a, W: Write> SqlWriter<'a, W> {
+	/// This function writes the records, as `INSERT` statements of at most the batch size of the SqlFormat,
+	/// or as the rows of the `COPY` statement
+	///
+	/// # Arguments
+	///
+	/// * `records: &[Vec<String>]` - The records, with a value for each column.</br>
+	pub fn write_records(&mut self, records: &[Vec<String>]) -> Result<(), Error> {
+		match self.format.statement {
+			SqlStatement::Insert => {
+				for batch in records.chunks(self.format.batch_size) {
+					writeln!(self.wtr, "{}", insert(&self.format.table, self.columns, batch))?;
+				}
+			},
+			SqlStatement::Copy => write!(self.wtr, "{}", copy_rows(self.columns, records))?,
+		}
+
+		Ok(())
+	}
+
+	/// This function finishes the writing of the records, (i.e.: it ends the data of the `COPY` statement)
+	pub fn finish(self) -> Result<(), Error> {
+		if self.format.statement == SqlStatement::Copy {
+			writeln!(self.wtr, "\\.")?;
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Represents the type of a column
+pub enum SqlType {
+	/// A 32-bit integer
+	Integer,
+	/// A 64-bit integer
+	BigInt,
+	/// A decimal number with the total number of digits (precision) and the number of decimal places (scale)
+	Numeric(usize, usize),
+	/// A string with a maximum length (in chars)
+	Varchar(usize),
+}
+
+impl SqlType {
+	/// This function returns true if the values of the type are numbers, (which are written without quotes)
+	pub fn is_numeric(&self) -> bool {
+		!matches!(self, SqlType::Varchar(_))
+	}
+
+	/// This function returns the type of a column of numbers, (None if the values aren't all plain numbers)
+	fn numeric(profile: &Profile, values: &SqlValueStats) -> Option<SqlType> {
+		if !profile.is_numeric() || profile.generated_semantic_type().is_some() {
+			return None;
+		}
+		if !values.plain_numbers || !profile.numeric.formats.keys().all(|f| is_plain(&NumberFormat::from_pattern(f))) {
+			return None;
+		}
+
+		let numbers = profile.numeric.min.iter().chain(profile.numeric.max.iter()).cloned().collect::<Vec<f64>>();
+		let scale = profile.numeric.decimals.max(values.scale);
+
+		let digits = numbers.iter().map(|n| integer_digits(*n)).chain(Some(values.digits)).max().unwrap_or(1).max(1);
+		let min = numbers.iter().cloned().fold(values.min, f64::min);
+		let max = numbers.iter().cloned().fold(values.max, f64::max);
+
+		if scale > 0 {
+			Some(SqlType::Numeric(digits + scale, scale))
+		} else if min >= f64::from(i32::MIN) && max <= f64::from(i32::MAX) {
+			Some(SqlType::Integer)
+		} else {
+			// the integers of the numbers are at most MAX_EXACT_INTEGER (see _NumericModel::parse()_), so they always fit a BIGINT
+			Some(SqlType::BigInt)
+		}
+	}
+}
+
+/// This function returns true if the number is plain (without thousands separators or leading zeros), so that it can be stored as a number
+/// and the generated value is kept as is
+fn is_plain(format: &NumberFormat) -> bool {
+	!format.grouping && format.width == 1
+}
+
+/// This function returns the number of digits of the integer part of a number
+fn integer_digits(number: f64) -> usize {
+	format!("{:.0}", number.abs().trunc()).len()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Represents the statistics of the generated values of a column that the type of the column is inferred from,
+/// so that the values don't need to be held in memory (see _SqlColumn::infer_from()_)
+pub struct SqlValueStats {
+	/// If true, all the values are plain numbers, (without thousands separators or leading zeros)
+	pub plain_numbers: bool,
+	/// The lowest of the numbers (or 0.0)
+	pub min: f64,
+	/// The highest of the numbers (or 0.0)
+	pub max: f64,
+	/// The highest number of digits of the integer parts of the numbers
+	pub digits: usize,
+	/// The highest number of decimal places of the numbers
+	pub scale: usize,
+	/// The maximum length (in chars) of the values
+	pub max_length: usize,
+}
+
+impl Default for SqlValueStats {
+	fn default() -> Self {
+		SqlValueStats::new()
+	}
+}
+
+impl SqlValueStats {
+	/// Constructs a new SqlValueStats, which has no values
+	pub fn new() -> SqlValueStats {
+		SqlValueStats {
+			plain_numbers: true,
+			min: 0.0,
+			max: 0.0,
+			digits: 0,
+			scale: 0,
+			max_length: 0,
+		}
+	}
+
+	/// This function adds a generated value to the statistics
+	///
+	/// # Arguments
+	///
+	/// * `value: &str` - The generated value.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::sql::SqlValueStats;
+	///
+	/// fn main() {
+	///		let mut stats = SqlValueStats::new();
+	///		stats.add("-12.5");
+	///		stats.add("");
+	///		stats.add("1024");
+	///
+	///		assert_eq!((stats.min, stats.max, stats.digits, stats.scale, stats.max_length), (-12.5, 1024.0, 4, 1, 5));
+	///		assert!(stats.plain_numbers);
+	/// }
+	/// ```
+	pub fn add(&mut self, value: &str) {
+		// the empty values are written as NULL, so they don't change the type of the column
+		if value.is_empty() {
+			return;
+		}
+
+		self.max_length = self.max_length.max(value.chars().count());
+
+		match NumericModel::parse(value) {
+			Some((number, format)) if is_plain(&format) => {
+				self.min = self.min.min(number);
+				self.max = self.max.max(number);
+				self.digits = self.digits.max(integer_digits(number));
+				self.scale = self.scale.max(format.decimals);
+			},
+			_ => self.plain_numbers = false,
+		}
+	}
+}
+
+impl fmt::Display for SqlType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SqlType::Integer => write!(f, "INTEGER"),
+			SqlType::BigInt => write!(f, "BIGINT"),
+			SqlType::Numeric(precision, scale) => write!(f, "NUMERIC({}, {})", precision, scale),
+			SqlType::Varchar(length) => write!(f, "VARCHAR({})", length),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Represents a column of the table
+pub struct SqlColumn {
+	/// The name of the column (e.g.: the field name)
+	pub name: String,
+	/// The type of the column
+	pub sql_type: SqlType,
+}
+
+impl SqlColumn {
+	/// Constructs a new SqlColumn
+	///
+	/// # Arguments
+	///
+	/// * `name: &str` - The name of the column.</br>
+	/// * `sql_type: SqlType` - The type of the column.</br>
+	pub fn new(name: &str, sql_type: SqlType) -> SqlColumn {
+		SqlColumn {
+			name: name.to_string(),
+			sql_type,
+		}
+	}
+
+	/// This function infers the column of a field from its Profile and the generated values of the field.
+	/// If the Profile generates numbers (see _Profile::is_numeric()_) and all the values are plain numbers, the column is an `INTEGER`,
+	/// `BIGINT` or `NUMERIC(precision, scale)` column that fits the analyzed and generated numbers,
+	/// otherwise the column is a `VARCHAR` column of the maximum length of the analyzed and generated values.
+	///
+	/// # Arguments
+	///
+	/// * `name: &str` - The name of the column.</br>
+	/// * `profile: &Profile` - The Profile of the field.</br>
+	/// * `values: &[&str]` - The generated values of the field.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	/// use test_data_generation::sql::{SqlColumn, SqlType};
+	///
+	/// fn main() {
+	///		let mut profile = Profile::new();
+	///		profile.analyze("12.50");
+	///		profile.analyze("3.99");
+	///
+	///		assert_eq!(SqlColumn::infer("price", &profile, &["7.25"]).sql_type, SqlType::Numeric(4, 2));
+	///		assert_eq!(SqlColumn::infer("price", &profile, &["7.25", "n/a"]).sql_type, SqlType::Varchar(5));
+	/// }
+	/// ```
+	pub fn infer(name: &str, profile: &Profile, values: &[&str]) -> SqlColumn {
+		let mut stats = SqlValueStats::new();
+		for value in values.iter() {
+			stats.add(value);
+		}
+
+		SqlColumn::infer_from(name, profile, &stats)
+	}
+
+	/// This function infers the column of a field, like _infer()_, but from the statistics of the generated values of the field
+	///
+	/// # Arguments
+	///
+	/// * `name: &str` - The name of the column.</br>
+	/// * `profile: &Profile` - The Profile of the field.</br>
+	/// * `values: &SqlValueStats` - The statistics of the generated values of the field.</br>
+	pub fn infer_from(name: &str, profile: &Profile, values: &SqlValueStats) -> SqlColumn {
+		let sql_type = SqlType::numeric(profile, values).unwrap_or_else(|| {
+			let analyzed = profile.sizes.keys().max().cloned().unwrap_or(0) as usize;
+			SqlType::Varchar(analyzed.max(values.max_length).max(1))
+		});
+
+		SqlColumn::new(name, sql_type)
+	}
+}
+
+/// This function quotes an identifier (e.g.: a table or column name) with double quotes, which are escaped by doubling them
+///
+/// # Arguments
+///
+/// * `name: &str` - The identifier.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::sql::quote_identifier;
+///
+/// fn main() {
+///		assert_eq!(quote_identifier("customer.name"), "\"customer.name\"");
+///		assert_eq!(quote_identifier("my \"table\""), "\"my \"\"table\"\"\"");
+/// }
+/// ```
+pub fn quote_identifier(name: &str) -> String {
+	format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// This function quotes a string literal with single quotes, which are escaped by doubling them
+///
+/// # Arguments
+///
+/// * `value: &str` - The string.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::sql::quote_literal;
+///
+/// fn main() {
+///		assert_eq!(quote_literal("O'Brian"), "'O''Brian'");
+/// }
+/// ```
+pub fn quote_literal(value: &str) -> String {
+	format!("'{}'", value.replace('\'', "''"))
+}
+
+/// This function escapes a value for the text format of the PostgreSQL `COPY` statement, (i.e.: backslashes, tabs, newlines and carriage returns)
+///
+/// # Arguments
+///
+/// * `value: &str` - The value.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::sql::copy_escape;
+///
+/// fn main() {
+///		assert_eq!(copy_escape("C:\\temp\tdir"), "C:\\\\temp\\tdir");
+/// }
+/// ```
+pub fn copy_escape(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len());
+
+	for c in value.chars() {
+		match c {
+			'\\' => escaped.push_str("\\\\"),
+			'\t' => escaped.push_str("\\t"),
+			'\n' => escaped.push_str("\\n"),
+			'\r' => escaped.push_str("\\r"),
+			_ => escaped.push(c),
+		}
+	}
+
+	escaped
+}
+
+/// This function returns the quoted names of the columns, separated by commas
+fn column_names(columns: &[SqlColumn]) -> String {
+	columns.iter().map(|c| quote_identifier(&c.name)).collect::<Vec<String>>().join(", ")
+}
+
+/// This function returns the error of a table without columns
+fn no_columns(table: &str) -> Error {
+	Error::Config(format!("The sql table {} must have at least 1 column", quote_identifier(table)))
+}
+
+/// This function returns the `CREATE TABLE` statement of the table
+///
+/// #Errors
+/// If there are no columns, an Error::Config is returned.
+///
+/// # Arguments
+///
+/// * `table: &str` - The name of the table.</br>
+/// * `columns: &[SqlColumn]` - The columns of the table.</br>
+pub fn create_table(table: &str, columns: &[SqlColumn]) -> Result<String, Error> {
+	if columns.is_empty() {
+		return Err(no_columns(table));
+	}
+
+	let definitions = columns.iter()
+		.map(|c| format!("  {} {}", quote_identifier(&c.name), c.sql_type))
+		.collect::<Vec<String>>();
+
+	Ok(format!("CREATE TABLE {} (\n{}\n);\n", quote_identifier(table), definitions.join(",\n")))
+}
+
+/// This function returns the `INSERT` statement of the records, (the numbers are written without quotes, and the empty numbers as `NULL`)
+///
+/// # Arguments
+///
+/// * `table: &str` - The name of the table.</br>
+/// * `columns: &[SqlColumn]` - The columns of the table.</br>
+/// * `records: &[Vec<String>]` - The records, with a value for each column.</br>
+pub fn insert(table: &str, columns: &[SqlColumn], records: &[Vec<String>]) -> String {
+	let rows = records.iter().map(|record| {
+		let values = columns.iter().zip(record.iter()).map(|(column, value)| {
+			if column.sql_type.is_numeric() && value.is_empty() {
+				"NULL".to_string()
+			} else if column.sql_type.is_numeric() {
+				value.clone()
+			} else {
+				quote_literal(value)
+			}
+		}).collect::<Vec<String>>();
+
+		format!("({})", values.join(", "))
+	}).collect::<Vec<String>>();
+
+	format!("INSERT INTO {} ({}) VALUES\n{};", quote_identifier(table), column_names(columns), rows.join(",\n"))
+}
+
+/// This function returns the PostgreSQL `COPY ... FROM STDIN` statement followed by the records in its text format, (ending with `\.`),
+/// where the empty numbers are written as `\N` (NULL)
+///
+/// # Arguments
+///
+/// * `table: &str` - The name of the table.</br>
+/// * `columns: &[SqlColumn]` - The columns of the table.</br>
+/// * `records: &[Vec<String>]` - The records, with a value for each column.</br>
+pub fn copy(table: &str, columns: &[SqlColumn], records: &[Vec<String>]) -> String {
+	format!("{}{}\\.\n", copy_statement(table, columns), copy_rows(columns, records))
+}
+
+/// This function returns the PostgreSQL `COPY ... FROM STDIN` statement, (without the records)
+fn copy_statement(table: &str, columns: &[SqlColumn]) -> String {
+	format!("COPY {} ({}) FROM STDIN;\n", quote_identifier(table), column_names(columns))
+}
+
+/// This function returns the records in the text format of the PostgreSQL `COPY` statement, (a line of tab separated values for each record)
+fn copy_rows(columns: &[SqlColumn], records: &[Vec<String>]) -> String {
+	let mut rows = String::new();
+
+	for record in records.iter() {
+		let values = columns.iter().zip(record.iter()).map(|(column, value)| {
+			if column.sql_type.is_numeric() && value.is_empty() {
+				"\\N".to_string()
+			} else {
+				copy_escape(value)
+			}
+		}).collect::<Vec<String>>();
+
+		rows.push_str(&values.join("\t"));
+		rows.push('\n');
+	}
+
+	rows
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn profile(entities: &[&str]) -> Profile {
+		let mut profile = Profile::new();
+		for entity in entities.iter() {
+			profile.analyze(entity);
+		}
+		profile
+	}
+
+	fn records(rows: &[&[&str]]) -> Vec<Vec<String>> {
+		rows.iter().map(|r| r.iter().map(|v| v.to_string()).collect()).collect()
+	}
+
+	#[test]
+	fn infer_numbers(){
+		assert_eq!(SqlColumn::infer("qty", &profile(&["1", "25", "-3"]), &["7"]).sql_type, SqlType::Integer);
+		assert_eq!(SqlColumn::infer("id", &profile(&["4000000000", "12"]), &["12"]).sql_type, SqlType::BigInt);
+		assert_eq!(SqlColumn::infer("price", &profile(&["129.00", "5.5"]), &[]).sql_type, SqlType::Numeric(5, 2));
+	}
+
+	#[test]
+	fn infer_strings(){
+		// leading zeros and thousands separators are kept by storing the numbers as strings
+		assert_eq!(SqlColumn::infer("zip", &profile(&["02108", "80202"]), &["00501"]).sql_type, SqlType::Varchar(5));
+		assert_eq!(SqlColumn::infer("amount", &profile(&["1,250", "3,499"]), &["2,000"]).sql_type, SqlType::Varchar(5));
+		assert_eq!(SqlColumn::infer("name", &profile(&["Aaron", "Abbey"]), &["Aaronson"]).sql_type, SqlType::Varchar(8));
+		assert_eq!(SqlColumn::infer("empty", &Profile::new(), &[]).sql_type, SqlType::Varchar(1));
+		// the integers beyond 2^53 aren't numbers, so that their digits are kept
+		assert_eq!(SqlColumn::infer("id", &profile(&["12345678901234567890", "98765432109876543210"]), &[]).sql_type, SqlType::Varchar(20));
+	}
+
+	#[test]
+	fn write_batched_inserts(){
+		let columns = vec![SqlColumn::new("name", SqlType::Varchar(7)), SqlColumn::new("age", SqlType::Integer)];
+		let records = records(&[&["Aaron", "42"], &["O'Brian", "7"], &["Abbey", "19"]]);
+		let mut sql = Vec::new();
+
+		SqlFormat::new().with_table("my \"people\"").with_batch_size(2).write(&mut sql, &columns, &records).unwrap();
+
+		assert_eq!(String::from_utf8(sql).unwrap(), concat!(
+			"CREATE TABLE \"my \"\"people\"\"\" (\n  \"name\" VARCHAR(7),\n  \"age\" INTEGER\n);\n\n",
+			"INSERT INTO \"my \"\"people\"\"\" (\"name\", \"age\") VALUES\n('Aaron', 42),\n('O''Brian', 7);\n",
+			"INSERT INTO \"my \"\"people\"\"\" (\"name\", \"age\") VALUES\n('Abbey', 19);\n",
+		));
+	}
+
+	#[test]
+	fn write_copy(){
+		let columns = vec![SqlColumn::new("path", SqlType::Varchar(20)), SqlColumn::new("note", SqlType::Varchar(20))];
+		let records = records(&[&["C:\\temp", "a\tb"], &["\\.", "line 1\nline 2"]]);
+		let mut sql = Vec::new();
+
+		SqlFormat::new().with_statement(SqlStatement::Copy).with_create_table(false).write(&mut sql, &columns, &records).unwrap();
+
+		assert_eq!(String::from_utf8(sql).unwrap(), "COPY \"test_data\" (\"path\", \"note\") FROM STDIN;\nC:\\\\temp\ta\\tb\n\\\\.\tline 1\\nline 2\n\\.\n");
+	}
+
+	#[test]
+	fn write_empty_numbers_as_null(){
+		let columns = vec![SqlColumn::new("name", SqlType::Varchar(5)), SqlColumn::new("age", SqlType::Integer)];
+		let records = records(&[&["", ""], &["Abbey", "19"]]);
+
+		let mut sql = Vec::new();
+		SqlFormat::new().with_create_table(false).write(&mut sql, &columns, &records).unwrap();
+		assert_eq!(String::from_utf8(sql).unwrap(), "INSERT INTO \"test_data\" (\"name\", \"age\") VALUES\n('', NULL),\n('Abbey', 19);\n");
+
+		let mut sql = Vec::new();
+		SqlFormat::new().with_create_table(false).with_statement(SqlStatement::Copy).write(&mut sql, &columns, &records).unwrap();
+		assert_eq!(String::from_utf8(sql).unwrap(), "COPY \"test_data\" (\"name\", \"age\") FROM STDIN;\n\t\\N\nAbbey\t19\n\\.\n");
+	}
+
+	#[test]
+	fn infer_numbers_with_empty_values(){
+		assert_eq!(SqlColumn::infer("qty", &profile(&["1", "25"]), &["7", ""]).sql_type, SqlType::Integer);
+	}
+
+	#[test]
+	fn no_columns(){
+		assert!(create_table("people", &[]).is_err());
+
+		for format in [SqlFormat::new(), SqlFormat::new().with_create_table(false).with_statement(SqlStatement::Copy)].iter() {
+			let mut sql = Vec::new();
+			match format.write(&mut sql, &[], &[vec![]]) {
+				Err(Error::Config(msg)) => assert_eq!(msg, "The sql table \"test_data\" must have at least 1 column"),
+				_ => panic!("expected a configuration error"),
+			}
+			assert!(sql.is_empty());
+		}
+	}
+
+	#[test]
+	fn zero_batch_size(){
+		match SqlFormat::new().with_batch_size(0).write(&mut Vec::new(), &[], &[]) {
+			Err(Error::Config(msg)) => assert_eq!(msg, "The sql batch size must be greater than 0"),
+			_ => panic!("expected a configuration error"),
+		}
+	}
+
+	#[test]
+	fn deserialize_defaults(){
+		let format: SqlFormat = serde_json::from_str(r#"{"table": "people", "statement": "Copy"}"#).unwrap();
+
+		assert_eq!(format, SqlFormat::new().with_table("people").with_statement(SqlStatement::Copy));
+	}
+}
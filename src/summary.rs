@@ -0,0 +1,241 @@
+//! The `summary` module provides the `ProfileSummary`, which is a report of what a Profile has learned from the sample data it analyzed.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::Profile;
+//!
+//! fn main() {
+//!		let mut profile = Profile::new();
+//!		profile.analyze("Smith, John");
+//!		profile.analyze("O'Brian, Henny");
+//!		profile.analyze("Dale, Danny");
+//!
+//!		// a human-readable report
+//!		println!("{}", profile.summary(5));
+//!
+//!		// a JSON report
+//!		println!("{}", profile.summary(5).serialize());
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use serde_json;
+
+use crate::Profile;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents a pattern of a ProfileSummary with its count and percent chance of occurrence
+pub struct PatternShare {
+	/// the symbolic pattern
+	pub pattern: String,
+	/// the number of analyzed entities that have the pattern
+	pub count: u32,
+	/// the percent of analyzed entities that have the pattern
+	pub percentage: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+/// Represents a summary of what a Profile has learned from the sample data it analyzed
+pub struct ProfileSummary {
+	/// the identifier of the Profile
+	pub id: Option<String>,
+	/// the number of sample data entities that have been analyzed
+	pub sample_count: u32,
+	/// the number of distinct patterns
+	pub distinct_patterns: usize,
+	/// the most frequent patterns, in decreasing order of occurrence
+	pub top_patterns: Vec<PatternShare>,
+	/// the percent of analyzed entities that have one of the top patterns
+	pub top_coverage: f64,
+	/// the length of the shortest pattern
+	pub size_min: u32,
+	/// the length of the longest pattern
+	pub size_max: u32,
+	/// the mean length of the patterns
+	pub size_mean: f64,
+	/// the percent of the analyzed chars for each pattern placeholder (character class)
+	pub char_classes: BTreeMap<char, f64>,
+	/// the estimated (Shannon) entropy of the patterns, in bits
+	pub pattern_entropy: f64,
+	/// the estimated (Shannon) entropy of the analyzed chars, in bits per char
+	pub char_entropy: f64,
+}
+
+/// This function returns the Shannon entropy (in bits) of a distribution of counts
+fn entropy<I: Iterator<Item = u64>>(counts: I) -> f64 {
+	let counts = counts.filter(|c| *c > 0).collect::<Vec<u64>>();
+	let total = counts.iter().sum::<u64>() as f64;
+
+	counts.iter().map(|c| {
+		let p = *c as f64 / total;
+		p * (1.0 / p).log2()
+	}).sum::<f64>()
+}
+
+/// This function returns the percent of the part of the total (0 if the total is 0)
+fn percentage(part: u64, total: u64) -> f64 {
+	if total == 0 {
+		0.0
+	} else {
+		part as f64 / total as f64 * 100.0
+	}
+}
+
+impl ProfileSummary {
+	/// Constructs a new ProfileSummary of a Profile
+	///
+	/// # Arguments
+	///
+	/// * `profile: &Profile` - The Profile to summarize.</br>
+	/// * `top_n: usize` - The number of most frequent patterns to report.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::{Profile, ProfileSummary};
+	///
+	/// fn main() {
+	///		let mut profile = Profile::new();
+	///		profile.analyze("One");
+	///		profile.analyze("Two");
+	///		profile.analyze("Three");
+	///
+	///		let summary = ProfileSummary::new(&profile, 1);
+	///
+	///		assert_eq!(summary.sample_count, 3);
+	///		assert_eq!(summary.size_max, 5);
+	/// }
+	/// ```
+	pub fn new(profile: &Profile, top_n: usize) -> ProfileSummary {
+		let sample_count = profile.patterns.values().sum::<u32>();
+
+		// rank the patterns by their count of occurrence (and then by the pattern to keep the order stable)
+		let mut ranked = profile.patterns.iter().collect::<Vec<(&String, &u32)>>();
+		ranked.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+		let top_patterns = ranked.iter().take(top_n).map(|(p, c)| {
+			PatternShare {
+				pattern: p.to_string(),
+				count: **c,
+				percentage: percentage(**c as u64, sample_count as u64),
+			}
+		}).collect::<Vec<PatternShare>>();
+		let top_count = top_patterns.iter().map(|p| p.count as u64).sum::<u64>();
+
+		let size_count = profile.sizes.values().map(|c| *c as u64).sum::<u64>();
+		let size_sum = profile.sizes.iter().map(|(s, c)| *s as u64 * *c as u64).sum::<u64>();
+
+		let mut classes: BTreeMap<char, u64> = BTreeMap::new();
+		let mut chars: BTreeMap<char, u64> = BTreeMap::new();
+		for (fact, count) in profile.facts.iter() {
+			*classes.entry(fact.pattern_placeholder).or_insert(0) += *count as u64;
+			*chars.entry(fact.key).or_insert(0) += *count as u64;
+		}
+		let char_count = profile.facts.total();
+
+		ProfileSummary {
+			id: profile.id.clone(),
+			sample_count,
+			distinct_patterns: profile.patterns.len(),
+			top_patterns,
+			top_coverage: percentage(top_count, sample_count as u64),
+			size_min: profile.sizes.keys().next().cloned().unwrap_or(0),
+			size_max: profile.sizes.keys().last().cloned().unwrap_or(0),
+			size_mean: if size_count == 0 { 0.0 } else { size_sum as f64 / size_count as f64 },
+			char_classes: classes.iter().map(|(k, c)| (*k, percentage(*c, char_count))).collect(),
+			pattern_entropy: entropy(profile.patterns.values().map(|c| *c as u64)),
+			char_entropy: entropy(chars.values().cloned()),
+		}
+	}
+
+	/// This function converts the ProfileSummary to a serialize JSON string.
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::Profile;
+	///
+	/// fn main() {
+	///		let mut profile = Profile::new();
+	///		profile.analyze("OK");
+	///
+	///		println!("{}", profile.summary(3).serialize());
+	///		// {"id":null,"sample_count":1,"distinct_patterns":1,"top_patterns":[{"pattern":"VC","count":1,"percentage":100.0}],"top_coverage":100.0,"size_min":2,"size_max":2,"size_mean":2.0,"char_classes":{"C":50.0,"V":50.0},"pattern_entropy":0.0,"char_entropy":1.0}
+	/// }
+	/// ```
+	pub fn serialize(&self) -> String {
+		serde_json::to_string(&self).unwrap()
+	}
+}
+
+impl fmt::Display for ProfileSummary {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Profile: {}", self.id.clone().unwrap_or_else(|| "(no id)".to_string()))?;
+		writeln!(f, "Samples analyzed: {}", self.sample_count)?;
+		writeln!(f, "Distinct patterns: {}", self.distinct_patterns)?;
+		writeln!(f, "Top {} patterns (coverage {:.2}%):", self.top_patterns.len(), self.top_coverage)?;
+
+		for share in self.top_patterns.iter() {
+			writeln!(f, "  {} {} ({:.2}%)", share.pattern, share.count, share.percentage)?;
+		}
+
+		writeln!(f, "Sizes: min {}, max {}, mean {:.2}", self.size_min, self.size_max, self.size_mean)?;
+		writeln!(f, "Character classes: {}", self.char_classes.iter().map(|(k, p)| format!("{} {:.2}%", k, p)).collect::<Vec<String>>().join(", "))?;
+		write!(f, "Entropy: {:.2} bits per pattern, {:.2} bits per char", self.pattern_entropy, self.char_entropy)
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn summary_of_profile(){
+		let mut profile = Profile::new_with_id("names".to_string());
+		profile.analyze("Smith, John");
+		profile.analyze("Dale, Danny");
+		profile.analyze("Dale, Danny");
+		profile.analyze("Rickets, Ronney");
+
+		let summary = profile.summary(1);
+
+		assert_eq!(summary.sample_count, 4);
+		assert_eq!(summary.distinct_patterns, 3);
+		assert_eq!(summary.top_patterns, vec![PatternShare { pattern: "CvcvpSCvccc".to_string(), count: 2, percentage: 50.0 }]);
+		assert_eq!(summary.top_coverage, 50.0);
+		assert_eq!(summary.size_min, 11);
+		assert_eq!(summary.size_max, 15);
+		assert_eq!(summary.size_mean, 12.0);
+		assert_eq!(summary.char_classes.get(&'S'), Some(&(4.0 / 48.0 * 100.0)));
+		assert_eq!(summary.pattern_entropy, 1.5);
+		assert!(summary.to_string().starts_with("Profile: names\nSamples analyzed: 4\n"));
+	}
+
+	#[test]
+	fn summary_of_empty_profile(){
+		let summary = Profile::new().summary(3);
+
+		assert_eq!(summary.sample_count, 0);
+		assert!(summary.top_patterns.is_empty());
+		assert_eq!(summary.top_coverage, 0.0);
+		assert_eq!(summary.size_mean, 0.0);
+		assert_eq!(summary.char_entropy, 0.0);
+	}
+
+	#[test]
+	fn summary_serialize(){
+		let mut profile = Profile::new();
+		profile.analyze("OK");
+
+		assert_eq!(profile.summary(3).serialize(), "{\"id\":null,\"sample_count\":1,\"distinct_patterns\":1,\"top_patterns\":[{\"pattern\":\"VC\",\"count\":1,\"percentage\":100.0}],\"top_coverage\":100.0,\"size_min\":2,\"size_max\":2,\"size_mean\":2.0,\"char_classes\":{\"C\":50.0,\"V\":50.0},\"pattern_entropy\":0.0,\"char_entropy\":1.0}");
+	}
+}
@@ -0,0 +1,576 @@
+//! The `tokens` module provides the `TokenModel`, which is used by a Profile in the token-aware mode.
+//! In the token-aware mode, the entities are split into tokens on white spaces and punctuation (e.g.: "123 Main Street" has the tokens "123", "Main" and "Street"),
+//! and the tokens in the same position (slot) of the entities with the same layout (i.e.: the same separators) are analyzed together,
+//! so that each token is generated independently with a plausible word shape.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//!
+//! use test_data_generation::Profile;
+//!
+//! fn main() {
+//!		// half of the generated tokens are whole tokens of the sample data
+//!		let mut profile = Profile::new_with_token_mode(0.5);
+//!		profile.analyze("Smith, John");
+//!		profile.analyze("O'Brian, Henny");
+//!		profile.analyze("Dale, Danny");
+//!		profile.analyze("Rickets, Ronney");
+//!		profile.pre_generate();
+//!
+//!		println!("The generated name is {:?}", profile.generate().unwrap());
+//! }
+//! ```
+
+use rand::Rng;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use crate::engine::SymbolClass;
+use crate::shared::AliasTable;
+use crate::{Constraints, Error, Profile};
+
+/// The maximum number of distinct tokens that are kept in the vocabulary of a slot
+pub const MAX_TOKEN_VOCABULARY: usize = 1024;
+
+lazy_static! {
+	// the runs of white spaces and punctuation that separate the tokens
+	static ref SEPARATORS: Regex = Regex::new(r"[\s\p{P}]+").unwrap();
+}
+
+/// This function splits an entity into its tokens and the separators around the tokens.
+/// There is always one more separator than there are tokens, (the first and last separators are empty unless the entity starts or ends with a separator).
+///
+/// # Arguments
+///
+/// * `entity: &str` - The textual str of the value to split.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use test_data_generation::tokens::tokenize;
+///
+/// fn main() {
+///		let (tokens, separators) = tokenize("Smith, John");
+///
+///		assert_eq!(tokens, vec!["Smith", "John"]);
+///		assert_eq!(separators, vec!["", ", ", ""]);
+/// }
+/// ```
+pub fn tokenize(entity: &str) -> (Vec<String>, Vec<String>) {
+	let mut tokens = Vec::new();
+	let mut separators = Vec::new();
+	let mut start = 0;
+	let mut separator = String::new();
+
+	for m in SEPARATORS.find_iter(entity) {
+		if m.start() > start {
+			separators.push(separator);
+			tokens.push(entity[start..m.start()].to_string());
+			separator = String::new();
+		}
+
+		separator.push_str(m.as_str());
+		start = m.end();
+	}
+
+	if entity.len() > start {
+		separators.push(separator);
+		tokens.push(entity[start..].to_string());
+		separator = String::new();
+	}
+
+	separators.push(separator);
+
+	(tokens, separators)
+}
+
+/// This function adds the count of a token, unless the vocabulary already has MAX_TOKEN_VOCABULARY other tokens
+fn count_token(vocabulary: &mut BTreeMap<String, u32>, token: String, count: u32) {
+	if vocabulary.len() < MAX_TOKEN_VOCABULARY || vocabulary.contains_key(&token) {
+		*vocabulary.entry(token).or_insert(0) += count;
+	}
+}
+
+/// This function returns the alias table of the tokens of a vocabulary that satisfy the predicate, weighted by their count of occurrence
+fn vocabulary_table(vocabulary: &BTreeMap<String, u32>, predicate: &dyn Fn(&str) -> bool) -> AliasTable<String> {
+	AliasTable::new(vocabulary.iter().filter(|(t, _c)| predicate(t)).map(|(t, c)| (t.to_string(), *c as u64)).collect())
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents the layout of an entity in the token-aware mode, which is the list of separators around its tokens
+pub struct TokenLayout {
+	/// the separators around the tokens (one more than the number of tokens)
+	pub separators: Vec<String>,
+	/// the number of analyzed entities that have the layout
+	pub count: u32,
+	/// the slots of the tokens of the layout, in the order of the tokens (one less than the number of separators)
+	pub slots: Vec<TokenSlot>,
+}
+
+impl TokenLayout {
+	/// Constructs a new TokenLayout with a slot for each of the tokens between the separators
+	fn new(separators: Vec<String>, classes: &[SymbolClass]) -> Result<TokenLayout, Error> {
+		let slots = (1..separators.len()).map(|_| TokenSlot::new(classes)).collect::<Result<Vec<TokenSlot>, Error>>()?;
+
+		Ok(TokenLayout {
+			separators,
+			count: 0,
+			slots,
+		})
+	}
+
+	/// This function returns the lowest and highest length of the tokens of each slot
+	fn bounds(&self) -> Vec<(usize, usize)> {
+		self.slots.iter().map(|slot| {
+			(slot.profile.sizes.keys().next().cloned().unwrap_or(0) as usize, slot.profile.sizes.keys().last().cloned().unwrap_or(0) as usize)
+		}).collect()
+	}
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents the tokens in the same position (slot) of the analyzed entities that have the same layout
+pub struct TokenSlot {
+	/// the Profile of the tokens in the slot
+	pub profile: Profile,
+	/// the distinct tokens in the slot with their count of occurrence.
+	/// Only the first MAX_TOKEN_VOCABULARY distinct tokens of the slot are kept.
+	pub vocabulary: BTreeMap<String, u32>,
+	/// the alias table of the vocabulary that is used to select a whole token (not archived)
+	#[serde(skip)]
+	vocabulary_table: Option<AliasTable<String>>,
+}
+
+impl TokenSlot {
+	/// Constructs a new TokenSlot whose Profile uses the symbol classes
+	fn new(classes: &[SymbolClass]) -> Result<TokenSlot, Error> {
+		Ok(TokenSlot {
+			profile: Profile::new_with_symbol_classes(classes)?,
+			vocabulary: BTreeMap::new(),
+			vocabulary_table: None,
+		})
+	}
+
+	/// This function analyzes a token of the slot
+	fn analyze(&mut self, token: &str) {
+		self.profile.analyze(token);
+		count_token(&mut self.vocabulary, token.to_string(), 1);
+		self.vocabulary_table = None;
+	}
+
+	/// This function merges the Profile and the vocabulary of another TokenSlot into this TokenSlot
+	fn merge(&mut self, other: &TokenSlot) {
+		self.profile.merge(&other.profile);
+		for (token, cnt) in other.vocabulary.iter() {
+			count_token(&mut self.vocabulary, token.to_string(), *cnt);
+		}
+		self.vocabulary_table = None;
+	}
+
+	/// This function prepares the Profile and the vocabulary of the slot for data generation
+	fn pre_generate(&mut self) {
+		self.profile.pre_generate();
+		self.vocabulary_table = Some(vocabulary_table(&self.vocabulary, &|_t| true));
+	}
+
+	/// This function seeds the Profile of the slot by the random number generator (if it isn't seeded yet),
+	/// so that seeded data generation is reproducible, and prepares the Profile for data generation
+	fn prepare_profile<R: Rng>(&mut self, rng: &mut R) {
+		if self.profile.rng.is_none() {
+			self.profile.set_seed(rng.gen());
+		}
+
+		if self.profile.pattern_ranks.is_empty() {
+			self.profile.pre_generate();
+		}
+	}
+
+	/// This function generates a token, which is either a whole token of the vocabulary or a token that is generated by the Profile of the slot
+	fn generate<R: Rng>(&mut self, rng: &mut R, real_token_ratio: f64) -> Result<String, Error> {
+		if rng.gen::<f64>() < real_token_ratio {
+			if self.vocabulary_table.is_none() {
+				self.vocabulary_table = Some(vocabulary_table(&self.vocabulary, &|_t| true));
+			}
+
+			if let Some(token) = self.vocabulary_table.as_ref().unwrap().sample(rng) {
+				return Ok(token.to_string());
+			}
+		}
+
+		self.prepare_profile(rng);
+		self.profile.generate()
+	}
+
+	/// This function generates a token, like _generate()_, but with the length of the token limited and its chars filtered
+	fn generate_with<R: Rng>(&mut self, rng: &mut R, real_token_ratio: f64, constraints: &Constraints, lengths: RangeInclusive<usize>, filter: &dyn Fn(&char) -> bool) -> Result<String, Error> {
+		if rng.gen::<f64>() < real_token_ratio {
+			let vocabulary = vocabulary_table(&self.vocabulary, &|t| lengths.contains(&t.chars().count()) && t.chars().all(|c| filter(&c)));
+
+			if let Some(token) = vocabulary.sample(rng) {
+				return Ok(token.to_string());
+			}
+		}
+
+		self.prepare_profile(rng);
+		self.profile.generate_with(&constraints.for_part(lengths))
+	}
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+/// Represents the model of the tokens of the entities that a Profile analyzed in the token-aware mode
+pub struct TokenModel {
+	/// The probability (0.0 to 1.0) that a generated token is a whole token of the sample data,
+	/// instead of a token that is generated from the patterns of its slot
+	pub real_token_ratio: f64,
+	/// The distinct layouts of the analyzed entities, each with the slots of its tokens
+	pub layouts: Vec<TokenLayout>,
+	/// The alias table of the layouts that is used to select the layout of the generated data (not archived)
+	#[serde(skip)]
+	layout_table: Option<AliasTable<usize>>,
+}
+
+impl TokenModel {
+	/// Constructs a new TokenModel
+	///
+	/// # Arguments
+	///
+	/// * `real_token_ratio: f64` - The probability (0.0 to 1.0) that a generated token is a whole token of the sample data.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::tokens::TokenModel;
+	///
+	/// fn main() {
+	///		let model = TokenModel::new(0.25);
+	///
+	///		assert!(model.layouts.is_empty());
+	/// }
+	/// ```
+	pub fn new(real_token_ratio: f64) -> TokenModel {
+		TokenModel {
+			real_token_ratio: real_token_ratio.clamp(0.0, 1.0),
+			layouts: Vec::new(),
+			layout_table: None,
+		}
+	}
+
+	/// This function analyzes the tokens of an entity in the slots of its layout
+	///
+	/// # Arguments
+	///
+	/// * `entity: &str` - The textual str of the value to analyze.</br>
+	/// * `classes: &[SymbolClass]` - The user-defined symbol classes that are used to analyze the tokens.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	///
+	/// use test_data_generation::tokens::TokenModel;
+	///
+	/// fn main() {
+	///		let mut model = TokenModel::new(0.0);
+	///		model.analyze("123 Main Street", &[]).unwrap();
+	///
+	///		assert_eq!(model.layouts[0].slots.len(), 3);
+	///		assert_eq!(model.layouts[0].slots[2].vocabulary.get("Street"), Some(&1));
+	/// }
+	/// ```
+	pub fn analyze(&mut self, entity: &str, classes: &[SymbolClass]) -> Result<(), Error> {
+		let (tokens, separators) = tokenize(entity);
+
+		let idx = match self.layouts.iter().position(|l| l.separators == separators) {
+			Some(idx) => idx,
+			None => {
+				self.layouts.push(TokenLayout::new(separators, classes)?);
+				self.layouts.len() - 1
+			},
+		};
+		self.layout_table = None;
+
+		let layout = &mut self.layouts[idx];
+		layout.count += 1;
+
+		for (slot, token) in layout.slots.iter_mut().zip(tokens.iter()) {
+			slot.analyze(token);
+		}
+
+		Ok(())
+	}
+
+	/// This function merges (unions) the layouts and their slots of another TokenModel into this TokenModel
+	///
+	/// # Arguments
+	///
+	/// * `other: &TokenModel` - The TokenModel to merge into this TokenModel.</br>
+	pub fn merge(&mut self, other: &TokenModel) {
+		for other_layout in other.layouts.iter() {
+			match self.layouts.iter_mut().find(|l| l.separators == other_layout.separators) {
+				Some(layout) => {
+					layout.count += other_layout.count;
+					for (slot, other_slot) in layout.slots.iter_mut().zip(other_layout.slots.iter()) {
+						slot.merge(other_slot);
+					}
+				},
+				None => self.layouts.push(other_layout.clone()),
+			}
+		}
+		self.layout_table = None;
+	}
+
+	/// This function prepares the layouts and the Profiles of their slots for data generation
+	pub fn pre_generate(&mut self) {
+		self.layout_table = Some(self.new_layout_table());
+
+		for slot in self.layouts.iter_mut().flat_map(|l| l.slots.iter_mut()) {
+			slot.pre_generate();
+		}
+	}
+
+	/// This function generates an entity by selecting a layout and generating each of its tokens independently
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator, which also seeds the Profiles of the slots.</br>
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use rand::SeedableRng;
+	/// use rand::rngs::StdRng;
+	/// use test_data_generation::tokens::TokenModel;
+	///
+	/// fn main() {
+	///		let mut model = TokenModel::new(1.0);
+	///		model.analyze("Smith, John", &[]).unwrap();
+	///
+	///		assert_eq!(model.generate(&mut StdRng::seed_from_u64(1)).unwrap(), "Smith, John");
+	/// }
+	/// ```
+	pub fn generate<R: Rng>(&mut self, rng: &mut R) -> Result<String, Error> {
+		if self.layout_table.is_none() {
+			self.layout_table = Some(self.new_layout_table());
+		}
+
+		let idx = match self.layout_table.as_ref().unwrap().sample(rng) {
+			Some(idx) => *idx,
+			None => return Err(Error::EmptyProfile("The token model has no layouts. Analyze some data first.".to_string())),
+		};
+
+		let real_token_ratio = self.real_token_ratio;
+		let layout = &mut self.layouts[idx];
+		let mut generated = String::new();
+
+		for (separator, slot) in layout.separators.iter().zip(layout.slots.iter_mut()) {
+			generated.push_str(separator);
+			generated.push_str(&slot.generate(rng, real_token_ratio)?);
+		}
+		generated.push_str(layout.separators.last().unwrap());
+
+		Ok(generated)
+	}
+
+	/// This function generates an entity, like _generate()_, but only with the layouts whose separators satisfy the filter, and with the lengths of
+	/// the tokens limited, so that the length of the entity satisfies the length constraints. The whole tokens are selected from the tokens
+	/// that satisfy the constraints, and the other tokens are generated by the Profiles of the slots with the length and char constraints.
+	/// NOTE: The regular expression and the forbidden values of the constraints aren't applied, since they apply to the whole entity.
+	///
+	/// # Arguments
+	///
+	/// * `rng: &mut R` - The random number generator, which also seeds the Profiles of the slots.</br>
+	/// * `constraints: &Constraints` - The constraints that the entity must satisfy.</br>
+	/// * `filter: &dyn Fn(&char) -> bool` - The filter of the chars of the entity, (i.e.: the forbidden chars and the char classes of the constraints).</br>
+	///
+	/// #Errors
+	/// If none of the layouts can satisfy the constraints, an Error::Unsatisfiable is returned.
+	///
+	/// # Example
+	///
+	/// ```
+	/// extern crate test_data_generation;
+	/// extern crate rand;
+	///
+	/// use rand::SeedableRng;
+	/// use rand::rngs::StdRng;
+	/// use test_data_generation::Constraints;
+	/// use test_data_generation::tokens::TokenModel;
+	///
+	/// fn main() {
+	///		let mut model = TokenModel::new(1.0);
+	///		model.analyze("Smith, John", &[]).unwrap();
+	///		model.analyze("Dale Danny", &[]).unwrap();
+	///
+	///		let constraints = Constraints::new().with_forbidden_chars(&[',']);
+	///		let generated = model.generate_with(&mut StdRng::seed_from_u64(1), &constraints, &|c: &char| *c != ',').unwrap();
+	///
+	///		assert_eq!(generated, "Dale Danny");
+	/// }
+	/// ```
+	pub fn generate_with<R: Rng>(&mut self, rng: &mut R, constraints: &Constraints, filter: &dyn Fn(&char) -> bool) -> Result<String, Error> {
+		let lengths = constraints.lengths();
+		let separators_len = |separators: &[String]| separators.iter().map(|s| s.chars().count()).sum::<usize>();
+
+		// restrict the layouts to those whose separators satisfy the filter, and that can have a length that satisfies the constraints
+		let layouts = AliasTable::new(self.layouts.iter().enumerate().filter(|(_idx, layout)| {
+			if !layout.separators.iter().all(|s| s.chars().all(|c| filter(&c))) {
+				return false;
+			}
+
+			let bounds = layout.bounds();
+			let len = separators_len(&layout.separators);
+			len + bounds.iter().map(|b| b.0).sum::<usize>() <= *lengths.end() && len + bounds.iter().map(|b| b.1).sum::<usize>() >= *lengths.start()
+		}).map(|(idx, layout)| (idx, layout.count as u64)).collect());
+
+		let idx = match layouts.sample(rng) {
+			Some(idx) => *idx,
+			None => return Err(Error::Unsatisfiable("None of the layouts of the token model satisfies the constraints".to_string())),
+		};
+
+		let real_token_ratio = self.real_token_ratio;
+		let layout = &mut self.layouts[idx];
+		let bounds = layout.bounds();
+		let mut generated = String::new();
+
+		for (idx, slot) in layout.slots.iter_mut().enumerate() {
+			generated.push_str(&layout.separators[idx]);
+
+			// the length of the token must leave room for the rest of the separators and the tokens
+			let rest_min = bounds[idx + 1..].iter().map(|b| b.0).sum::<usize>();
+			let rest_max = bounds[idx + 1..].iter().map(|b| b.1).sum::<usize>();
+			let rest = generated.chars().count() + separators_len(&layout.separators[idx + 1..]);
+			let token_lengths = lengths.start().saturating_sub(rest + rest_max)..=lengths.end().saturating_sub(rest + rest_min);
+
+			generated.push_str(&slot.generate_with(rng, real_token_ratio, constraints, token_lengths, filter)?);
+		}
+		generated.push_str(layout.separators.last().unwrap());
+
+		Ok(generated)
+	}
+
+	/// This function clears the random number generators of the Profiles of the slots, so that they are seeded again when data is generated
+	pub(crate) fn clear_seeds(&mut self) {
+		for slot in self.layouts.iter_mut().flat_map(|l| l.slots.iter_mut()) {
+			slot.profile.rng = None;
+		}
+	}
+
+	/// This function returns the alias table of the layouts, weighted by their count of occurrence
+	fn new_layout_table(&self) -> AliasTable<usize> {
+		AliasTable::new(self.layouts.iter().enumerate().map(|(idx, l)| (idx, l.count as u64)).collect())
+	}
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand::rngs::StdRng;
+
+	#[test]
+	fn tokenize_separators(){
+		assert_eq!(tokenize("123 Main Street"), (vec!["123".to_string(), "Main".to_string(), "Street".to_string()], vec!["".to_string(), " ".to_string(), " ".to_string(), "".to_string()]));
+		assert_eq!(tokenize(" (555) 123-4567"), (vec!["555".to_string(), "123".to_string(), "4567".to_string()], vec![" (".to_string(), ") ".to_string(), "-".to_string(), "".to_string()]));
+		assert_eq!(tokenize(""), (Vec::new(), vec!["".to_string()]));
+		assert_eq!(tokenize("..."), (Vec::new(), vec!["...".to_string()]));
+	}
+
+	#[test]
+	fn analyze_layouts(){
+		let mut model = TokenModel::new(0.0);
+		model.analyze("Smith, John", &[]).unwrap();
+		model.analyze("Dale, Danny", &[]).unwrap();
+		model.analyze("Madonna", &[]).unwrap();
+
+		assert_eq!(model.layouts.len(), 2);
+		assert_eq!(model.layouts[0].count, 2);
+		assert_eq!(model.layouts[0].slots.len(), 2);
+		assert_eq!(model.layouts[0].slots[0].profile.pattern_total, 2);
+		assert_eq!(model.layouts[0].slots[1].vocabulary.keys().collect::<Vec<&String>>(), vec!["Danny", "John"]);
+		assert_eq!(model.layouts[1].slots.len(), 1);
+		assert_eq!(model.layouts[1].slots[0].vocabulary.keys().collect::<Vec<&String>>(), vec!["Madonna"]);
+	}
+
+	#[test]
+	fn analyze_slots_per_layout(){
+		// the first token is the last name in one layout and the first name in the other layout
+		let mut model = TokenModel::new(1.0);
+		model.analyze("Smith, John", &[]).unwrap();
+		model.analyze("Danny Dale", &[]).unwrap();
+
+		assert_eq!(model.layouts[0].slots[0].vocabulary.keys().collect::<Vec<&String>>(), vec!["Smith"]);
+		assert_eq!(model.layouts[1].slots[0].vocabulary.keys().collect::<Vec<&String>>(), vec!["Danny"]);
+
+		let mut rng = StdRng::seed_from_u64(3);
+		for _ in 0..20 {
+			let generated = model.generate(&mut rng).unwrap();
+			assert!(generated == "Smith, John" || generated == "Danny Dale");
+		}
+	}
+
+	#[test]
+	fn analyze_vocabulary_limit(){
+		let mut model = TokenModel::new(1.0);
+		for i in 0..(MAX_TOKEN_VOCABULARY + 10) {
+			model.analyze(&format!("x{}", i), &[]).unwrap();
+		}
+		model.analyze("x0", &[]).unwrap();
+
+		assert_eq!(model.layouts[0].count, MAX_TOKEN_VOCABULARY as u32 + 11);
+		assert_eq!(model.layouts[0].slots[0].vocabulary.len(), MAX_TOKEN_VOCABULARY);
+		assert_eq!(model.layouts[0].slots[0].vocabulary["x0"], 2);
+	}
+
+	#[test]
+	fn generate_tokens_per_slot(){
+		let mut model = TokenModel::new(0.5);
+		for entity in ["Smith, John", "Dale, Danny", "Rickets, Ronney"].iter() {
+			model.analyze(entity, &[]).unwrap();
+		}
+		model.pre_generate();
+
+		let mut rng = StdRng::seed_from_u64(7);
+		for _ in 0..20 {
+			let generated = model.generate(&mut rng).unwrap();
+			let (tokens, separators) = tokenize(&generated);
+
+			assert_eq!(separators, vec!["", ", ", ""]);
+			assert!(tokens[1].starts_with(|c: char| c.is_uppercase()));
+		}
+	}
+
+	#[test]
+	fn generate_empty_model(){
+		match TokenModel::new(0.5).generate(&mut StdRng::seed_from_u64(1)) {
+			Err(Error::EmptyProfile(_)) => {},
+			_ => panic!("expected an empty profile error"),
+		}
+	}
+
+	#[test]
+	fn merge_models(){
+		let mut model = TokenModel::new(1.0);
+		model.analyze("Smith, John", &[]).unwrap();
+		let mut other = TokenModel::new(1.0);
+		other.analyze("Dale, Danny", &[]).unwrap();
+		other.analyze("123 Main Street", &[]).unwrap();
+
+		model.merge(&other);
+
+		assert_eq!(model.layouts.len(), 2);
+		assert_eq!(model.layouts[0].count, 2);
+		assert_eq!(model.layouts[0].slots[0].vocabulary.len(), 2);
+		assert_eq!(model.layouts[1].slots.len(), 3);
+		assert_eq!(model.layouts[1].slots[0].vocabulary.len(), 1);
+	}
+}
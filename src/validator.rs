@@ -0,0 +1,238 @@
+//! The `validator` module provides the functionality to derive a regular expression from the patterns that a Profile has learned,
+//! so that the same Profile that generates the test data can also validate data (e.g.: in API contract tests).
+//! Each pattern placeholder is replaced by the set of chars that have been analyzed for the placeholder (see _Profile::to_regex()_).
+//! If the Profile generates values of a semantic type or numbers, the regular expression also matches the formats of the generated values.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate test_data_generation;
+//! extern crate regex;
+//!
+//! use regex::Regex;
+//! use test_data_generation::{Profile, RegexForm};
+//!
+//! fn main() {
+//!		let mut profile = Profile::new();
+//!		profile.analyze("01/04/2017");
+//!		profile.analyze("12/21/2017");
+//!
+//!		let validator = Regex::new(&profile.to_regex(RegexForm::Exact).unwrap()).unwrap();
+//!
+//!		assert!(validator.is_match("02/01/2017"));
+//!		assert!(!validator.is_match("2017-01-02"));
+//! }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::generalization::run_lengths;
+use crate::{Error, Profile};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Represents the form of the regular expression that is derived from a Profile
+pub enum RegexForm {
+	/// An alternation of the exact patterns that have been analyzed, (e.g.: `^(?:[A-Z][a-z]{2}|[0-9]{3})$`)
+	Exact,
+	/// An alternation of the generalized (run-length) patterns, which allows any number of repetitions of a run
+	/// between the lowest and highest number that has been analyzed, (e.g.: `^(?:[A-Z][a-z]{1,4})$`)
+	Generalized,
+}
+
+/// This function returns the regex syntax of a char that can be used in a character class
+fn escape_char(c: char) -> String {
+	if c.is_alphanumeric() {
+		c.to_string()
+	} else {
+		format!("\\x{{{:X}}}", c as u32)
+	}
+}
+
+/// This function returns the regex character class of a set of chars, in which 3 or more consecutive chars are written as a range (e.g.: `[0-9]`)
+///
+/// # Arguments
+///
+/// * `chars: &BTreeSet<char>` - The set of chars.</br>
+///
+/// # Example
+///
+/// ```
+/// extern crate test_data_generation;
+///
+/// use std::collections::BTreeSet;
+/// use test_data_generation::validator::char_class;
+///
+/// fn main() {
+///		let chars = "abcdxz-".chars().collect::<BTreeSet<char>>();
+///
+///		assert_eq!(char_class(&chars), "[\\x{2D}a-dxz]");
+/// }
+/// ```
+pub fn char_class(chars: &BTreeSet<char>) -> String {
+	if chars.len() == 1 {
+		return escape_char(*chars.iter().next().unwrap());
+	}
+
+	let mut ranges: Vec<(char, char)> = Vec::new();
+	for c in chars.iter() {
+		match ranges.last_mut() {
+			Some(range) if (range.1 as u32) + 1 == *c as u32 => range.1 = *c,
+			_ => ranges.push((*c, *c)),
+		}
+	}
+
+	let class = ranges.iter().map(|(start, end)| {
+		match *end as u32 - *start as u32 {
+			0 => escape_char(*start),
+			1 => format!("{}{}", escape_char(*start), escape_char(*end)),
+			_ => format!("{}-{}", escape_char(*start), escape_char(*end)),
+		}
+	}).collect::<String>();
+
+	format!("[{}]", class)
+}
+
+/// This function returns the regex quantifier of the lowest and highest number of repetitions
+fn quantifier(min: u32, max: u32) -> String {
+	match (min, max) {
+		(1, 1) => String::new(),
+		(min, max) if min == max => format!("{{{}}}", min),
+		(min, max) => format!("{{{},{}}}", min, max),
+	}
+}
+
+/// This function returns the regular expression of the patterns of a Profile in the specified form.
+/// The patterns are in decreasing order of occurrence, and an EmptyProfile Error is returned if the Profile hasn't analyzed any data.
+/// If the Profile generates values of a semantic type or numbers, the formats of the generated values precede the patterns, so that the Profile validates its own test data.
+///
+/// # Arguments
+///
+/// * `profile: &Profile` - The Profile.</br>
+/// * `form: RegexForm` - The form of the regular expression.</br>
+pub fn profile_regex(profile: &Profile, form: RegexForm) -> Result<String, Error> {
+	if profile.patterns.is_empty() {
+		return Err(Error::EmptyProfile(format!("The profile {:?} has no patterns. Analyze some data first.", profile.id.clone().unwrap_or_default())));
+	}
+
+	// the chars that have been analyzed for each pattern placeholder
+	let mut chars: BTreeMap<char, BTreeSet<char>> = BTreeMap::new();
+	for (fact, _count) in profile.facts.iter() {
+		chars.entry(fact.pattern_placeholder).or_default().insert(fact.key);
+	}
+	let classes = chars.iter().map(|(p, c)| (*p, char_class(c))).collect::<BTreeMap<char, String>>();
+	let class_of = |placeholder: char| classes.get(&placeholder).cloned().unwrap_or_else(|| escape_char(placeholder));
+
+	// the values of a semantic type and the numbers are generated in their formats instead of the patterns (see _Profile::generate()_)
+	let generated = match profile.semantic.generated_type() {
+		Some(semantic_type) => profile.semantic.regex_alternatives(semantic_type),
+		None if profile.numeric.is_numeric() => profile.numeric.regex_alternatives(),
+		None => Vec::new(),
+	};
+
+	let patterns = match form {
+		RegexForm::Exact => {
+			let mut ranked = profile.patterns.iter().collect::<Vec<(&String, &u32)>>();
+			ranked.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+			ranked.iter().map(|(pattern, _count)| {
+				run_lengths(pattern).iter().map(|(placeholder, len)| format!("{}{}", class_of(*placeholder), quantifier(*len, *len))).collect::<String>()
+			}).collect::<Vec<String>>()
+		},
+		RegexForm::Generalized => {
+			profile.generalize().patterns.iter().map(|pattern| {
+				pattern.runs.iter().map(|run| format!("{}{}", class_of(run.symbol), quantifier(run.min(), run.max()))).collect::<String>()
+			}).collect::<Vec<String>>()
+		},
+	};
+
+	// distinct placeholders can have the same chars, so remove the duplicate alternatives
+	let mut seen = BTreeSet::new();
+	let alternatives = generated.into_iter().chain(patterns).filter(|a| seen.insert(a.clone())).collect::<Vec<String>>();
+
+	Ok(format!("^(?:{})$", alternatives.join("|")))
+}
+
+// Unit Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use regex::Regex;
+
+	#[test]
+	fn char_class_ranges(){
+		assert_eq!(char_class(&"7".chars().collect()), "7");
+		assert_eq!(char_class(&" ".chars().collect()), "\\x{20}");
+		assert_eq!(char_class(&"0123456789".chars().collect()), "[0-9]");
+		assert_eq!(char_class(&"abxyzé".chars().collect()), "[abx-zé]");
+	}
+
+	#[test]
+	fn exact_regex(){
+		let mut profile = Profile::new();
+		profile.analyze("Smith, John");
+		profile.analyze("Dale, Danny");
+		profile.analyze("Dale, Danny");
+		profile.analyze("42");
+
+		let regex = profile.to_regex(RegexForm::Exact).unwrap();
+
+		assert!(regex.starts_with("^(?:[DJS][aeio][hl-nty][aeio]\\x{2C}\\x{20}[DJS]"));
+		assert!(regex.contains("|[24]{2}|"));
+
+		let validator = Regex::new(&regex).unwrap();
+		for value in ["Smith, John", "Dale, Danny", "42", "Sale, Donny", "24"].iter() {
+			assert!(validator.is_match(value), "{} should match {}", value, regex);
+		}
+		for value in ["Smith,John", "Smith, Johnny", "43", "smith, john", ""].iter() {
+			assert!(!validator.is_match(value), "{} should not match {}", value, regex);
+		}
+	}
+
+	#[test]
+	fn generalized_regex(){
+		let mut profile = Profile::new();
+		profile.analyze("Ab1");
+		profile.analyze("Abbb12");
+		profile.analyze("Ab123");
+
+		let regex = profile.to_regex(RegexForm::Generalized).unwrap();
+		assert_eq!(regex, "^(?:Ab{1,3}[1-3]{1,3})$");
+
+		let validator = Regex::new(&regex).unwrap();
+		assert!(validator.is_match("Abb3"));
+		assert!(!validator.is_match("Abbbb1"));
+	}
+
+	#[test]
+	fn generated_values_regex(){
+		let mut phones = Profile::new();
+		phones.analyze("(555) 123-4567");
+		phones.analyze("(212) 555-0199");
+		let mut amounts = Profile::new();
+		amounts.analyze("1,250.00");
+		amounts.analyze("-3,499.95");
+		amounts.analyze("12.50");
+
+		for profile in [&mut phones, &mut amounts].iter_mut() {
+			profile.pre_generate();
+
+			for form in [RegexForm::Exact, RegexForm::Generalized].iter() {
+				let regex = profile.to_regex(*form).unwrap();
+				let validator = Regex::new(&regex).unwrap();
+
+				for _ in 0..50 {
+					let generated = profile.generate().unwrap();
+					assert!(validator.is_match(&generated), "{} should match {}", generated, regex);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn empty_profile_regex(){
+		match Profile::new().to_regex(RegexForm::Exact) {
+			Err(Error::EmptyProfile(_)) => {},
+			_ => panic!("expected an empty profile error"),
+		}
+	}
+}
@@ -11,7 +11,7 @@ mod tests {
     fn test_pattern_definition_analyze_multithread(){
         let words = vec!("word-one".to_string(),"word-two".to_string(),"word-three".to_string(),"word-four".to_string(),"word-five".to_string());
 
-        let results = Xtest::analyze_entities(words);
+        let results = Xtest::analyze_entities(words).unwrap();
 
         println!("{:?}", results);
         assert_eq!(results.len(), 5);
@@ -19,16 +19,16 @@ mod tests {
 		let mut dsp = DataSampleParser::new_with(&String::from("./tests/config/tdg.yaml"));
 
 		info!("Demo ...");
-		println!("generate date:{}", dsp.demo_date());
-		println!("generate person:{}", dsp.demo_person_name());
+		println!("generate date:{}", dsp.demo_date().unwrap());
+		println!("generate person:{}", dsp.demo_person_name().unwrap());
 
     	info!("Analyzing CSV file ...");
    		println!("reading csv file: {}", dsp.analyze_csv_file(&String::from("./tests/samples/sample-01.csv")).unwrap());
 
    		info!("Generating a first name based on the CSV file ...");
-   		println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()));
+   		println!("Generated data for first name {}",dsp.generate_by_field_name("firstname".to_string()).unwrap());
 
-   		println!("Generated data record: {:?}",dsp.generate_record());
+   		println!("Generated data record: {:?}",dsp.generate_record().unwrap());
 
     }
 }